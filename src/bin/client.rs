@@ -13,7 +13,7 @@ use std::{
     path::PathBuf,
 };
 
-use quadropong::client::{app::App, config::Config, error::ClientError};
+use quadropong::client::{app::App, config::Config, error::ClientError, net::tcp::TcpClient};
 
 fn setup_logger(log_path: PathBuf) -> Result<(), fern::InitError> {
     fern::Dispatch::new()
@@ -70,13 +70,28 @@ async fn main() -> Result<(), ClientError> {
         error!("Failed to setup logger (not critical, continuing): {}", e);
     }
 
-    let config = if let Ok(config) = Config::load_config() {
+    let mut config = if let Ok(config) = Config::load_config() {
         config
     } else {
         error!("Failed to load settings, using default one");
         Config::default()
     };
 
+    // the server knows best where it takes UDP, unless an address was built in or set in the settings
+    if config.uses_default_socket_addr() {
+        match TcpClient::new(&config.api_url).server_info().await {
+            Ok(info) => {
+                let socket_addr = info.socket_addr(&config.api_url);
+                info!("Using UDP socket address {} from the server", socket_addr);
+                config.advertised_socket_addr = Some(socket_addr);
+            }
+            Err(e) => error!(
+                "Failed to get server info, using {}: {}",
+                config.socket_addr, e
+            ),
+        }
+    }
+
     let mut terminal = setup_terminal()?;
 
     let app_running = async {
@@ -1,14 +1,25 @@
 use chrono::Utc;
-use log::{error, info};
+use log::{error, info, log, warn};
 use quadropong::common::{
-    game_loop::process_input,
-    models::{ClientInput, ClientInputWithAddr},
+    game_loop::{process_input, InputQueue, DEFAULT_MAX_QUEUED_INPUTS},
+    models::{ticks_elapsed, ClientInput, ClientInputWithAddr, GameConfig, ServerInfo},
     GameRooms,
 };
-use std::{collections::VecDeque, env, net::UdpSocket, sync::Arc, time::Duration};
+use std::{
+    env,
+    net::{SocketAddr, UdpSocket},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::{sync::Mutex, time};
 
-use quadropong::server::api::app;
+use quadropong::server::api::{
+    app, server_info_router, ws_router, AdminToken, CreationLimiter, WsClients, WsState,
+    DEFAULT_CREATE_GAME_BURST, DEFAULT_CREATE_GAME_INTERVAL,
+};
 
 fn setup_logger() -> Result<(), fern::InitError> {
     fern::Dispatch::new()
@@ -30,12 +41,36 @@ fn setup_logger() -> Result<(), fern::InitError> {
     Ok(())
 }
 
+fn env_or(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 #[tokio::main]
 async fn main() {
     let _ = setup_logger(); // Ignore logger failure
 
-    // Create a shared GameRooms instance
-    let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+    let defaults = GameConfig::default();
+    let tick_rate = env_or("TICK_RATE", defaults.tick_rate).max(1);
+    let game_config = GameConfig {
+        ping_timeout: env_or("PING_TIMEOUT", defaults.ping_timeout),
+        goal_timeout: env_or("GOAL_TIMEOUT", defaults.goal_timeout),
+        game_delete_timeout: env_or("GAME_DELETE_TIMEOUT", defaults.game_delete_timeout),
+        cleanup_interval: env_or("CLEANUP_INTERVAL", defaults.cleanup_interval).max(1),
+        idle_lobby_timeout: env_or("IDLE_LOBBY_TIMEOUT", defaults.idle_lobby_timeout),
+        tick_rate,
+        // broadcasting more often than the games change only repeats the same states
+        broadcast_rate: env_or("BROADCAST_RATE", defaults.broadcast_rate).clamp(1, tick_rate),
+        max_lobbies: env_or("MAX_LOBBIES", defaults.max_lobbies),
+        max_username_length: env_or("MAX_USERNAME_LENGTH", defaults.max_username_length as u64)
+            .max(1) as usize,
+    };
+    info!("Using {:?}", game_config);
+
+    // Set once the UDP socket is bound and the game loop is running
+    let is_ready = Arc::new(AtomicBool::new(false));
 
     let port: u16 = env::var("PORT")
         .ok()
@@ -47,10 +82,22 @@ async fn main() {
         .and_then(|p| p.parse().ok())
         .unwrap_or(34254);
 
+    // Behind NAT or Docker players reach the UDP socket on another address than it is bound to
+    let udp_bind_addr = env::var("UDP_BIND_ADDR").unwrap_or_else(|_| "0.0.0.0".to_string());
+    let server_info = ServerInfo {
+        udp_port,
+        advertised_udp_addr: env::var("ADVERTISED_UDP_ADDR").ok(),
+    };
+
+    // Create a shared GameRooms instance, joining players learn the UDP endpoint from it
+    let mut rooms = GameRooms::with_config(game_config);
+    rooms.server_info = Some(server_info.clone());
+    let game_rooms = Arc::new(Mutex::new(rooms));
+
     let addr = format!("0.0.0.0:{}", port);
 
-    let socket =
-        UdpSocket::bind(format!("0.0.0.0:{}", udp_port)).expect("Failed to bind to UDP socket");
+    let socket = UdpSocket::bind(format!("{}:{}", udp_bind_addr, udp_port))
+        .expect("Failed to bind to UDP socket");
     let _ = socket.set_nonblocking(true);
     let socket = Arc::new(socket);
 
@@ -59,8 +106,9 @@ async fn main() {
 
     let game_rooms_send = game_rooms.clone();
 
-    let message_queue: Arc<Mutex<VecDeque<ClientInputWithAddr>>> =
-        Arc::new(Mutex::new(VecDeque::new()));
+    // inputs over the cap push out the oldest ones instead of growing the queue under a flood
+    let max_queued_inputs = env_or("MAX_QUEUED_INPUTS", DEFAULT_MAX_QUEUED_INPUTS as u64) as usize;
+    let message_queue = Arc::new(Mutex::new(InputQueue::new(max_queued_inputs)));
 
     // Spawn UDP receiver task
     let message_queue_recv = message_queue.clone();
@@ -71,7 +119,7 @@ async fn main() {
                 Ok((size, addr)) => match rmp_serde::from_slice::<ClientInput>(&buf[..size]) {
                     Ok(input) => {
                         let input = ClientInputWithAddr { addr, input };
-                        message_queue_recv.lock().await.push_back(input);
+                        message_queue_recv.lock().await.push(input);
                     }
                     Err(e) => {
                         error!("Failed to deserialize UDP packet: {}", e);
@@ -87,7 +135,7 @@ async fn main() {
 
     let game_rooms_cleaner = game_rooms.clone();
     tokio::spawn(async move {
-        let mut interval = time::interval(Duration::from_secs(60));
+        let mut interval = time::interval(Duration::from_millis(game_config.cleanup_interval));
         loop {
             info!("Running game room cleaner process");
             interval.tick().await;
@@ -95,66 +143,108 @@ async fn main() {
         }
     });
 
+    // Players connected over WebSocket get their game states there instead of over UDP
+    let ws_clients = WsClients::new();
+    let ws_state = WsState {
+        game_rooms: game_rooms.clone(),
+        message_queue: message_queue.clone(),
+        clients: ws_clients.clone(),
+    };
+
     let game_rooms_loop = game_rooms.clone();
     let message_queue_loop = message_queue.clone();
+    let is_ready_loop = is_ready.clone();
     tokio::spawn(async move {
-        let mut interval = time::interval(Duration::from_millis(1000 / 60));
+        let mut interval =
+            time::interval(Duration::from_secs_f64(1.0 / game_config.tick_rate as f64));
+        let mut last_tick = Instant::now();
+        let mut dropped_inputs = 0;
+        is_ready_loop.store(true, Ordering::Release);
         loop {
             interval.tick().await;
+            // scale the physics by the real time passed, a tick may come late
+            let now = Instant::now();
+            let dt = ticks_elapsed(now.duration_since(last_tick));
+            last_tick = now;
 
             // Process all messages in the queue
             let mut queue = message_queue_loop.lock().await;
-            while let Some(input) = queue.pop_front() {
-                process_input(input.input, game_rooms_loop.clone(), input.addr).await;
+            if queue.dropped() > dropped_inputs {
+                warn!(
+                    "Input queue full, dropped {} inputs since the last tick",
+                    queue.dropped() - dropped_inputs
+                );
+                dropped_inputs = queue.dropped();
+            }
+            while let Some(input) = queue.pop() {
+                if let Err(e) =
+                    process_input(input.input, game_rooms_loop.clone(), input.addr).await
+                {
+                    log!(e.level(), "Input from {} not applied: {}", input.addr, e);
+                }
             }
 
             let mut rooms = game_rooms_loop.lock().await;
-            for game in rooms.lobbies.values_mut() {
-                game.game_tick();
-            }
+            rooms.tick_games(dt);
+            rooms.record_results();
         }
     });
 
     tokio::spawn(async move {
         // Game state broadcast loop
-        let mut interval = time::interval(Duration::from_millis(1000 / 60));
+        let mut interval = time::interval(Duration::from_secs_f64(
+            1.0 / game_config.broadcast_rate as f64,
+        ));
         loop {
             interval.tick().await;
 
-            let games = {
-                let rooms = game_rooms_send.lock().await;
-                rooms.lobbies.values().cloned().collect::<Vec<_>>()
-            };
+            let snapshots = game_rooms_send.lock().await.broadcast_snapshots();
 
             // Broadcast the game state to all players
-            for game in games {
-                match game.to_network_bytes() {
-                    Ok(serialized) => {
-                        for player in game.players.values() {
-                            if let Some(addr) = player.addr {
-                                if let Err(e) = socket.send_to(&serialized, addr) {
-                                    error!(
-                                        "Failed to send game state to player {} on {}: {}",
-                                        player.id, addr, e
-                                    );
-                                }
-                            }
-                        }
+            for snapshot in snapshots {
+                for (player_id, addr) in snapshot.recipients {
+                    if ws_clients.send(&player_id, &snapshot.bytes).await {
+                        continue;
                     }
-                    Err(e) => {
-                        error!("Failed to serialize game state: {}", e);
+                    if let Some(addr) = addr {
+                        if let Err(e) = socket.send_to(&snapshot.bytes, addr) {
+                            error!(
+                                "Failed to send game state to player {} on {}: {}",
+                                player_id, addr, e
+                            );
+                        }
                     }
                 }
             }
         }
     });
 
+    // games a single address may create at once, and how often it gets another one
+    let creation_limiter = CreationLimiter::new(
+        env_or("CREATE_GAME_BURST", DEFAULT_CREATE_GAME_BURST as u64) as u32,
+        Duration::from_millis(env_or(
+            "CREATE_GAME_INTERVAL",
+            DEFAULT_CREATE_GAME_INTERVAL.as_millis() as u64,
+        )),
+    );
+
+    // removing games is left to whoever knows the token, when one is set
+    let admin_token = AdminToken::new(env::var("ADMIN_TOKEN").ok());
+
     let listener = tokio::net::TcpListener::bind(addr).await;
 
     match listener {
         Ok(listener) => {
             info!("Listening on {}", listener.local_addr().unwrap());
-            axum::serve(listener, app(game_rooms)).await.unwrap();
+            let app = app(game_rooms, is_ready, creation_limiter, admin_token)
+                .merge(ws_router(ws_state))
+                .merge(server_info_router(server_info));
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
         }
         Err(e) => {
             error!("Failed to start listening: {}", e);
@@ -1,16 +1,35 @@
 use crossterm::event::{Event, EventStream, KeyEvent};
 use futures_util::TryStreamExt;
 use ratatui::{prelude::Backend, Terminal};
-use std::{sync::Arc, thread::sleep, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::{self, sync::Mutex, task, time::Instant};
 use tokio_util::sync::CancellationToken;
 
 use super::{
     config::Config,
     error::ClientError,
-    states::{menu::Menu, quit::Quit, traits::State},
+    states::{
+        menu::Menu,
+        quit::Quit,
+        traits::State,
+        utils::{
+            debug_overlay::FpsCounter,
+            render::{is_large_enough, render_too_small},
+        },
+    },
 };
 
+/// Time left of a frame at `fps` after drawing took `draw_elapsed`, nothing when the draw ran over
+pub fn frame_sleep(fps: u32, draw_elapsed: Duration) -> Duration {
+    (Duration::from_secs(1) / fps.max(1)).saturating_sub(draw_elapsed)
+}
+
 pub struct App<'a, B: Backend> {
     current_state: Arc<Mutex<Box<dyn State>>>,
     config: Arc<Mutex<Config>>,
@@ -33,6 +52,9 @@ impl<'a, B: Backend> App<'a, B> {
         let update_state = Arc::clone(&self.current_state);
         let cancellation_token_clone = self.cancellation_token.clone();
         let update_settings = Arc::clone(&self.config);
+        // set on a resize, the next frame is drawn on a cleared screen
+        let resized = Arc::new(AtomicBool::new(false));
+        let resized_clone = Arc::clone(&resized);
         let update_handle = task::spawn(async move {
             let mut reader = EventStream::new();
             let mut last_key_event_time = Instant::now();
@@ -61,6 +83,9 @@ impl<'a, B: Backend> App<'a, B> {
                                     input = Some(key_event.code);
                                 }
                             }
+                            Ok(Some(Event::Resize(..))) => {
+                                resized_clone.store(true, Ordering::Relaxed);
+                            }
                             Err(e) => return Err(e.into()),
                             _ => {}
                         }
@@ -92,6 +117,8 @@ impl<'a, B: Backend> App<'a, B> {
             Ok(())
         });
 
+        let mut fps_counter = FpsCounter::new(std::time::Instant::now());
+
         // Main render loop
         loop {
             // Check for cancellation
@@ -99,15 +126,30 @@ impl<'a, B: Backend> App<'a, B> {
                 break;
             }
 
-            // Lock the state and render (release the lock as soon as possible)
-            {
-                let current_state = self.current_state.lock().await;
-                self.terminal.draw(|f| current_state.render(f))?;
+            let frame_start = Instant::now();
+
+            // leftovers of the old layout would otherwise stay on screen until overdrawn
+            if resized.swap(false, Ordering::Relaxed) {
+                self.terminal.autoresize()?;
+                self.terminal.clear()?;
             }
+
+            // Lock the state and render (release the lock as soon as possible)
             {
-                let fps = self.config.lock().await.fps;
-                sleep(Duration::from_secs(1) / fps);
+                let mut current_state = self.current_state.lock().await;
+                current_state.set_measured_fps(fps_counter.frame(std::time::Instant::now()));
+                self.terminal.draw(|f| {
+                    if is_large_enough(f.area()) {
+                        current_state.render(f)
+                    } else {
+                        render_too_small(f)
+                    }
+                })?;
             }
+
+            // only the rest of the frame is slept, so slow draws don't lower the frame rate
+            let fps = self.config.lock().await.capped_fps();
+            tokio::time::sleep(frame_sleep(fps, frame_start.elapsed())).await;
         }
 
         // Wait for the update task to finish
@@ -116,3 +158,20 @@ impl<'a, B: Backend> App<'a, B> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_sleep() {
+        assert_eq!(frame_sleep(50, Duration::ZERO), Duration::from_millis(20));
+        assert_eq!(
+            frame_sleep(50, Duration::from_millis(5)),
+            Duration::from_millis(15)
+        );
+        // a draw longer than the frame starts the next one right away
+        assert_eq!(frame_sleep(50, Duration::from_millis(30)), Duration::ZERO);
+        assert_eq!(frame_sleep(0, Duration::ZERO), Duration::from_secs(1));
+    }
+}
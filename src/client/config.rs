@@ -3,23 +3,49 @@ use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use std::{fs, io, path};
 
+use crate::common::PlayerPosition;
+
+use super::key_bindings::KeyBindings;
+use super::palette::{ColorPalette, PositionColors};
 use super::states::{
     settings::Options,
+    training::TrainingConfig,
     utils::widget::{get_widget_text, Widget},
 };
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Config {
-    #[serde(skip_serializing, skip_deserializing)]
     #[serde(default = "default_api_addr")]
     pub api_url: String,
-    #[serde(skip_serializing, skip_deserializing)]
     #[serde(default = "default_socket_addr")]
     pub socket_addr: String,
     pub player_name: String,
     pub player_color: Color,
-    pub other_players_color: Color,
+    #[serde(default)]
+    pub color_palette: ColorPalette,
+    #[serde(default)]
+    pub position_colors: PositionColors,
     pub fps: u32,
+    #[serde(default)]
+    pub reduced_motion: bool,
+    #[serde(default)]
+    pub key_bindings: KeyBindings,
+    #[serde(default)]
+    pub debug_overlay: bool, // allows toggling the FPS/latency overlay and the server paddle in game with F3
+    #[serde(default)]
+    pub training: TrainingConfig,
+    #[serde(default)]
+    pub record_replays: bool, // saves every online game to the replays directory
+    #[serde(default = "default_ball_trail")]
+    pub ball_trail: usize, // positions drawn behind the ball, 0 or reduced_motion turns the trail off
+    #[serde(default)]
+    pub use_websocket: bool, // game traffic over the API's WebSocket, for networks blocking UDP
+    #[serde(default)]
+    pub mirror_controls_for_far_sides: bool, // Top and Right paddles move as seen from their seat
+    #[serde(default)]
+    pub enable_sound: bool, // rings the terminal bell on goals and our deflections
+    #[serde(skip)]
+    pub advertised_socket_addr: Option<String>, // where the server takes UDP, never saved
 }
 
 impl Default for Config {
@@ -29,24 +55,68 @@ impl Default for Config {
             api_url: default_api_addr(),
             socket_addr: default_socket_addr(),
             player_color: Color::Green,
-            other_players_color: Color::White,
+            color_palette: ColorPalette::default(),
+            position_colors: PositionColors::default(),
             fps: 60,
+            reduced_motion: false,
+            key_bindings: KeyBindings::default(),
+            debug_overlay: false,
+            training: TrainingConfig::default(),
+            record_replays: false,
+            ball_trail: default_ball_trail(),
+            use_websocket: false,
+            mirror_controls_for_far_sides: false,
+            enable_sound: false,
+            advertised_socket_addr: None,
         }
     }
 }
 
+/// Range of the frame rate the client renders at, the lower end also keeps it from dividing by 0
+pub const FPS_RANGE: (u32, u32) = (1, 240);
+
 pub fn default_api_addr() -> String {
     option_env!("API_URL")
         .unwrap_or_else(|| "http://127.0.0.1:3000")
         .to_string()
 }
 
+fn default_ball_trail() -> usize {
+    4
+}
+
 pub fn default_socket_addr() -> String {
     option_env!("SOCKET_ADDR")
         .unwrap_or_else(|| "127.0.0.1:34254")
         .to_string()
 }
 
+/// Accepts an `http(s)://host[:port]` address of the server's API
+pub fn validate_api_url(url: &str) -> Result<(), &'static str> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| "not a valid URL")?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("must start with http:// or https://");
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err("missing the host");
+    }
+    Ok(())
+}
+
+/// Accepts a `host:port` UDP address the way `UdpClient::new` reads it
+pub fn validate_socket_addr(addr: &str) -> Result<(), &'static str> {
+    let Some((host, port)) = addr.split_once(':') else {
+        return Err("must be host:port");
+    };
+    if host.is_empty() || port.contains(':') {
+        return Err("must be host:port");
+    }
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(()),
+        _ => Err("invalid port number"),
+    }
+}
+
 impl Config {
     pub fn to_vec(&self) -> Vec<String> {
         vec![
@@ -54,11 +124,37 @@ impl Config {
             self.api_url.clone(),
             self.socket_addr.clone(),
             self.player_color.to_string(),
-            self.other_players_color.to_string(),
+            format!("{:?}", self.color_palette),
             self.fps.to_string(),
+            self.reduced_motion.to_string(),
         ]
     }
 
+    /// Whether the UDP address was neither built in nor changed in the settings
+    pub fn uses_default_socket_addr(&self) -> bool {
+        option_env!("SOCKET_ADDR").is_none() && self.socket_addr == default_socket_addr()
+    }
+
+    /// Where to send UDP, the address the server advertised unless the user set one
+    pub fn udp_addr(&self) -> &str {
+        match &self.advertised_socket_addr {
+            Some(addr) if self.uses_default_socket_addr() => addr,
+            _ => &self.socket_addr,
+        }
+    }
+
+    /// The configured frame rate within `FPS_RANGE`, the file may hold anything
+    pub fn capped_fps(&self) -> u32 {
+        self.fps.clamp(FPS_RANGE.0, FPS_RANGE.1)
+    }
+
+    /// Color of the player on the given position, the override from the config wins over the palette
+    pub fn position_color(&self, position: PlayerPosition) -> Color {
+        self.position_colors
+            .get(position)
+            .unwrap_or_else(|| self.color_palette.color(position))
+    }
+
     pub fn get_log_path() -> Option<path::PathBuf> {
         if let Some(mut log_dir) = dirs::data_local_dir() {
             log_dir.push("quadropong");
@@ -73,6 +169,17 @@ impl Config {
         }
     }
 
+    pub fn get_replays_dir() -> Option<path::PathBuf> {
+        if let Some(mut replays_dir) = dirs::data_local_dir() {
+            replays_dir.push("quadropong");
+            replays_dir.push("replays");
+            fs::create_dir_all(&replays_dir).ok()?;
+            Some(replays_dir)
+        } else {
+            None
+        }
+    }
+
     pub fn get_config_path() -> Option<path::PathBuf> {
         if let Some(mut config_dir) = dirs::config_local_dir() {
             config_dir.push("quadropong");
@@ -151,16 +258,84 @@ impl Config {
                     self.player_color = slider.clone().get_color();
                 }
             }
-            Options::OtherPlayersColor(widget) => {
-                if let Widget::Slider(slider) = widget {
-                    self.other_players_color = slider.clone().get_color();
+            Options::ColorblindPalette(widget) => {
+                if let Widget::Toggle(toggle) = widget {
+                    self.color_palette = if toggle.value {
+                        ColorPalette::Colorblind
+                    } else {
+                        ColorPalette::Default
+                    };
                 }
             }
             Options::FPS(widget) => {
-                if let Ok(number) = get_widget_text(widget).parse() {
-                    self.fps = number;
+                if let Ok(number) = get_widget_text(widget).parse::<u32>() {
+                    self.fps = number.clamp(FPS_RANGE.0, FPS_RANGE.1);
+                }
+            }
+            Options::ReducedMotion(widget) => {
+                if let Widget::Toggle(toggle) = widget {
+                    self.reduced_motion = toggle.value;
+                }
+            }
+            Options::KeyBindings(widget) => {
+                if let Widget::KeyBindings(editor) = widget {
+                    self.key_bindings = editor.bindings.clone();
+                }
+            }
+            Options::ServerUrl(widget) => {
+                let url = get_widget_text(widget);
+                if validate_api_url(&url).is_ok() {
+                    self.api_url = url;
+                }
+            }
+            Options::UdpAddress(widget) => {
+                let addr = get_widget_text(widget);
+                if validate_socket_addr(&addr).is_ok() {
+                    self.socket_addr = addr;
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_api_url() {
+        assert!(validate_api_url("http://127.0.0.1:3000").is_ok());
+        assert!(validate_api_url("https://quadropong.example").is_ok());
+
+        assert!(validate_api_url("").is_err());
+        assert!(validate_api_url("127.0.0.1:3000").is_err());
+        assert!(validate_api_url("ftp://quadropong.example").is_err());
+        assert!(validate_api_url("http://").is_err());
+    }
+
+    #[test]
+    fn test_capped_fps() {
+        let config = |fps| Config {
+            fps,
+            ..Default::default()
+        };
+        assert_eq!(config(60).capped_fps(), 60);
+        assert_eq!(config(0).capped_fps(), FPS_RANGE.0);
+        assert_eq!(config(10_000).capped_fps(), FPS_RANGE.1);
+    }
+
+    #[test]
+    fn test_validate_socket_addr() {
+        assert!(validate_socket_addr("127.0.0.1:34254").is_ok());
+        assert!(validate_socket_addr("quadropong.example:34254").is_ok());
+
+        assert!(validate_socket_addr("").is_err());
+        assert!(validate_socket_addr("127.0.0.1").is_err());
+        assert!(validate_socket_addr(":34254").is_err());
+        assert!(validate_socket_addr("127.0.0.1:port").is_err());
+        assert!(validate_socket_addr("127.0.0.1:70000").is_err());
+        assert!(validate_socket_addr("127.0.0.1:0").is_err());
+        // UdpClient splits on every colon, so IPv6 and schemes are rejected
+        assert!(validate_socket_addr("udp://127.0.0.1:34254").is_err());
+    }
+}
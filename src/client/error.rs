@@ -1,6 +1,6 @@
 use std::sync::PoisonError;
 
-use super::net::error::{TcpError, UdpError};
+use super::net::error::{TcpError, UdpError, WsError};
 use thiserror::Error;
 use tokio::task::JoinError;
 
@@ -12,6 +12,8 @@ pub enum ClientError {
     IoError(#[from] std::io::Error),
     #[error("Sync error: {0}")]
     SyncError(String),
+    #[error("Replay error: {0}")]
+    ReplayError(String),
 }
 
 impl From<TcpError> for ClientError {
@@ -26,6 +28,12 @@ impl From<UdpError> for ClientError {
     }
 }
 
+impl From<WsError> for ClientError {
+    fn from(error: WsError) -> Self {
+        ClientError::NetError(format!("WsError: {}", error))
+    }
+}
+
 impl From<JoinError> for ClientError {
     fn from(error: JoinError) -> Self {
         ClientError::SyncError(format!("JoinError: {}", error))
@@ -0,0 +1,186 @@
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Ready,
+    Leave,
+    AddBot,
+    RemoveBot,
+    ReadyBots,
+    CycleFocus,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 11] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Ready,
+        Action::Leave,
+        Action::AddBot,
+        Action::RemoveBot,
+        Action::ReadyBots,
+        Action::CycleFocus,
+        Action::Quit,
+    ];
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match self {
+            Action::MoveUp => "move up",
+            Action::MoveDown => "move down",
+            Action::MoveLeft => "move left",
+            Action::MoveRight => "move right",
+            Action::Ready => "ready",
+            Action::Leave => "leave",
+            Action::AddBot => "add bot",
+            Action::RemoveBot => "remove bot",
+            Action::ReadyBots => "ready bots",
+            Action::CycleFocus => "cycle focus",
+            Action::Quit => "quit",
+        };
+        write!(f, "{}", action)
+    }
+}
+
+/// Keys bound to each action, letters match regardless of case
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_up: Vec<KeyCode>,
+    pub move_down: Vec<KeyCode>,
+    pub move_left: Vec<KeyCode>,
+    pub move_right: Vec<KeyCode>,
+    pub ready: Vec<KeyCode>,
+    pub leave: Vec<KeyCode>,
+    pub add_bot: Vec<KeyCode>,
+    pub remove_bot: Vec<KeyCode>,
+    pub ready_bots: Vec<KeyCode>,
+    pub cycle_focus: Vec<KeyCode>, // moves the highlight to the next player on the board
+    pub quit: Vec<KeyCode>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            move_up: vec![KeyCode::Up, KeyCode::Char('w')],
+            move_down: vec![KeyCode::Down, KeyCode::Char('s')],
+            move_left: vec![KeyCode::Left, KeyCode::Char('a')],
+            move_right: vec![KeyCode::Right, KeyCode::Char('d')],
+            ready: vec![KeyCode::Enter],
+            leave: vec![KeyCode::Esc],
+            add_bot: vec![KeyCode::Char('a')],
+            remove_bot: vec![KeyCode::Char('d')],
+            ready_bots: vec![KeyCode::Char('b')],
+            cycle_focus: vec![KeyCode::Tab],
+            quit: vec![KeyCode::Char('q')],
+        }
+    }
+}
+
+fn normalize(key: KeyCode) -> KeyCode {
+    match key {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        key => key,
+    }
+}
+
+impl KeyBindings {
+    pub fn keys(&self, action: Action) -> &Vec<KeyCode> {
+        match action {
+            Action::MoveUp => &self.move_up,
+            Action::MoveDown => &self.move_down,
+            Action::MoveLeft => &self.move_left,
+            Action::MoveRight => &self.move_right,
+            Action::Ready => &self.ready,
+            Action::Leave => &self.leave,
+            Action::AddBot => &self.add_bot,
+            Action::RemoveBot => &self.remove_bot,
+            Action::ReadyBots => &self.ready_bots,
+            Action::CycleFocus => &self.cycle_focus,
+            Action::Quit => &self.quit,
+        }
+    }
+
+    pub fn keys_mut(&mut self, action: Action) -> &mut Vec<KeyCode> {
+        match action {
+            Action::MoveUp => &mut self.move_up,
+            Action::MoveDown => &mut self.move_down,
+            Action::MoveLeft => &mut self.move_left,
+            Action::MoveRight => &mut self.move_right,
+            Action::Ready => &mut self.ready,
+            Action::Leave => &mut self.leave,
+            Action::AddBot => &mut self.add_bot,
+            Action::RemoveBot => &mut self.remove_bot,
+            Action::ReadyBots => &mut self.ready_bots,
+            Action::CycleFocus => &mut self.cycle_focus,
+            Action::Quit => &mut self.quit,
+        }
+    }
+
+    pub fn is(&self, action: Action, key: KeyCode) -> bool {
+        let key = normalize(key);
+        self.keys(action)
+            .iter()
+            .any(|bound| normalize(*bound) == key)
+    }
+
+    /// Replaces all keys of the action with a single one
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        *self.keys_mut(action) = vec![normalize(key)];
+    }
+
+    /// Label of the first key bound to the action, used in key hints
+    pub fn label(&self, action: Action) -> String {
+        match self.keys(action).first() {
+            Some(KeyCode::Char(c)) if *c != ' ' => c.to_ascii_uppercase().to_string(),
+            Some(key) => key.to_string(),
+            None => "-".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings() {
+        let bindings = KeyBindings::default();
+        assert!(bindings.is(Action::MoveUp, KeyCode::Up));
+        assert!(bindings.is(Action::MoveUp, KeyCode::Char('W')));
+        assert!(!bindings.is(Action::MoveUp, KeyCode::Down));
+        assert_eq!(bindings.label(Action::AddBot), "A");
+        assert_eq!(bindings.label(Action::Leave), "Esc");
+    }
+
+    #[test]
+    fn test_custom_binding() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind(Action::MoveUp, KeyCode::Char('I'));
+
+        assert!(bindings.is(Action::MoveUp, KeyCode::Char('i')));
+        assert!(bindings.is(Action::MoveUp, KeyCode::Char('I')));
+        assert!(!bindings.is(Action::MoveUp, KeyCode::Up));
+        assert_eq!(bindings.label(Action::MoveUp), "I");
+    }
+
+    #[test]
+    fn test_bindings_from_config() {
+        let bindings: KeyBindings =
+            serde_json::from_str(r#"{ "ready": ["Tab"], "quit": [{ "Char": "x" }] }"#).unwrap();
+
+        assert!(bindings.is(Action::Ready, KeyCode::Tab));
+        assert!(bindings.is(Action::Quit, KeyCode::Char('x')));
+        // missing actions keep their defaults
+        assert!(bindings.is(Action::Leave, KeyCode::Esc));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod app;
 pub mod config;
 pub mod error;
+pub mod key_bindings;
 pub mod net;
+pub mod palette;
 pub mod states;
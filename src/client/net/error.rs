@@ -31,3 +31,15 @@ pub enum UdpError {
     #[error("Invalid source")]
     InvalidSource,
 }
+
+#[derive(Debug, Error)]
+pub enum WsError {
+    #[error("WebSocket error: {0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] RmpSerdeEncodeError),
+    #[error("MessagePack deserialization error: {0}")]
+    MsgPackDeserialization(#[from] RmpSerdeDecodeError),
+    #[error("Connection closed")]
+    Closed,
+}
@@ -1,3 +1,5 @@
 pub mod error;
 pub mod tcp;
+pub mod transport;
 pub mod udp;
+pub mod ws;
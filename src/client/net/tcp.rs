@@ -1,15 +1,37 @@
-use log::info;
-use reqwest::Client;
+use std::time::Duration;
+
+use log::{info, warn};
+use reqwest::{Client, RequestBuilder, Response};
 use serde_json;
 use uuid::Uuid;
 
-use crate::common::{Game, JoinGameRequest, Player};
+use crate::common::models::{QuickMatchRequest, QuickMatchResponse, ServerInfo};
+use crate::common::{Game, JoinGameRequest, JoinGameResponse, Player};
 
 use super::error::TcpError;
 
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often a request that failed for a transient reason is sent again
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration, // doubled after every failed attempt
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+        }
+    }
+}
+
 pub struct TcpClient {
     server_addr: String,
     client: Client,
+    retry: RetryPolicy,
 }
 
 impl TcpClient {
@@ -17,28 +39,61 @@ impl TcpClient {
         TcpClient {
             server_addr: server_addr.to_string(),
             client: Client::new(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sends the request built by `request` until it succeeds, retrying failed connections,
+    /// and timeouts and 5xx responses too when the request is `idempotent`. The server may have
+    /// acted on a request it did not answer, so that one is not sent twice.
+    /// A 4xx response is final right away
+    async fn send_with_retry(
+        &self,
+        idempotent: bool,
+        request: impl Fn() -> RequestBuilder,
+    ) -> Result<Response, TcpError> {
+        let mut delay = self.retry.base_delay;
+        let mut attempt = 1;
+        loop {
+            let last_attempt = attempt >= self.retry.max_attempts;
+            match request().timeout(REQUEST_TIMEOUT).send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response)
+                    if last_attempt || !idempotent || !response.status().is_server_error() =>
+                {
+                    return Err(TcpError::ServerError(format!(
+                        "Server returned status code: {}",
+                        response.status()
+                    )));
+                }
+                Err(e) if last_attempt || !(e.is_connect() || idempotent && e.is_timeout()) => {
+                    return Err(TcpError::FailedToSendRequest(e));
+                }
+                Ok(response) => warn!(
+                    "Attempt {} failed with status code {}, retrying",
+                    attempt,
+                    response.status()
+                ),
+                Err(e) => warn!("Attempt {} failed: {}, retrying", attempt, e),
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
         }
     }
 
     pub async fn create_game(&self) -> Result<Game, TcpError> {
         let url = format!("{}/game", self.server_addr);
 
-        // Send the request and handle potential errors
+        // Send the request, retrying only connections that failed before it went out
         let response = self
-            .client
-            .post(&url)
-            .timeout(std::time::Duration::from_secs(5))
-            .send()
-            .await
-            .map_err(TcpError::FailedToSendRequest)?;
-
-        // Check if the response status is successful
-        if !response.status().is_success() {
-            return Err(TcpError::ServerError(format!(
-                "Server returned status code: {}",
-                response.status()
-            )));
-        }
+            .send_with_retry(false, || self.client.post(&url))
+            .await?;
 
         // Read the response body and handle potential errors
         let response_text = response
@@ -55,11 +110,129 @@ impl TcpClient {
     pub async fn get_game(&self, game_id: Uuid) -> Result<Game, TcpError> {
         let url = format!("{}/game/{}", self.server_addr, game_id);
 
+        // Send the request, retrying transient failures
+        let response = self.send_with_retry(true, || self.client.get(&url)).await?;
+
+        // Read the response body and handle potential errors
+        let response_text = response
+            .text()
+            .await
+            .map_err(TcpError::FailedToReadResponse)?;
+
+        // Deserialize the response and handle potential errors
+        let game: Game = serde_json::from_str(&response_text)?;
+
+        Ok(game)
+    }
+
+    pub async fn list_games(&self) -> Result<Vec<Game>, TcpError> {
+        let url = format!("{}/game", self.server_addr);
+
+        // Send the request, retrying transient failures
+        let response = self.send_with_retry(true, || self.client.get(&url)).await?;
+
+        // Read the response body and handle potential errors
+        let response_text = response
+            .text()
+            .await
+            .map_err(TcpError::FailedToReadResponse)?;
+
+        // Deserialize the response and handle potential errors
+        let games: Vec<Game> = serde_json::from_str(&response_text)?;
+
+        Ok(games)
+    }
+
+    /// Where the server takes game traffic over UDP
+    pub async fn server_info(&self) -> Result<ServerInfo, TcpError> {
+        let url = format!("{}/server/info", self.server_addr);
+
+        // Send the request, retrying transient failures
+        let response = self.send_with_retry(true, || self.client.get(&url)).await?;
+
+        // Read the response body and handle potential errors
+        let response_text = response
+            .text()
+            .await
+            .map_err(TcpError::FailedToReadResponse)?;
+
+        // Deserialize the response and handle potential errors
+        let info: ServerInfo = serde_json::from_str(&response_text)?;
+
+        Ok(info)
+    }
+
+    pub async fn get_player(&self, game_id: Uuid, player_id: Uuid) -> Result<Player, TcpError> {
+        let url = format!("{}/game/{}/player/{}", self.server_addr, game_id, player_id);
+
+        // Send the request, retrying transient failures
+        let response = self.send_with_retry(true, || self.client.get(&url)).await?;
+
+        // Read the response body and handle potential errors
+        let response_text = response
+            .text()
+            .await
+            .map_err(TcpError::FailedToReadResponse)?;
+
+        // Deserialize the response and handle potential errors
+        let player: Player = serde_json::from_str(&response_text)?;
+
+        Ok(player)
+    }
+
+    /// `password` is only checked by private games
+    pub async fn join_game(
+        &self,
+        game_id: Uuid,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Result<JoinGameResponse, TcpError> {
+        let url = format!("{}/game/{}/join", self.server_addr, game_id);
+        let payload_json = serde_json::to_string(&JoinGameRequest {
+            username,
+            preferred_position: None,
+            password,
+        })?;
+
+        // Send the request, retrying only connections that failed before it went out
+        let response = self
+            .send_with_retry(false, || {
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .body(payload_json.clone())
+            })
+            .await?;
+
+        // Read the response body and handle potential errors
+        let response_text = response
+            .text()
+            .await
+            .map_err(TcpError::FailedToReadResponse)?;
+
+        // Deserialize the response and handle potential errors
+        let joined: JoinGameResponse = serde_json::from_str(&response_text)?;
+
+        Ok(joined)
+    }
+
+    /// Joins any open game, the server creates one when there is none.
+    /// Not retried, a retry after a lost reply would take a second seat
+    pub async fn quick_match(
+        &self,
+        username: Option<String>,
+    ) -> Result<QuickMatchResponse, TcpError> {
+        let url = format!("{}/quickmatch", self.server_addr);
+        info!("Sending request to {}", url);
+        let payload_json = serde_json::to_string(&QuickMatchRequest { username })?;
+
         // Send the request and handle potential errors
         let response = self
             .client
-            .get(&url)
-            .timeout(std::time::Duration::from_secs(5))
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .body(payload_json)
+            .timeout(REQUEST_TIMEOUT)
             .send()
             .await
             .map_err(TcpError::FailedToSendRequest)?;
@@ -79,26 +252,20 @@ impl TcpClient {
             .map_err(TcpError::FailedToReadResponse)?;
 
         // Deserialize the response and handle potential errors
-        let game: Game = serde_json::from_str(&response_text)?;
+        let matched: QuickMatchResponse = serde_json::from_str(&response_text)?;
 
-        Ok(game)
+        Ok(matched)
     }
 
-    pub async fn join_game(
-        &self,
-        game_id: Uuid,
-        username: Option<String>,
-    ) -> Result<Player, TcpError> {
-        let url = format!("{}/game/{}/join", self.server_addr, game_id);
-        let payload_json = serde_json::to_string(&JoinGameRequest { username })?;
+    pub async fn add_bot(&self, game_id: Uuid) -> Result<Player, TcpError> {
+        let url = format!("{}/game/{}/add_bot", self.server_addr, game_id);
+        info!("Sending request to {}", url);
 
         // Send the request and handle potential errors
         let response = self
             .client
             .post(&url)
-            .timeout(std::time::Duration::from_secs(5))
-            .header("Content-Type", "application/json")
-            .body(payload_json)
+            .timeout(REQUEST_TIMEOUT)
             .send()
             .await
             .map_err(TcpError::FailedToSendRequest)?;
@@ -123,15 +290,16 @@ impl TcpClient {
         Ok(player)
     }
 
-    pub async fn add_bot(&self, game_id: Uuid) -> Result<Player, TcpError> {
-        let url = format!("{}/game/{}/add_bot", self.server_addr, game_id);
+    /// Adds bots until the game is full, returns the ones added
+    pub async fn fill_bots(&self, game_id: Uuid) -> Result<Vec<Player>, TcpError> {
+        let url = format!("{}/game/{}/fill_bots", self.server_addr, game_id);
         info!("Sending request to {}", url);
 
         // Send the request and handle potential errors
         let response = self
             .client
             .post(&url)
-            .timeout(std::time::Duration::from_secs(5))
+            .timeout(REQUEST_TIMEOUT)
             .send()
             .await
             .map_err(TcpError::FailedToSendRequest)?;
@@ -151,9 +319,9 @@ impl TcpClient {
             .map_err(TcpError::FailedToReadResponse)?;
 
         // Deserialize the response and handle potential errors
-        let player: Player = serde_json::from_str(&response_text)?;
+        let bots: Vec<Player> = serde_json::from_str(&response_text)?;
 
-        Ok(player)
+        Ok(bots)
     }
 
     pub async fn remove_bot(&self, game_id: Uuid) -> Result<(), TcpError> {
@@ -164,7 +332,7 @@ impl TcpClient {
         let response = self
             .client
             .post(&url)
-            .timeout(std::time::Duration::from_secs(5))
+            .timeout(REQUEST_TIMEOUT)
             .send()
             .await
             .map_err(TcpError::FailedToSendRequest)?;
@@ -180,19 +348,25 @@ impl TcpClient {
         Ok(())
     }
 
+    /// `password` is only checked by private games
     pub async fn play_again(
         &self,
         game_id: Uuid,
         username: Option<String>,
-    ) -> Result<Player, TcpError> {
+        password: Option<String>,
+    ) -> Result<JoinGameResponse, TcpError> {
         let url = format!("{}/game/{}/play_again", self.server_addr, game_id);
-        let payload_json = serde_json::to_string(&JoinGameRequest { username })?;
+        let payload_json = serde_json::to_string(&JoinGameRequest {
+            username,
+            preferred_position: None,
+            password,
+        })?;
 
         // Send the request and handle potential errors
         let response = self
             .client
             .post(&url)
-            .timeout(std::time::Duration::from_secs(5))
+            .timeout(REQUEST_TIMEOUT)
             .header("Content-Type", "application/json")
             .body(payload_json)
             .send()
@@ -214,9 +388,9 @@ impl TcpClient {
             .map_err(TcpError::FailedToReadResponse)?;
 
         // Deserialize the response and handle potential errors
-        let player: Player = serde_json::from_str(&response_text)?;
+        let joined: JoinGameResponse = serde_json::from_str(&response_text)?;
 
-        Ok(player)
+        Ok(joined)
     }
 }
 #[cfg(test)]
@@ -224,10 +398,18 @@ mod tests {
     use crate::common::models::GameState;
 
     use super::*;
-    use mockito::Server;
+    use mockito::{Matcher, Server};
     use serde_json::json;
     use uuid::Uuid;
 
+    /// Client retrying right away, so the tests don't wait for the backoff
+    fn fast_retrying_client(url: &str) -> TcpClient {
+        TcpClient::new(url).with_retry(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+        })
+    }
+
     #[tokio::test]
     async fn test_create_game_success() {
         let mut server = Server::new_async().await;
@@ -266,12 +448,14 @@ mod tests {
         let mock = server
             .mock("POST", "/game")
             .with_status(500)
+            .expect(1)
             .create_async()
             .await;
 
-        let client = TcpClient::new(&server.url());
+        let client = fast_retrying_client(&server.url());
         let result = client.create_game().await;
 
+        // the game may have been created before the server failed, so it is not sent again
         mock.assert();
         assert!(matches!(result, Err(TcpError::ServerError(_))));
     }
@@ -328,6 +512,62 @@ mod tests {
         assert_eq!(game.state, GameState::Active);
     }
 
+    #[tokio::test]
+    async fn test_get_game_retried_until_success() {
+        let mut server = Server::new_async().await;
+        let game_id = Uuid::new_v4();
+        let path = format!("/game/{}", game_id);
+        let failing = server
+            .mock("GET", path.as_str())
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let succeeding = server
+            .mock("GET", path.as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": game_id,
+                    "players": {},
+                    "state": "WaitingForPlayers",
+                    "created_at": "2023-10-01T12:34:56Z",
+                    "started_at": null,
+                    "ball": null,
+                    "last_goal_at": null
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = fast_retrying_client(&server.url());
+        let game = client.get_game(game_id).await.unwrap();
+
+        failing.assert();
+        succeeding.assert();
+        assert_eq!(game.id, game_id);
+    }
+
+    #[tokio::test]
+    async fn test_client_error_not_retried() {
+        let mut server = Server::new_async().await;
+        let game_id = Uuid::new_v4();
+        let mock = server
+            .mock("POST", format!("/game/{}/join", game_id).as_str())
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = fast_retrying_client(&server.url());
+        let result = client.join_game(game_id, None, None).await;
+
+        mock.assert();
+        assert!(matches!(result, Err(TcpError::ServerError(_))));
+    }
+
     #[tokio::test]
     async fn test_get_game_not_found() {
         let mut server = Server::new_async().await;
@@ -345,6 +585,193 @@ mod tests {
         assert!(matches!(result, Err(TcpError::ServerError(_))));
     }
 
+    #[tokio::test]
+    async fn test_list_games_success() {
+        let mut server = Server::new_async().await;
+        let (waiting_id, active_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let player_id = Uuid::new_v4();
+        let mock = server
+            .mock("GET", "/game")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!([
+                    {
+                        "id": waiting_id,
+                        "players": {
+                            player_id.to_string(): {
+                                "id": player_id,
+                                "name": "alice",
+                                "joined_at": "2023-10-01T12:34:56Z",
+                                "ping_timestamp": null,
+                                "score": 0,
+                                "addr": null,
+                                "position": "Top",
+                                "paddle_position": 5.0,
+                                "paddle_delta": 0.3,
+                                "paddle_width": 1.0,
+                                "is_ready": false,
+                                "is_ai": false
+                            }
+                        },
+                        "state": "WaitingForPlayers",
+                        "created_at": "2023-10-01T12:34:56Z",
+                        "started_at": null,
+                        "ball": null,
+                        "last_goal_at": null
+                    },
+                    {
+                        "id": active_id,
+                        "players": {},
+                        "state": "Active",
+                        "created_at": "2023-10-01T12:34:56Z",
+                        "started_at": "2023-10-01T12:35:00Z",
+                        "ball": null,
+                        "last_goal_at": null
+                    }
+                ])
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.list_games().await;
+
+        mock.assert();
+        let games = result.unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].id, waiting_id);
+        assert_eq!(games[0].players[&player_id].name, "alice");
+        assert_eq!(games[1].state, GameState::Active);
+    }
+
+    #[tokio::test]
+    async fn test_list_games_invalid_response() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/game")
+            .with_status(200)
+            .with_body("{}")
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.list_games().await;
+
+        mock.assert();
+        assert!(matches!(
+            result,
+            Err(TcpError::FailedToDeserializeResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_server_info_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/server/info")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "udp_port": 40000,
+                    "advertised_udp_addr": null
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.server_info().await;
+
+        mock.assert();
+        let info = result.unwrap();
+        assert_eq!(info.udp_port, 40000);
+        assert_eq!(info.advertised_udp_addr, None);
+        assert_eq!(info.socket_addr(&server.url()), "127.0.0.1:40000");
+    }
+
+    #[tokio::test]
+    async fn test_server_info_not_found() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/server/info")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.server_info().await;
+
+        mock.assert();
+        assert!(matches!(result, Err(TcpError::ServerError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_player_success() {
+        let mut server = Server::new_async().await;
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+        let mock = server
+            .mock(
+                "GET",
+                format!("/game/{}/player/{}", game_id, player_id).as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": player_id,
+                    "name": "test_user",
+                    "joined_at": "2023-10-01T12:34:56Z",
+                    "ping_timestamp": null,
+                    "score": 3,
+                    "addr": null,
+                    "position": "Left",
+                    "paddle_position": 0.5,
+                    "paddle_delta": 0.0,
+                    "paddle_width": 0.2,
+                    "is_ready": true,
+                    "is_ai": false
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.get_player(game_id, player_id).await;
+
+        mock.assert();
+        let player = result.unwrap();
+        assert_eq!(player.id, player_id);
+        assert_eq!(player.score, 3);
+        assert!(player.is_ready);
+    }
+
+    #[tokio::test]
+    async fn test_get_player_not_found() {
+        let mut server = Server::new_async().await;
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+        let mock = server
+            .mock(
+                "GET",
+                format!("/game/{}/player/{}", game_id, player_id).as_str(),
+            )
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.get_player(game_id, player_id).await;
+
+        mock.assert();
+        assert!(matches!(result, Err(TcpError::ServerError(_))));
+    }
+
     #[tokio::test]
     async fn test_join_game_with_username() {
         let mut server = Server::new_async().await;
@@ -370,7 +797,8 @@ mod tests {
                     "paddle_delta": 0.0,
                     "paddle_width": 0.2,
                     "is_ready": false,
-                    "is_ai": false
+                    "is_ai": false,
+                    "reconnect_token": "secret"
                 })
                 .to_string(),
             )
@@ -378,10 +806,14 @@ mod tests {
             .await;
 
         let client = TcpClient::new(&server.url());
-        let result = client.join_game(game_id, Some(username.to_string())).await;
+        let result = client
+            .join_game(game_id, Some(username.to_string()), None)
+            .await;
 
         mock.assert();
-        let player = result.unwrap();
+        let joined = result.unwrap();
+        assert_eq!(joined.reconnect_token, "secret");
+        let player = joined.player;
         assert_eq!(player.id, player_id);
         assert_eq!(player.name, username);
     }
@@ -410,7 +842,8 @@ mod tests {
                     "paddle_delta": 0.0,
                     "paddle_width": 0.2,
                     "is_ready": false,
-                    "is_ai": false
+                    "is_ai": false,
+                    "reconnect_token": "secret"
                 })
                 .to_string(),
             )
@@ -418,14 +851,50 @@ mod tests {
             .await;
 
         let client = TcpClient::new(&server.url());
-        let result = client.join_game(game_id, None).await;
+        let result = client.join_game(game_id, None, None).await;
 
         mock.assert();
-        let player = result.unwrap();
+        let joined = result.unwrap();
+        assert_eq!(joined.reconnect_token, "secret");
+        let player = joined.player;
         assert_eq!(player.id, player_id);
         assert!(player.name.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_quick_match() {
+        let mut server = Server::new_async().await;
+        let mut game = Game::new();
+        let player = Player::new("test_user".to_string(), false);
+        game.add_player(player.clone()).unwrap();
+        let mut body = json!({ "game": game });
+        body.as_object_mut().unwrap().extend(
+            serde_json::to_value(&player)
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        body["reconnect_token"] = json!("secret");
+        let mock = server
+            .mock("POST", "/quickmatch")
+            .match_body(json!({ "username": "test_user" }).to_string().as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.quick_match(Some("test_user".to_string())).await;
+
+        mock.assert();
+        let matched = result.unwrap();
+        assert_eq!(matched.game.id, game.id);
+        assert_eq!(matched.joined.player.id, player.id);
+        assert_eq!(matched.joined.reconnect_token, "secret");
+    }
+
     #[tokio::test]
     async fn test_add_bot_success() {
         let mut server = Server::new_async().await;
@@ -481,6 +950,44 @@ mod tests {
         assert!(matches!(result, Err(TcpError::ServerError(_))));
     }
 
+    #[tokio::test]
+    async fn test_fill_bots_success() {
+        let mut server = Server::new_async().await;
+        let game_id = Uuid::new_v4();
+        let bot = |name: &str, position: &str| {
+            json!({
+                "id": Uuid::new_v4(),
+                "name": name,
+                "joined_at": "2023-10-01T12:34:56Z",
+                "ping_timestamp": null,
+                "score": 0,
+                "addr": null,
+                "position": position,
+                "paddle_position": 0.5,
+                "paddle_delta": 0.0,
+                "paddle_width": 0.2,
+                "is_ready": true,
+                "is_ai": true
+            })
+        };
+        let mock = server
+            .mock("POST", format!("/game/{}/fill_bots", game_id).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!([bot("bot_2", "Left"), bot("bot_3", "Top")]).to_string())
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client.fill_bots(game_id).await;
+
+        mock.assert();
+        let bots = result.unwrap();
+        assert_eq!(bots.len(), 2);
+        assert!(bots.iter().all(|bot| bot.is_ai));
+        assert_eq!(bots[0].name, "bot_2");
+    }
+
     #[tokio::test]
     async fn test_remove_bot_success() {
         let mut server = Server::new_async().await;
@@ -540,7 +1047,8 @@ mod tests {
                     "paddle_delta": 0.0,
                     "paddle_width": 0.2,
                     "is_ready": false,
-                    "is_ai": false
+                    "is_ai": false,
+                    "reconnect_token": "secret"
                 })
                 .to_string(),
             )
@@ -548,14 +1056,41 @@ mod tests {
             .await;
 
         let client = TcpClient::new(&server.url());
-        let result = client.play_again(game_id, Some(username.to_string())).await;
+        let result = client
+            .play_again(game_id, Some(username.to_string()), None)
+            .await;
 
         mock.assert();
-        let player = result.unwrap();
+        let joined = result.unwrap();
+        assert_eq!(joined.reconnect_token, "secret");
+        let player = joined.player;
         assert_eq!(player.id, player_id);
         assert_eq!(player.name, username);
     }
 
+    #[tokio::test]
+    async fn test_play_again_sends_password() {
+        let mut server = Server::new_async().await;
+        let game_id = Uuid::new_v4();
+        // a private game checks the password again, without it the request is forbidden
+        let mock = server
+            .mock("POST", format!("/game/{}/play_again", game_id).as_str())
+            .match_body(Matcher::PartialJson(
+                json!({ "username": "a", "password": "secret" }),
+            ))
+            .with_status(403)
+            .create_async()
+            .await;
+
+        let client = TcpClient::new(&server.url());
+        let result = client
+            .play_again(game_id, Some("a".to_string()), Some("secret".to_string()))
+            .await;
+
+        mock.assert();
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_play_again_failure() {
         let mut server = Server::new_async().await;
@@ -567,7 +1102,7 @@ mod tests {
             .await;
 
         let client = TcpClient::new(&server.url());
-        let result = client.play_again(game_id, None).await;
+        let result = client.play_again(game_id, None, None).await;
 
         mock.assert();
         assert!(matches!(result, Err(TcpError::ServerError(_))));
@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use crate::client::config::Config;
+use crate::client::error::ClientError;
+use crate::common::models::{ClientInput, GameDto};
+
+use super::udp::UdpClient;
+use super::ws::WsClient;
+
+/// Carries the game inputs and updates, over WebSocket when `use_websocket` is set
+pub enum Transport {
+    Udp(UdpClient),
+    Ws(WsClient),
+}
+
+impl Transport {
+    pub fn new(config: &Config, game_id: Uuid) -> Result<Self, ClientError> {
+        if config.use_websocket {
+            Ok(Self::Ws(WsClient::new(&config.api_url, game_id)))
+        } else {
+            Ok(Self::Udp(UdpClient::new(config.udp_addr())?))
+        }
+    }
+
+    pub async fn send_client_input(&self, client_input: ClientInput) -> Result<(), ClientError> {
+        match self {
+            Self::Udp(client) => Ok(client.send_client_input(client_input).await?),
+            Self::Ws(client) => Ok(client.send_client_input(client_input).await?),
+        }
+    }
+
+    pub async fn recv_updated_game(&self) -> Result<GameDto, ClientError> {
+        match self {
+            Self::Udp(client) => Ok(client.recv_updated_game().await?),
+            Self::Ws(client) => Ok(client.recv_updated_game().await?),
+        }
+    }
+}
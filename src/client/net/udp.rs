@@ -1,4 +1,5 @@
 use std::net::ToSocketAddrs;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::common::models::{ClientInput, GameDto};
 
@@ -8,6 +9,7 @@ use super::error::UdpError;
 pub struct UdpClient {
     server_addr: std::net::SocketAddr,
     socket: tokio::net::UdpSocket,
+    seq: AtomicU64, // stamped on every input so the server can drop reordered ones
 }
 
 impl UdpClient {
@@ -37,10 +39,12 @@ impl UdpClient {
         Ok(Self {
             server_addr,
             socket: tokio::net::UdpSocket::from_std(socket)?,
+            seq: AtomicU64::new(0),
         })
     }
 
-    pub async fn send_client_input(&self, client_input: ClientInput) -> Result<(), UdpError> {
+    pub async fn send_client_input(&self, mut client_input: ClientInput) -> Result<(), UdpError> {
+        client_input.seq = self.seq.fetch_add(1, Ordering::Relaxed) + 1;
         let serialized = rmp_serde::to_vec(&client_input)?;
         self.socket.send_to(&serialized, &self.server_addr).await?;
         Ok(())
@@ -61,8 +65,8 @@ impl UdpClient {
 mod tests {
     use super::*;
     use crate::common::models::{
-        BallDto, ClientInput, ClientInputType, Direction, GameDto, GameState, PlayerDto,
-        PlayerPosition, Vec2,
+        BallDto, ClientInput, ClientInputType, Direction, GameDto, GameMode, GameState, PlayerDto,
+        PlayerPosition, Vec2, PROTOCOL_VERSION,
     };
     use std::{collections::HashMap, net::SocketAddr};
     use tokio::net::UdpSocket;
@@ -91,11 +95,14 @@ mod tests {
             ClientInputType::Ping,
         ];
 
-        for action in test_cases {
+        for (index, action) in test_cases.into_iter().enumerate() {
             let input = ClientInput {
                 game_id: Uuid::new_v4().to_string(),
                 player_id: Uuid::new_v4().to_string(),
                 action: action.clone(),
+                reconnect_token: None,
+                protocol_version: PROTOCOL_VERSION,
+                seq: 0,
             };
 
             client.send_client_input(input.clone()).await.unwrap();
@@ -107,6 +114,7 @@ mod tests {
             assert_eq!(received.game_id, input.game_id);
             assert_eq!(received.player_id, input.player_id);
             assert_eq!(received.action, input.action);
+            assert_eq!(received.seq, index as u64 + 1);
         }
     }
 
@@ -120,6 +128,9 @@ mod tests {
                 game_id: Uuid::new_v4().to_string(),
                 player_id: Uuid::new_v4().to_string(),
                 action: ClientInputType::Ping,
+                reconnect_token: None,
+                protocol_version: PROTOCOL_VERSION,
+                seq: 0,
             })
             .await
             .unwrap();
@@ -130,9 +141,24 @@ mod tests {
             id: Uuid::new_v4(),
             state: GameState::WaitingForPlayers,
             players: HashMap::new(),
+            player_count: 0,
             ball: None,
             created_at: chrono::Utc::now(),
             started_at: None,
+            board_width: 10.0,
+            board_height: 10.0,
+            start_countdown_ms: None,
+            sequence: 1,
+            extra_balls: vec![],
+            forfeited_by: None,
+            sudden_death: false,
+            serving: false,
+            protocol_version: PROTOCOL_VERSION,
+            last_goal_at: None,
+            name: None,
+            is_private: false,
+            mode: GameMode::Score,
+            goal_timeout_ms: None,
         };
 
         server_socket
@@ -171,6 +197,9 @@ mod tests {
                     paddle_delta: 0.0,
                     paddle_width: 0.2,
                     is_ready: i == 0,
+                    returns: 0,
+                    lives: 0,
+                    last_score_at: None,
                 },
             );
         }
@@ -178,14 +207,30 @@ mod tests {
         let game_dto = GameDto {
             id: Uuid::new_v4(),
             state: GameState::Active,
+            player_count: players.len(),
             players,
             ball: Some(BallDto {
                 position: Vec2 { x: 0.5, y: 0.5 },
                 velocity: Vec2 { x: 0.1, y: -0.1 },
                 radius: 0.05,
+                last_touched_by: Some(player_ids[0]),
             }),
             created_at: chrono::Utc::now(),
             started_at: Some(chrono::Utc::now()),
+            board_width: 10.0,
+            board_height: 10.0,
+            start_countdown_ms: None,
+            sequence: 1,
+            extra_balls: vec![],
+            forfeited_by: None,
+            sudden_death: false,
+            serving: false,
+            protocol_version: PROTOCOL_VERSION,
+            last_goal_at: None,
+            name: None,
+            is_private: false,
+            mode: GameMode::Score,
+            goal_timeout_ms: None,
         };
 
         server_socket
@@ -213,9 +258,24 @@ mod tests {
             id: Uuid::new_v4(),
             state: GameState::Finished,
             players: HashMap::new(),
+            player_count: 0,
             ball: None,
             created_at: chrono::Utc::now(),
             started_at: None,
+            board_width: 10.0,
+            board_height: 10.0,
+            start_countdown_ms: None,
+            sequence: 1,
+            extra_balls: vec![],
+            forfeited_by: None,
+            sudden_death: false,
+            serving: false,
+            protocol_version: PROTOCOL_VERSION,
+            last_goal_at: None,
+            name: None,
+            is_private: false,
+            mode: GameMode::Score,
+            goal_timeout_ms: None,
         };
 
         rogue_server
@@ -271,6 +331,9 @@ mod tests {
                 game_id: Uuid::new_v4().to_string(),
                 player_id: Uuid::new_v4().to_string(),
                 action: ClientInputType::Ping,
+                reconnect_token: None,
+                protocol_version: PROTOCOL_VERSION,
+                seq: 0,
             })
             .await
             .unwrap();
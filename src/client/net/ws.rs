@@ -0,0 +1,191 @@
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, Notify};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+use crate::common::models::{ClientInput, ClientInputType, GameDto};
+
+use super::error::WsError;
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// WebSocket endpoint of the game, the API url with its scheme swapped
+pub fn game_ws_url(api_url: &str, game_id: Uuid) -> String {
+    let base = api_url.trim_end_matches('/');
+    let base = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        base.to_string()
+    };
+    format!("{}/game/{}/ws", base, game_id)
+}
+
+/// Same API as `UdpClient`, for networks that block UDP.
+/// Connects on the first sent input, which has to be the `JoinGame`,
+/// after a close the next input reconnects and the join is sent again ahead of it
+pub struct WsClient {
+    url: String,
+    sink: Mutex<Option<SplitSink<WsStream, Message>>>,
+    stream: Mutex<Option<SplitStream<WsStream>>>,
+    connected: Notify,
+    join: Mutex<Option<Box<ClientInput>>>, // the server identifies every new connection by it
+}
+
+impl WsClient {
+    pub fn new(api_url: &str, game_id: Uuid) -> Self {
+        Self {
+            url: game_ws_url(api_url, game_id),
+            sink: Mutex::new(None),
+            stream: Mutex::new(None),
+            connected: Notify::new(),
+            join: Mutex::new(None),
+        }
+    }
+
+    pub async fn send_client_input(&self, client_input: ClientInput) -> Result<(), WsError> {
+        let serialized = rmp_serde::to_vec(&client_input)?;
+        let is_join = client_input.action == ClientInputType::JoinGame;
+        let mut join = self.join.lock().await;
+        if is_join {
+            *join = Some(Box::new(client_input));
+        }
+
+        let mut sink = self.sink.lock().await;
+        if sink.is_none() {
+            let (socket, _) = connect_async(self.url.as_str()).await?;
+            let (mut socket_sink, socket_stream) = socket.split();
+            // a reconnect, the server only takes other inputs once we identified again
+            if let Some(join) = join.as_ref().filter(|_| !is_join) {
+                socket_sink
+                    .send(Message::Binary(rmp_serde::to_vec(join)?))
+                    .await?;
+            }
+            *sink = Some(socket_sink);
+            *self.stream.lock().await = Some(socket_stream);
+            self.connected.notify_waiters();
+        }
+
+        match sink.as_mut() {
+            Some(sink) => Ok(sink.send(Message::Binary(serialized)).await?),
+            None => Err(WsError::Closed),
+        }
+    }
+
+    /// Waits for the connection to be opened by the first sent input
+    pub async fn recv_updated_game(&self) -> Result<GameDto, WsError> {
+        loop {
+            // registered before checking, so a connection opened in between still wakes us
+            let connected = self.connected.notified();
+            let mut guard = self.stream.lock().await;
+            let Some(stream) = guard.as_mut() else {
+                drop(guard);
+                connected.await;
+                continue;
+            };
+
+            match stream.next().await {
+                Some(Ok(Message::Binary(bytes))) => return Ok(rmp_serde::from_slice(&bytes)?),
+                Some(Ok(Message::Close(_))) | None => {
+                    // the next sent input reconnects, until then wait instead of spinning
+                    *guard = None;
+                    drop(guard);
+                    *self.sink.lock().await = None;
+                    return Err(WsError::Closed);
+                }
+                Some(Ok(_)) => continue, // pings are answered by tungstenite
+                Some(Err(e)) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::game_loop::InputQueue;
+    use crate::common::models::Direction;
+    use crate::common::{GameRooms, Player};
+    use crate::server::api::{ws_router, WsClients, WsState};
+    use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+    #[tokio::test]
+    async fn test_reconnect_sends_join_first() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+        let mut player = Player::new("Player 1".to_string(), false);
+        let token = player.issue_reconnect_token();
+        game_rooms
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(player.clone())
+            .unwrap();
+        let message_queue = Arc::new(Mutex::new(InputQueue::default()));
+        let state = WsState {
+            game_rooms,
+            message_queue: message_queue.clone(),
+            clients: WsClients::new(),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api_url = format!("http://{}", listener.local_addr().unwrap());
+        let app = ws_router(state).into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let received = || async {
+            tokio::time::timeout(Duration::from_secs(2), async {
+                loop {
+                    if let Some(input) = message_queue.lock().await.pop() {
+                        return input.input;
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .unwrap()
+        };
+        let input = |action| ClientInput::new(game_id.to_string(), player.id.to_string(), action);
+        let move_paddle = ClientInputType::MovePaddle(Direction::Positive);
+
+        let client = WsClient::new(&api_url, game_id);
+        client
+            .send_client_input(input(ClientInputType::JoinGame).with_reconnect_token(token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(received().await.action, ClientInputType::JoinGame);
+
+        // the connection drops, e.g. a proxy timed it out
+        *client.sink.lock().await = None;
+        *client.stream.lock().await = None;
+
+        client
+            .send_client_input(input(move_paddle.clone()))
+            .await
+            .unwrap();
+        let join = received().await;
+        assert_eq!(join.action, ClientInputType::JoinGame);
+        assert_eq!(join.reconnect_token, Some(token.clone()));
+        let moved = received().await;
+        assert_eq!(moved.action, move_paddle);
+        // vouched for by the connection, whatever address it comes from now
+        assert_eq!(moved.reconnect_token, Some(token));
+    }
+
+    #[test]
+    fn test_game_ws_url() {
+        let game_id = Uuid::nil();
+        assert_eq!(
+            game_ws_url("http://127.0.0.1:3000", game_id),
+            format!("ws://127.0.0.1:3000/game/{}/ws", game_id)
+        );
+        assert_eq!(
+            game_ws_url("https://quadropong.example/", game_id),
+            format!("wss://quadropong.example/game/{}/ws", game_id)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::common::PlayerPosition;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Default,
+    Colorblind, // Okabe-Ito colors, distinguishable with the common color vision deficiencies
+}
+
+impl ColorPalette {
+    pub fn color(self, position: PlayerPosition) -> Color {
+        match self {
+            ColorPalette::Default => match position {
+                PlayerPosition::Top => Color::LightRed,
+                PlayerPosition::Bottom => Color::LightGreen,
+                PlayerPosition::Left => Color::LightBlue,
+                PlayerPosition::Right => Color::LightYellow,
+            },
+            ColorPalette::Colorblind => match position {
+                PlayerPosition::Top => Color::Rgb(230, 159, 0), // orange
+                PlayerPosition::Bottom => Color::Rgb(86, 180, 233), // sky blue
+                PlayerPosition::Left => Color::Rgb(0, 158, 115), // bluish green
+                PlayerPosition::Right => Color::Rgb(204, 121, 167), // reddish purple
+            },
+        }
+    }
+}
+
+/// Per-position color overrides, positions without one use the palette color
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PositionColors {
+    pub top: Option<Color>,
+    pub bottom: Option<Color>,
+    pub left: Option<Color>,
+    pub right: Option<Color>,
+}
+
+impl PositionColors {
+    pub fn get(&self, position: PlayerPosition) -> Option<Color> {
+        match position {
+            PlayerPosition::Top => self.top,
+            PlayerPosition::Bottom => self.bottom,
+            PlayerPosition::Left => self.left,
+            PlayerPosition::Right => self.right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITIONS: [PlayerPosition; 4] = [
+        PlayerPosition::Top,
+        PlayerPosition::Bottom,
+        PlayerPosition::Left,
+        PlayerPosition::Right,
+    ];
+
+    #[test]
+    fn test_palette_colors_are_distinct() {
+        for palette in [ColorPalette::Default, ColorPalette::Colorblind] {
+            let colors: Vec<_> = POSITIONS.iter().map(|p| palette.color(*p)).collect();
+            for (i, color) in colors.iter().enumerate() {
+                assert!(!colors[i + 1..].contains(color));
+            }
+        }
+    }
+
+    #[test]
+    fn test_position_colors_override() {
+        let colors = PositionColors {
+            left: Some(Color::White),
+            ..Default::default()
+        };
+        assert_eq!(colors.get(PlayerPosition::Left), Some(Color::White));
+        assert_eq!(colors.get(PlayerPosition::Top), None);
+    }
+}
@@ -1,9 +1,13 @@
 use crate::client::config;
 use crate::client::net::error::TcpError;
 use crate::client::net::tcp::TcpClient;
+use crate::common::JoinGameResponse;
 
 use super::lobby::Lobby;
+use super::lobby_browser::LobbyBrowser;
 use super::menu::Menu;
+use super::password_prompt::PasswordPrompt;
+use super::server_busy::ServerBusy;
 use super::traits::{HasConfig, Render, State, Update};
 use super::utils::input::Input;
 use super::utils::render::{into_title, render_inner_rectangle, render_outer_rectangle};
@@ -18,9 +22,13 @@ use ratatui::text::Line;
 use ratatui::widgets::{Block, Paragraph, Wrap};
 use ratatui::Frame;
 
+// consecutive failed requests before the server is considered down or busy
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
 #[derive(PartialEq)]
 pub enum Options {
     Create,
+    Browse,
     Join,
 }
 
@@ -28,6 +36,7 @@ impl std::fmt::Display for Options {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Options::Create => write!(f, " {} ", into_title("create lobby")),
+            Options::Browse => write!(f, " {} ", into_title("browse lobbies")),
             Options::Join => write!(f, " {} ", into_title("join lobby")),
         }
     }
@@ -40,17 +49,19 @@ pub struct CreateOrJoinLobby {
     error_message: Option<String>,
     tcp_client: TcpClient,
     config: config::Config,
+    consecutive_failures: u32,
 }
 
 impl CreateOrJoinLobby {
     pub fn new(config: config::Config) -> Result<Self, ClientError> {
         Ok(Self {
-            options: vec![Options::Create, Options::Join],
+            options: vec![Options::Create, Options::Browse, Options::Join],
             selected: 0,
             join_lobby_input: Input::new(),
             error_message: None,
             tcp_client: TcpClient::new(&config.api_url),
             config,
+            consecutive_failures: 0,
         })
     }
 
@@ -66,6 +77,25 @@ impl CreateOrJoinLobby {
         }
     }
 
+    async fn join(&mut self, game_id: uuid::Uuid) -> Result<JoinGameResponse, TcpError> {
+        let joined = self
+            .tcp_client
+            .join_game(game_id, Some(self.config.player_name.clone()), None)
+            .await;
+        self.record_request_result(&joined);
+        joined
+    }
+
+    /// Counts requests that never got a response, any answer from the server resets the count
+    fn record_request_result<T>(&mut self, result: &Result<T, TcpError>) {
+        match result {
+            Err(TcpError::FailedToSendRequest(_)) | Err(TcpError::FailedToReadResponse(_)) => {
+                self.consecutive_failures += 1;
+            }
+            _ => self.consecutive_failures = 0,
+        }
+    }
+
     fn handle_net_error(&mut self, e: TcpError) {
         error!("Error joining game: {}", e);
         let hide_bg_issues_msg =
@@ -107,20 +137,19 @@ impl Update for CreateOrJoinLobby {
             match self.options[self.selected] {
                 Options::Create => {
                     if key_code == KeyCode::Enter {
-                        match self.tcp_client.create_game().await {
+                        let created = self.tcp_client.create_game().await;
+                        self.record_request_result(&created);
+                        match created {
                             // Game is created, but we need to join it to get our player id
-                            Ok(game) => match self
-                                .tcp_client
-                                .join_game(game.id, Some(self.config.player_name.clone()))
-                                .await
-                            {
+                            Ok(game) => match self.join(game.id).await {
                                 // We successfully joined the game
-                                Ok(our_player) => {
-                                    info!("Moving from CreateOrJoinLobby to Lobby via create, game id: {:?}, our player id: {:?}", game.id, our_player.id);
+                                Ok(joined) => {
+                                    info!("Moving from CreateOrJoinLobby to Lobby via create, game id: {:?}, our player id: {:?}", game.id, joined.player.id);
                                     return Ok(Some(Box::new(Lobby::new(
                                         game,
-                                        our_player.id,
+                                        joined,
                                         self.config.clone(),
+                                        None,
                                     )?)));
                                 }
                                 Err(e) => {
@@ -133,6 +162,25 @@ impl Update for CreateOrJoinLobby {
                         }
                     }
                 }
+                Options::Browse => {
+                    if key_code == KeyCode::Enter {
+                        let listed = self.tcp_client.list_games().await;
+                        self.record_request_result(&listed);
+                        match listed {
+                            Ok(games) => {
+                                info!("Moving from CreateOrJoinLobby to LobbyBrowser");
+                                return Ok(Some(Box::new(LobbyBrowser::new(
+                                    games,
+                                    self.config.clone(),
+                                )?)));
+                            }
+                            Err(e) => {
+                                error!("Error listing games: {}", e);
+                                self.error_message = Some(e.to_string());
+                            }
+                        }
+                    }
+                }
                 Options::Join => match key_code {
                     KeyCode::Left
                     | KeyCode::Right
@@ -142,18 +190,24 @@ impl Update for CreateOrJoinLobby {
                     KeyCode::Enter => {
                         match uuid::Uuid::parse_str(&self.join_lobby_input.input) {
                             Ok(inputted_game_id) => {
-                                match self.tcp_client.get_game(inputted_game_id).await {
-                                    Ok(game) => match self
-                                        .tcp_client
-                                        .join_game(game.id, Some(self.config.player_name.clone()))
-                                        .await
-                                    {
-                                        Ok(our_player) => {
-                                            info!("Moving from CreateOrJoinLobby to Lobby via join, game id: {:?}, our player id: {:?}", game.id, our_player.id);
+                                let fetched = self.tcp_client.get_game(inputted_game_id).await;
+                                self.record_request_result(&fetched);
+                                match fetched {
+                                    Ok(game) if game.is_private => {
+                                        info!("Moving from CreateOrJoinLobby to PasswordPrompt");
+                                        return Ok(Some(Box::new(PasswordPrompt::new(
+                                            game.id,
+                                            self.config.clone(),
+                                        )?)));
+                                    }
+                                    Ok(game) => match self.join(game.id).await {
+                                        Ok(joined) => {
+                                            info!("Moving from CreateOrJoinLobby to Lobby via join, game id: {:?}, our player id: {:?}", game.id, joined.player.id);
                                             return Ok(Some(Box::new(Lobby::new(
                                                 game,
-                                                our_player.id,
+                                                joined,
                                                 self.config.clone(),
+                                                None,
                                             )?)));
                                         }
                                         Err(e) => {
@@ -176,6 +230,13 @@ impl Update for CreateOrJoinLobby {
                     _ => {}
                 },
             }
+            if self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                info!(
+                    "Moving from CreateOrJoinLobby to ServerBusy after {} failed requests",
+                    self.consecutive_failures
+                );
+                return Ok(Some(Box::new(ServerBusy::new(self.config.clone())?)));
+            }
         }
         Ok(None)
     }
@@ -198,10 +259,13 @@ impl Render for CreateOrJoinLobby {
 
         let inner_rect = render_inner_rectangle(frame, outer_rect);
 
-        let [create_area, join_area] =
-            Layout::vertical(vec![Constraint::Length(1), Constraint::Length(3)])
-                .flex(Flex::SpaceAround)
-                .areas(inner_rect);
+        let [create_area, browse_area, join_area] = Layout::vertical(vec![
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .flex(Flex::SpaceAround)
+        .areas(inner_rect);
         let [_, error_area, _] = Layout::vertical(vec![
             Constraint::Fill(1),
             Constraint::Length(2),
@@ -217,6 +281,14 @@ impl Render for CreateOrJoinLobby {
         };
         frame.render_widget(Paragraph::new(create_area_text).centered(), create_area);
 
+        // render browse lobbies area
+        let browse_area_text = if self.options[self.selected] == Options::Browse {
+            Line::from(format!(">{}<", Options::Browse)).bold()
+        } else {
+            Line::from(Options::Browse.to_string())
+        };
+        frame.render_widget(Paragraph::new(browse_area_text).centered(), browse_area);
+
         let block_width_layout = Layout::horizontal(vec![
             Constraint::Percentage(15),
             Constraint::Percentage(70),
@@ -245,7 +317,7 @@ impl Render for CreateOrJoinLobby {
         let mut style = Style::default();
         if self.options[self.selected] == Options::Join {
             frame.set_cursor_position(Position::new(
-                inner_join_input_area.x + self.join_lobby_input.char_index.try_into().unwrap_or(0),
+                inner_join_input_area.x + self.join_lobby_input.cursor_column(),
                 inner_join_input_area.y,
             ));
             style = Style::default().bold();
@@ -272,3 +344,31 @@ impl Render for CreateOrJoinLobby {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_repeated_failures_show_server_busy() {
+        // nothing listens on port 1, so every request fails to send
+        let config = config::Config {
+            api_url: "http://127.0.0.1:1".to_string(),
+            ..Default::default()
+        };
+        let mut state = CreateOrJoinLobby::new(config).unwrap();
+
+        for _ in 1..MAX_CONSECUTIVE_FAILURES {
+            let next = state.update(Some(KeyCode::Enter)).await.unwrap();
+            assert!(next.is_none());
+            assert!(state.error_message.is_some());
+        }
+
+        let next = state.update(Some(KeyCode::Enter)).await.unwrap();
+        assert!(next
+            .expect("Expected a state transition")
+            .as_any()
+            .downcast_ref::<ServerBusy>()
+            .is_some());
+    }
+}
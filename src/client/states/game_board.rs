@@ -1,42 +1,101 @@
 use crate::client::config;
 use crate::client::error::ClientError;
-use crate::client::net::udp::UdpClient;
+use crate::client::key_bindings::Action;
+use crate::client::net::transport::Transport;
 use crate::client::states::menu::Menu;
-use crate::common::models::{ClientInput, ClientInputType, Direction, GameDto, GameState};
+use crate::common::models::{
+    ClientInput, ClientInputType, Direction, GameDto, GameState, PlayerDto, DEFAULT_GOAL_TIMEOUT,
+};
 use crate::common::PlayerPosition;
 
 use super::game_end::GameEnd;
+use super::replay::ReplayRecorder;
 use super::traits::{HasConfig, Render, State, Update};
-use super::utils::render::{render_disconnect_popup, render_game};
+use super::utils::acceleration::KeyHold;
+use super::utils::connection::{
+    render_reconnecting, ConnectionState, ConnectionWatch, CHECK_INTERVAL,
+};
+use super::utils::debug_overlay::{format_stats, render_debug_overlay, ReceiveStats};
+use super::utils::goal_flash::{render_goal_flash, show_goal_flash};
+use super::utils::prediction::PaddlePrediction;
+use super::utils::render::{render_disconnect_popup, render_game, render_ghost_paddle};
+use super::utils::sequence::apply_if_newer;
+use super::utils::sound::{detect_cue, SoundCues};
+use super::utils::trail::BallTrail;
 
 use crossterm::event::KeyCode;
 use log::{debug, error, info};
 use ratatui::Frame;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
 use uuid::Uuid;
 
+/// Direction a movement action moves the paddle of `position`, `None` for the other axis.
+/// With `mirror` the far sides, Top and Right, move the way they look from their seat
+pub fn move_direction(position: PlayerPosition, action: Action, mirror: bool) -> Option<Direction> {
+    let positive = match (position, action) {
+        (PlayerPosition::Left | PlayerPosition::Right, Action::MoveDown)
+        | (PlayerPosition::Top | PlayerPosition::Bottom, Action::MoveRight) => true,
+        (PlayerPosition::Left | PlayerPosition::Right, Action::MoveUp)
+        | (PlayerPosition::Top | PlayerPosition::Bottom, Action::MoveLeft) => false,
+        _ => return None,
+    };
+    let mirrored = mirror && matches!(position, PlayerPosition::Top | PlayerPosition::Right);
+    Some(if positive != mirrored {
+        Direction::Positive
+    } else {
+        Direction::Negative
+    })
+}
+
+/// The player after `focused` among those with a paddle on the board, in the order they joined,
+/// wrapping around to the first, `focused` stays when nobody else is there
+fn next_focus(game: &GameDto, focused: Uuid) -> Uuid {
+    let mut present: Vec<&PlayerDto> = game
+        .players
+        .values()
+        .filter(|player| player.position.is_some())
+        .collect();
+    present.sort_by_key(|player| (player.joined_at, player.id));
+
+    let next = match present.iter().position(|player| player.id == focused) {
+        Some(index) => present.get(index + 1).or(present.first()),
+        None => present.first(),
+    };
+    next.map_or(focused, |player| player.id)
+}
+
 pub struct GameBoard {
     game: Arc<Mutex<GameDto>>,
     our_player_id: Uuid,
     our_player_position: PlayerPosition,
+    focused_player_id: Uuid, // whose name is highlighted, ours until cycled to someone else
     cancellation_token: CancellationToken,
     _receive_update_handle: JoinHandle<()>,
     _ping_handle: JoinHandle<()>,
-    udp_client: Arc<UdpClient>,
+    transport: Arc<Transport>,
     config: config::Config,
-    disconnected: Arc<AtomicBool>,
+    connection: Arc<Mutex<ConnectionWatch>>,
+    receive_stats: Arc<Mutex<ReceiveStats>>,
+    ball_trail: Arc<Mutex<BallTrail>>,
+    prediction: Arc<Mutex<PaddlePrediction>>,
+    goal_seen_at: Arc<Mutex<Option<chrono::DateTime<chrono::Utc>>>>, // our clock, the server's may differ
+    key_hold: KeyHold,
+    show_debug_overlay: bool,
+    measured_fps: f64,
+    password: Option<String>, // of a private game, handed on for playing again
 }
 
 impl GameBoard {
     pub fn new(
         game: GameDto,
         our_player_id: Uuid,
-        udp_client: Arc<UdpClient>,
+        transport: Arc<Transport>,
         config: config::Config,
+        password: Option<String>,
     ) -> Result<Self, ClientError> {
         // if for some reason the player position is not set, default to left
         let our_player_position = game
@@ -44,28 +103,97 @@ impl GameBoard {
             .get(&our_player_id)
             .map(|player| player.position.unwrap_or(PlayerPosition::Left))
             .unwrap_or(PlayerPosition::Left);
+        // sequence of the last applied snapshot, moved into the receive task
+        let mut last_sequence = game.sequence;
         let game = Arc::new(Mutex::new(game));
         let cancellation_token = CancellationToken::new();
-        let disconnected = Arc::new(AtomicBool::new(false));
+        let connection = Arc::new(Mutex::new(ConnectionWatch::new(Instant::now())));
+        let receive_stats = Arc::new(Mutex::new(ReceiveStats::new(Instant::now())));
+        let trail_length = if config.reduced_motion {
+            0
+        } else {
+            config.ball_trail
+        };
+        let ball_trail = Arc::new(Mutex::new(BallTrail::new(trail_length)));
+        let prediction = Arc::new(Mutex::new(PaddlePrediction::new()));
+        // with reduced motion our paddle only moves when the server says so
+        let predict_paddle = !config.reduced_motion;
+        let goal_seen_at = Arc::new(Mutex::new(None));
+        let enable_sound = config.enable_sound;
+        let mut sound_cues = SoundCues::new();
+        let mut recorder = if config.record_replays {
+            ReplayRecorder::create_new().unwrap_or_else(|e| {
+                error!("Failed to start recording replay: {}", e);
+                None
+            })
+        } else {
+            None
+        };
 
         let game_clone = Arc::clone(&game);
-        let udp_client_clone = Arc::clone(&udp_client);
+        let receive_stats_clone = Arc::clone(&receive_stats);
+        let ball_trail_clone = Arc::clone(&ball_trail);
+        let prediction_clone = Arc::clone(&prediction);
+        let goal_seen_at_clone = Arc::clone(&goal_seen_at);
+        let transport_clone = Arc::clone(&transport);
         let cancellation_token_clone = cancellation_token.clone();
-        let disconnected_clone = Arc::clone(&disconnected);
+        let connection_clone = Arc::clone(&connection);
         let receive_update_handle = tokio::spawn(async move {
             loop {
                 tokio::select! {
                     // Exit loop on cancellation
                     _ = cancellation_token_clone.cancelled() => break,
-                    _ = tokio::time::sleep(std::time::Duration::from_secs(3)) => {
-                        disconnected_clone.store(true, Ordering::Relaxed);
+                    _ = tokio::time::sleep(CHECK_INTERVAL) => {
+                        if let Ok(mut connection) = connection_clone.lock() {
+                            connection.update(Instant::now());
+                        }
                     }
                     // Process incoming game updates
-                    result = udp_client_clone.recv_updated_game() => {
+                    result = transport_clone.recv_updated_game() => {
                         match result {
                             Ok(updated_game) => {
+                                if let Ok(mut stats) = receive_stats_clone.lock() {
+                                    stats.record(Instant::now());
+                                }
+                                if let Ok(mut connection) = connection_clone.lock() {
+                                    connection.record(Instant::now());
+                                }
+                                if let Some(rec) = recorder.as_mut() {
+                                    if let Err(e) = rec.record(&updated_game) {
+                                        // stop recording rather than logging every frame
+                                        error!("Failed to record replay: {}", e);
+                                        recorder = None;
+                                    }
+                                }
                                 if let Ok(mut current_game) = game_clone.lock() {
-                                    *current_game = updated_game;
+                                    let previous_goal_at = current_game.last_goal_at;
+                                    let cue = enable_sound
+                                        .then(|| detect_cue(&current_game, &updated_game, our_player_id))
+                                        .flatten();
+                                    if apply_if_newer(&mut current_game, &mut last_sequence, updated_game) {
+                                        if let Some(cue) = cue {
+                                            sound_cues.play(cue, Instant::now());
+                                        }
+                                        if current_game.last_goal_at.is_some() && current_game.last_goal_at != previous_goal_at {
+                                            if let Ok(mut goal_seen_at) = goal_seen_at_clone.lock() {
+                                                *goal_seen_at = Some(chrono::Utc::now());
+                                            }
+                                        }
+                                        if let Ok(mut trail) = ball_trail_clone.lock() {
+                                            trail.push(&current_game);
+                                        }
+                                        let board = current_game.board();
+                                        if predict_paddle {
+                                            if let (Some(us), Ok(mut prediction)) = (
+                                                current_game.players.get_mut(&our_player_id),
+                                                prediction_clone.lock(),
+                                            ) {
+                                                prediction.apply(us, board);
+                                            }
+                                        }
+                                    } else {
+                                        debug!("Dropped an out of order game update");
+                                    }
                                 } else {
                                     error!("Failed to lock game");
                                 }
@@ -79,7 +207,7 @@ impl GameBoard {
             }
         });
 
-        let udp_client_clone = Arc::clone(&udp_client);
+        let transport_clone = Arc::clone(&transport);
         let cancellation_token_clone = cancellation_token.clone();
         let game_clone = Arc::clone(&game);
         let ping_handle = tokio::spawn(async move {
@@ -99,7 +227,7 @@ impl GameBoard {
 
                 tokio::select! {
                     _ = cancellation_token_clone.cancelled() => break,
-                    _ = udp_client_clone.send_client_input(client_input) => {
+                    _ = transport_clone.send_client_input(client_input) => {
                         debug!("Ping sent");
                     }
                 }
@@ -110,21 +238,93 @@ impl GameBoard {
             game,
             our_player_id,
             our_player_position,
+            focused_player_id: our_player_id,
             cancellation_token,
             _receive_update_handle: receive_update_handle,
             _ping_handle: ping_handle,
-            udp_client,
+            transport,
             config,
-            disconnected,
+            connection,
+            receive_stats,
+            ball_trail,
+            prediction,
+            goal_seen_at,
+            key_hold: KeyHold::new(),
+            show_debug_overlay: false,
+            measured_fps: 0.0,
+            password,
         })
     }
 
-    fn create_move_input(&self, direction: Direction) -> Option<ClientInput> {
+    /// Kept up to date by the receive task, a poisoned lock is taken as a lost connection
+    fn connection_state(&self) -> ConnectionState {
+        self.connection
+            .lock()
+            .map_or(ConnectionState::Disconnected, |connection| {
+                connection.state()
+            })
+    }
+
+    /// Our paddle where the server has it, next to the predicted one in the debug view
+    fn render_ghost_paddle(&self, game: &GameDto, frame: &mut Frame) {
+        let Some(us) = game.players.get(&self.our_player_id) else {
+            return;
+        };
+        if let Some(authoritative) = self
+            .prediction
+            .lock()
+            .ok()
+            .and_then(|prediction| prediction.authoritative())
+        {
+            render_ghost_paddle(us, authoritative, game.board(), frame);
+        }
+    }
+
+    /// Lets the server free our slot right away instead of waiting for the ping timeout
+    async fn send_disconnect(&self) {
+        let game_id = match self.game.lock() {
+            Ok(game) => game.id,
+            Err(_) => {
+                error!("Failed to lock game");
+                return;
+            }
+        };
+        let client_input = ClientInput::new(
+            game_id.to_string(),
+            self.our_player_id.to_string(),
+            ClientInputType::Disconnect,
+        );
+        if let Err(e) = self.transport.send_client_input(client_input).await {
+            error!("Failed to send disconnect: {}", e);
+        }
+    }
+
+    /// Shows our move right away instead of waiting for the server's next state,
+    /// not with reduced motion
+    fn predict_move(&self, direction: &Direction, boost: f32) {
+        if self.config.reduced_motion {
+            return;
+        }
+        if let (Ok(mut game), Ok(mut prediction)) = (self.game.lock(), self.prediction.lock()) {
+            let board = game.board();
+            if let Some(us) = game.players.get_mut(&self.our_player_id) {
+                prediction.predict(us, direction, boost, board);
+            }
+        }
+    }
+
+    fn create_move_input(&self, direction: Direction, boost: f32) -> Option<ClientInput> {
+        // a single press keeps the plain move older servers understand
+        let action = if boost > 1.0 {
+            ClientInputType::MovePaddleBy(direction, boost)
+        } else {
+            ClientInputType::MovePaddle(direction)
+        };
         if let Ok(game) = self.game.lock() {
             Some(ClientInput::new(
                 game.id.to_string(),
                 self.our_player_id.to_string(),
-                ClientInputType::MovePaddle(direction),
+                action,
             ))
         } else {
             error!("Failed to lock game");
@@ -155,62 +355,62 @@ impl Update for GameBoard {
                     game.clone(),
                     self.our_player_id,
                     self.config.clone(),
+                    self.password.clone(),
                 )?)));
             }
         } else {
             error!("Failed to lock game");
         }
         if let Some(key_code) = key_code {
-            match key_code {
-                KeyCode::Esc => {
-                    if self.disconnected.load(Ordering::Relaxed) {
-                        info!("Moving from Lobby to CreateOrJoinLobby due to disconnection");
-                    } else {
-                        info!("Moving from GameBoard to Menu due to user leaving");
-                    }
-                    return Ok(Some(Box::new(Menu::new(0, self.config.clone())?)));
+            if key_code == KeyCode::F(3) && self.config.debug_overlay {
+                self.show_debug_overlay = !self.show_debug_overlay;
+                return Ok(None);
+            }
+
+            let bindings = &self.config.key_bindings;
+            if bindings.is(Action::CycleFocus, key_code) {
+                if let Ok(game) = self.game.lock() {
+                    self.focused_player_id = next_focus(&game, self.focused_player_id);
                 }
-                _ => match self.our_player_position {
-                    PlayerPosition::Left | PlayerPosition::Right => match key_code {
-                        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => {
-                            if let Some(input) = self.create_move_input(Direction::Negative) {
-                                self.udp_client
-                                    .send_client_input(input)
-                                    .await
-                                    .unwrap_or_else(|e| error!("Failed to send move input: {}", e));
-                            }
-                        }
-                        KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('S') => {
-                            if let Some(input) = self.create_move_input(Direction::Positive) {
-                                self.udp_client
-                                    .send_client_input(input)
-                                    .await
-                                    .unwrap_or_else(|e| error!("Failed to send move input: {}", e));
-                            }
-                        }
-                        _ => {}
-                    },
-                    PlayerPosition::Top | PlayerPosition::Bottom => match key_code {
-                        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') => {
-                            if let Some(input) = self.create_move_input(Direction::Positive) {
-                                self.udp_client
-                                    .send_client_input(input)
-                                    .await
-                                    .unwrap_or_else(|e| error!("Failed to send move input: {}", e));
-                            }
-                        }
-                        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') => {
-                            if let Some(input) = self.create_move_input(Direction::Negative) {
-                                self.udp_client
-                                    .send_client_input(input)
-                                    .await
-                                    .unwrap_or_else(|e| error!("Failed to send move input: {}", e));
-                            }
-                        }
-                        _ => {}
-                    },
-                },
-            };
+                return Ok(None);
+            }
+
+            if bindings.is(Action::Leave, key_code) {
+                self.send_disconnect().await;
+                if self.connection_state() == ConnectionState::Disconnected {
+                    info!("Moving from Lobby to CreateOrJoinLobby due to disconnection");
+                } else {
+                    info!("Moving from GameBoard to Menu due to user leaving");
+                }
+                return Ok(Some(Box::new(Menu::new(0, self.config.clone())?)));
+            }
+
+            let direction = [
+                Action::MoveUp,
+                Action::MoveDown,
+                Action::MoveLeft,
+                Action::MoveRight,
+            ]
+            .into_iter()
+            .filter(|action| bindings.is(*action, key_code))
+            .find_map(|action| {
+                move_direction(
+                    self.our_player_position,
+                    action,
+                    self.config.mirror_controls_for_far_sides,
+                )
+            });
+
+            if let Some(direction) = direction {
+                let boost = self.key_hold.press(&direction, Instant::now());
+                self.predict_move(&direction, boost);
+                if let Some(input) = self.create_move_input(direction, boost) {
+                    self.transport
+                        .send_client_input(input)
+                        .await
+                        .unwrap_or_else(|e| error!("Failed to send move input: {}", e));
+                }
+            }
         }
         Ok(None)
     }
@@ -219,20 +419,51 @@ impl Update for GameBoard {
 impl Render for GameBoard {
     fn render(&self, frame: &mut Frame) {
         if let Ok(game) = self.game.lock() {
+            // drawn first so the predicted paddle covers it wherever they overlap
+            if self.show_debug_overlay {
+                self.render_ghost_paddle(&game, frame);
+            }
+            let trail = self.ball_trail.lock().ok();
             render_game(
                 &game,
-                self.our_player_id,
-                self.config.player_color,
-                self.config.other_players_color,
+                self.focused_player_id,
+                |position| self.config.position_color(position),
+                trail.as_deref(),
                 frame,
             );
+            let goal_seen_at = self.goal_seen_at.lock().ok().and_then(|seen_at| *seen_at);
+            // servers that do not send the pause use the default one
+            let goal_timeout = game.goal_timeout_ms.unwrap_or(DEFAULT_GOAL_TIMEOUT);
+            if !self.config.reduced_motion
+                && show_goal_flash(goal_seen_at, chrono::Utc::now(), goal_timeout)
+            {
+                render_goal_flash(frame, game.board());
+            }
         } else {
             error!("Failed to lock game");
         }
-        if self.disconnected.load(Ordering::Relaxed) {
-            render_disconnect_popup(frame, frame.area());
+        if self.show_debug_overlay {
+            if let Ok(stats) = self.receive_stats.lock() {
+                render_debug_overlay(
+                    frame,
+                    format_stats(
+                        self.measured_fps,
+                        stats.last_received.elapsed(),
+                        stats.packets_per_sec,
+                    ),
+                );
+            }
+        }
+        match self.connection_state() {
+            ConnectionState::Connected => {}
+            ConnectionState::Reconnecting => render_reconnecting(frame, frame.area()),
+            ConnectionState::Disconnected => render_disconnect_popup(frame, frame.area()),
         }
     }
+
+    fn set_measured_fps(&mut self, fps: f64) {
+        self.measured_fps = fps;
+    }
 }
 
 impl Drop for GameBoard {
@@ -240,3 +471,160 @@ impl Drop for GameBoard {
         self.cancellation_token.cancel();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::net::udp::UdpClient;
+    use crate::common::{Game, Player};
+    use std::time::Duration;
+    use tokio::net::UdpSocket;
+
+    #[test]
+    fn test_move_direction() {
+        use Direction::{Negative, Positive};
+        use PlayerPosition::{Bottom, Left, Right, Top};
+
+        for (position, mirror, up, down, left, right) in [
+            (Left, false, Some(Negative), Some(Positive), None, None),
+            (Left, true, Some(Negative), Some(Positive), None, None),
+            (Right, false, Some(Negative), Some(Positive), None, None),
+            (Right, true, Some(Positive), Some(Negative), None, None),
+            (Bottom, false, None, None, Some(Negative), Some(Positive)),
+            (Bottom, true, None, None, Some(Negative), Some(Positive)),
+            (Top, false, None, None, Some(Negative), Some(Positive)),
+            (Top, true, None, None, Some(Positive), Some(Negative)),
+        ] {
+            assert_eq!(move_direction(position, Action::MoveUp, mirror), up);
+            assert_eq!(move_direction(position, Action::MoveDown, mirror), down);
+            assert_eq!(move_direction(position, Action::MoveLeft, mirror), left);
+            assert_eq!(move_direction(position, Action::MoveRight, mirror), right);
+        }
+        assert_eq!(move_direction(Top, Action::Ready, true), None);
+    }
+
+    #[test]
+    fn test_next_focus() {
+        let mut game = Game::new();
+        let now = chrono::Utc::now();
+        let mut ids = Vec::new();
+        for (i, position) in [
+            Some(PlayerPosition::Left),
+            Some(PlayerPosition::Right),
+            None, // eliminated, no paddle left to watch
+            Some(PlayerPosition::Top),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut player = Player::new(format!("Player {}", i + 1), false);
+            player.position = position;
+            player.joined_at = now + chrono::Duration::seconds(i as i64);
+            ids.push(player.id);
+            game.add_player(player).unwrap();
+        }
+        let game = GameDto::from(game);
+
+        assert_eq!(next_focus(&game, ids[0]), ids[1]);
+        assert_eq!(next_focus(&game, ids[1]), ids[3]);
+        assert_eq!(next_focus(&game, ids[3]), ids[0]);
+        // someone not on the board starts over from the first player
+        assert_eq!(next_focus(&game, ids[2]), ids[0]);
+        assert_eq!(next_focus(&game, Uuid::new_v4()), ids[0]);
+
+        let empty = GameDto::from(Game::new());
+        let alone = Uuid::new_v4();
+        assert_eq!(next_focus(&empty, alone), alone);
+    }
+
+    #[tokio::test]
+    async fn test_leave_sends_disconnect() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server_socket.local_addr().unwrap().to_string();
+        let transport = Arc::new(Transport::Udp(UdpClient::new(&server_addr).unwrap()));
+        let game = Game::new();
+        let our_player_id = Uuid::new_v4();
+        let mut game_board = GameBoard::new(
+            GameDto::from(game.clone()),
+            our_player_id,
+            transport,
+            config::Config::default(),
+            None,
+        )
+        .unwrap();
+
+        let next = game_board.update(Some(KeyCode::Esc)).await.unwrap();
+        assert!(next.is_some());
+
+        // skip pings that might have been sent in the meantime
+        let mut buf = [0; 1024];
+        let disconnect = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let (len, _) = server_socket.recv_from(&mut buf).await.unwrap();
+                let input: ClientInput = rmp_serde::from_slice(&buf[..len]).unwrap();
+                if input.action == ClientInputType::Disconnect {
+                    return input;
+                }
+            }
+        })
+        .await
+        .expect("Expected a disconnect message");
+
+        assert_eq!(disconnect.game_id, game.id.to_string());
+        assert_eq!(disconnect.player_id, our_player_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_reduced_motion_skips_motion_effects() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server_socket.local_addr().unwrap().to_string();
+        let mut game = Game::new();
+        let mut us = Player::new("us".to_string(), false);
+        us.position = Some(PlayerPosition::Bottom);
+        let our_player_id = us.id;
+        game.add_player(us).unwrap();
+
+        let board = |reduced_motion| {
+            let transport = Arc::new(Transport::Udp(UdpClient::new(&server_addr).unwrap()));
+            let config = config::Config {
+                reduced_motion,
+                ..Default::default()
+            };
+            let game_board =
+                GameBoard::new(GameDto::from(&game), our_player_id, transport, config, None)
+                    .unwrap();
+            *game_board.goal_seen_at.lock().unwrap() = Some(chrono::Utc::now());
+            game_board
+        };
+        let paddle_position = |game_board: &GameBoard| {
+            game_board.game.lock().unwrap().players[&our_player_id].paddle_position
+        };
+        let shows_goal_banner = |game_board: &GameBoard| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+            terminal.draw(|frame| game_board.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            screen.contains("GOAL!")
+        };
+
+        let animated = board(false);
+        let start = paddle_position(&animated);
+        animated.predict_move(&Direction::Positive, 1.0);
+        assert_ne!(paddle_position(&animated), start);
+        assert!(animated.ball_trail.lock().unwrap().length() > 0);
+        assert!(shows_goal_banner(&animated));
+
+        let reduced = board(true);
+        reduced.predict_move(&Direction::Positive, 1.0);
+        assert_eq!(paddle_position(&reduced), start);
+        assert_eq!(reduced.ball_trail.lock().unwrap().length(), 0);
+        assert!(!shows_goal_banner(&reduced));
+    }
+}
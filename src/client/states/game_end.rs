@@ -12,7 +12,7 @@ use uuid::Uuid;
 use crate::client::error::ClientError;
 use crate::{
     client::{config, net::tcp::TcpClient, states::lobby::Lobby},
-    common::models::{GameDto, PlayerDto},
+    common::models::{score_tie_break, GameDto, GameMode, PlayerDto},
 };
 
 use super::{
@@ -27,6 +27,7 @@ pub struct GameEnd {
     config: config::Config,
     tcp_client: TcpClient,
     error_message: Option<String>,
+    password: Option<String>, // a private game asks for it again when playing again
 }
 
 impl GameEnd {
@@ -34,6 +35,7 @@ impl GameEnd {
         game: GameDto,
         our_player_id: Uuid,
         config: config::Config,
+        password: Option<String>,
     ) -> Result<Self, ClientError> {
         Ok(Self {
             game,
@@ -41,10 +43,30 @@ impl GameEnd {
             tcp_client: TcpClient::new(&config.api_url),
             config,
             error_message: None,
+            password,
         })
     }
 }
 
+/// Players by their standing in `mode`, the best first, whoever reached an equal score first goes ahead
+fn ranked(mut players: Vec<&PlayerDto>, mode: GameMode) -> Vec<&PlayerDto> {
+    players.sort_by(|a, b| {
+        b.standing(mode)
+            .cmp(&a.standing(mode))
+            .then_with(|| score_tie_break(a.last_score_at, b.last_score_at))
+    });
+    players
+}
+
+/// Player with the most returns, none when nobody returned the ball
+fn most_returns<'a>(players: &[&'a PlayerDto]) -> Option<&'a PlayerDto> {
+    players
+        .iter()
+        .copied()
+        .filter(|player| player.returns > 0)
+        .max_by_key(|player| player.returns)
+}
+
 impl State for GameEnd {}
 
 impl HasConfig for GameEnd {
@@ -69,18 +91,23 @@ impl Update for GameEnd {
                     log::info!("Player wants to play again");
                     match self
                         .tcp_client
-                        .play_again(self.game.id, Some(self.config.player_name.clone()))
+                        .play_again(
+                            self.game.id,
+                            Some(self.config.player_name.clone()),
+                            self.password.clone(),
+                        )
                         .await
                     {
-                        Ok(player) => {
+                        Ok(joined) => {
                             log::info!("Play again request sent");
                             match self.tcp_client.get_game(self.game.id).await {
                                 Ok(game) => {
                                     log::info!("Game received");
                                     return Ok(Some(Box::new(Lobby::new(
                                         game,
-                                        player.id,
+                                        joined,
                                         self.config.clone(),
+                                        self.password.clone(),
                                     )?)));
                                 }
                                 Err(e) => {
@@ -125,23 +152,31 @@ impl Render for GameEnd {
         });
         // let inner = render_inner_rectangle(frame, outer_rect);
 
-        // Sort players by score (assuming PlayerDto has a `score` field)
-        let mut players: Vec<&PlayerDto> = self.game.players.values().collect();
-        players.sort_by(|a, b| b.score.cmp(&a.score)); // Sort in descending order
+        let players = ranked(self.game.players.values().collect(), self.game.mode);
 
         // Define podium heights
         let podium_heights = [inner.height / 2, inner.height / 3, inner.height / 4];
         let podium_width = inner.width / 5; // Adjust width to fit all podiums
 
         // Create a layout for the podiums and the 4th player message
-        let [_, podium_area, _, humiliation_area, _] = Layout::vertical(vec![
-            Constraint::Percentage(25),
-            Constraint::Percentage(60),
-            Constraint::Length(1),
-            Constraint::Length(1),
-            Constraint::Percentage(10),
-        ])
-        .areas(inner);
+        let [_, forfeit_area, podium_area, _, humiliation_area, stats_area, _] =
+            Layout::vertical(vec![
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Percentage(60),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Percentage(10),
+            ])
+            .areas(inner);
+
+        if let Some(name) = &self.game.forfeited_by {
+            frame.render_widget(
+                Paragraph::new(Line::from(format!(" {} forfeited ", name).yellow())).centered(),
+                forfeit_area,
+            );
+        }
 
         // Create a layout for the podiums with 1st place centered
         let [second_place_area, first_place_area, third_place_area] = Layout::horizontal(vec![
@@ -233,5 +268,77 @@ impl Render for GameEnd {
 
             frame.render_widget(humiliation_paragraph, humiliation_area);
         }
+
+        if let Some(player) = most_returns(&players) {
+            frame.render_widget(
+                Paragraph::new(Line::from(format!(
+                    " Most returns: {} ({}) ",
+                    player.name, player.returns
+                )))
+                .centered(),
+                stats_area,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{models::PlayerDto, Player};
+
+    fn player(name: &str, returns: u32) -> PlayerDto {
+        let mut player = PlayerDto::from(Player::new(name.to_string(), false));
+        player.returns = returns;
+        player
+    }
+
+    #[test]
+    fn test_most_returns() {
+        let alice = player("alice", 4);
+        let bob = player("bob", 9);
+        let carol = player("carol", 0);
+
+        assert_eq!(
+            most_returns(&[&alice, &bob, &carol]).map(|player| player.id),
+            Some(bob.id)
+        );
+        assert!(most_returns(&[&carol]).is_none());
+    }
+
+    #[test]
+    fn test_ranked_by_who_reached_the_score_first() {
+        let now = chrono::Utc::now();
+        let mut alice = player("alice", 0);
+        alice.score = 3;
+        alice.last_score_at = Some(now);
+        let mut bob = player("bob", 0);
+        bob.score = 3;
+        bob.last_score_at = Some(now - chrono::Duration::seconds(5));
+        let mut carol = player("carol", 0);
+        carol.score = 5;
+        carol.last_score_at = Some(now);
+
+        let ranked: Vec<_> = ranked(vec![&alice, &bob, &carol], GameMode::Score)
+            .iter()
+            .map(|player| player.name.as_str())
+            .collect();
+        assert_eq!(ranked, vec!["carol", "bob", "alice"]);
+    }
+
+    #[test]
+    fn test_ranked_by_lives() {
+        let mut alice = player("alice", 0);
+        alice.score = 5;
+        alice.lives = 0;
+        let mut bob = player("bob", 0);
+        bob.score = 1;
+        bob.lives = 2;
+
+        let ranked: Vec<_> = ranked(vec![&alice, &bob], GameMode::Lives)
+            .iter()
+            .map(|player| player.name.as_str())
+            .collect();
+        assert_eq!(ranked, vec!["bob", "alice"]);
     }
 }
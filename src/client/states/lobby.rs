@@ -1,17 +1,26 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use crate::client::config;
 use crate::client::error::ClientError;
+use crate::client::key_bindings::Action;
 use crate::client::net::tcp::TcpClient;
-use crate::client::net::udp::UdpClient;
-use crate::common::models::{ClientInput, ClientInputType, GameDto, GameState};
-use crate::common::Game;
+use crate::client::net::transport::Transport;
+use crate::common::models::{ClientInput, ClientInputType, GameDto, GameState, PROTOCOL_VERSION};
+use crate::common::{Game, JoinGameResponse};
 
 use super::create_or_join_lobby::CreateOrJoinLobby;
 use super::game_board::GameBoard;
 use super::traits::{HasConfig, Render, State, Update};
-use super::utils::render::{render_disconnect_popup, render_outer_rectangle, render_player_list};
+use super::utils::connection::{
+    render_reconnecting, ConnectionState, ConnectionWatch, CHECK_INTERVAL,
+};
+use super::utils::render::{
+    countdown_text, render_disconnect_popup, render_error_popup, render_outer_rectangle,
+    render_player_list,
+};
+use super::utils::sequence::apply_if_newer;
 
 use arboard::Clipboard;
 use crossterm::event::KeyCode;
@@ -32,54 +41,86 @@ pub struct Lobby {
     cancellation_token: CancellationToken,
     _receive_update_handle: JoinHandle<Result<(), ClientError>>,
     _ping_handle: JoinHandle<()>,
-    udp_client: Arc<UdpClient>,
+    transport: Arc<Transport>,
     tcp_client: Arc<TcpClient>,
     config: config::Config,
-    disconnected: Arc<AtomicBool>,
+    connection: Arc<Mutex<ConnectionWatch>>,
+    version_mismatch: Arc<AtomicBool>, // the server speaks another protocol version than we do
+    password: Option<String>,          // we joined with, playing again needs it once more
 }
 
 impl Lobby {
+    /// `joined` is the server's reply to our join, it carries our reconnect token
+    /// and the UDP endpoint to use unless the user set one,
+    /// `password` is the one we joined a private game with
     pub fn new(
         game: Game,
-        our_player_id: Uuid,
-        config: config::Config,
+        joined: JoinGameResponse,
+        mut config: config::Config,
+        password: Option<String>,
     ) -> Result<Self, ClientError> {
-        let udp_client = Arc::new(UdpClient::new(&config.socket_addr)?);
+        if let Some(server_info) = &joined.server_info {
+            config.advertised_socket_addr = Some(server_info.socket_addr(&config.api_url));
+        }
+        let our_player_id = joined.player.id;
+        let reconnect_token = joined.reconnect_token;
+        let transport = Arc::new(Transport::new(&config, game.id)?);
 
         let tcp_client = Arc::new(TcpClient::new(&config.api_url));
 
         let cancellation_token = CancellationToken::new();
         let game_id = game.id;
+        // sequence of the last applied snapshot, moved into the receive task
+        let mut last_sequence = game.sequence;
         let game_dto = Arc::new(Mutex::new(GameDto::from(game)));
-        let disconnected = Arc::new(AtomicBool::new(false));
+        let connection = Arc::new(Mutex::new(ConnectionWatch::new(Instant::now())));
+        let version_mismatch = Arc::new(AtomicBool::new(false));
 
         // Start a task to receive updates
         let game_clone = Arc::clone(&game_dto);
-        let udp_client_clone = Arc::clone(&udp_client);
+        let transport_clone = Arc::clone(&transport);
         let cancellation_token_clone = cancellation_token.clone();
-        let disconnected_clone = Arc::clone(&disconnected);
+        let connection_clone = Arc::clone(&connection);
+        let version_mismatch_clone = Arc::clone(&version_mismatch);
         let receive_update_handle = tokio::spawn(async move {
             // send introduction message
             let client_input = ClientInput::new(
                 game_id.to_string(),
                 our_player_id.to_string(),
                 ClientInputType::JoinGame,
-            );
-            udp_client_clone.send_client_input(client_input).await?;
+            )
+            .with_reconnect_token(reconnect_token);
+            transport_clone.send_client_input(client_input).await?;
 
             loop {
                 tokio::select! {
                     // Exit loop on cancellation
                     _ = cancellation_token_clone.cancelled() => break,
-                    _ = tokio::time::sleep(std::time::Duration::from_secs(3)) => {
-                        disconnected_clone.store(true, Ordering::Relaxed);
+                    _ = tokio::time::sleep(CHECK_INTERVAL) => {
+                        if let Ok(mut connection) = connection_clone.lock() {
+                            connection.update(Instant::now());
+                        }
                     }
                     // Process incoming game updates
-                    result = udp_client_clone.recv_updated_game() => {
+                    result = transport_clone.recv_updated_game() => {
+                        if result.is_ok() {
+                            if let Ok(mut connection) = connection_clone.lock() {
+                                connection.record(Instant::now());
+                            }
+                        }
                         match result {
+                            Ok(updated_game) if updated_game.protocol_version != PROTOCOL_VERSION => {
+                                error!(
+                                    "Server uses protocol version {}, we use {}",
+                                    updated_game.protocol_version, PROTOCOL_VERSION
+                                );
+                                version_mismatch_clone.store(true, Ordering::Relaxed);
+                            }
                             Ok(updated_game) => {
                                 if let Ok(mut current_game) = game_clone.lock() {
-                                    *current_game = updated_game;
+                                    if !apply_if_newer(&mut current_game, &mut last_sequence, updated_game) {
+                                        debug!("Dropped an out of order game update");
+                                    }
                                 } else {
                                     error!("Failed to lock game");
                                 }
@@ -94,7 +135,7 @@ impl Lobby {
         });
 
         // Start a task to send ping messages
-        let udp_client_clone = Arc::clone(&udp_client);
+        let transport_clone = Arc::clone(&transport);
         let cancellation_token_clone = cancellation_token.clone();
         let ping_handle = tokio::spawn(async move {
             let ping_interval = std::time::Duration::from_secs(1);
@@ -108,7 +149,7 @@ impl Lobby {
 
                 tokio::select! {
                     _ = cancellation_token_clone.cancelled() => break,
-                    _ = udp_client_clone.send_client_input(client_input) => {
+                    _ = transport_clone.send_client_input(client_input) => {
                         debug!("Sent ping message");
                     }
                 }
@@ -119,17 +160,42 @@ impl Lobby {
             game: game_dto,
             game_id,
             our_player_id,
-            udp_client,
+            transport,
             tcp_client,
             cancellation_token,
             _receive_update_handle: receive_update_handle,
             _ping_handle: ping_handle,
             config,
-            disconnected,
+            connection,
+            version_mismatch,
+            password,
         })
     }
 }
 
+impl Lobby {
+    /// Kept up to date by the receive task, a poisoned lock is taken as a lost connection
+    fn connection_state(&self) -> ConnectionState {
+        self.connection
+            .lock()
+            .map_or(ConnectionState::Disconnected, |connection| {
+                connection.state()
+            })
+    }
+
+    /// Lets the server free our slot right away instead of waiting for the ping timeout
+    async fn send_disconnect(&self) {
+        let client_input = ClientInput::new(
+            self.game_id.to_string(),
+            self.our_player_id.to_string(),
+            ClientInputType::Disconnect,
+        );
+        if let Err(e) = self.transport.send_client_input(client_input).await {
+            error!("Failed to send disconnect: {}", e);
+        }
+    }
+}
+
 impl State for Lobby {}
 
 impl HasConfig for Lobby {
@@ -151,8 +217,9 @@ impl Update for Lobby {
                 return Ok(Some(Box::new(GameBoard::new(
                     game.clone(),
                     self.our_player_id,
-                    Arc::clone(&self.udp_client),
+                    Arc::clone(&self.transport),
                     self.config.clone(),
+                    self.password.clone(),
                 )?)));
             }
         } else {
@@ -174,29 +241,39 @@ impl Update for Lobby {
                     }
                 }
 
-                KeyCode::Enter => {
+                key if self.config.key_bindings.is(Action::Ready, key) => {
                     // send player ready
                     let client_input = ClientInput::new(
                         self.game_id.to_string(),
                         self.our_player_id.to_string(),
                         ClientInputType::PlayerReady,
                     );
-                    self.udp_client.send_client_input(client_input).await?;
+                    self.transport.send_client_input(client_input).await?;
                     info!("Toggle player ready");
                 }
-                KeyCode::Char('a') | KeyCode::Char('A') => {
+                key if self.config.key_bindings.is(Action::AddBot, key) => {
                     match self.tcp_client.add_bot(self.game_id).await {
                         Err(e) => info!("Add bot failed: {}", e),
                         Ok(_) => info!("Add bot called"),
                     }
                 }
-                KeyCode::Char('d') | KeyCode::Char('D') => {
+                key if self.config.key_bindings.is(Action::RemoveBot, key) => {
                     match self.tcp_client.remove_bot(self.game_id).await {
                         Err(e) => info!("Remove bot failed: {}", e),
                         Ok(_) => info!("Remove bot called"),
                     }
                 }
-                KeyCode::Esc => {
+                key if self.config.key_bindings.is(Action::ReadyBots, key) => {
+                    let client_input = ClientInput::new(
+                        self.game_id.to_string(),
+                        self.our_player_id.to_string(),
+                        ClientInputType::ToggleBotsReady,
+                    );
+                    self.transport.send_client_input(client_input).await?;
+                    info!("Toggle bots ready");
+                }
+                key if self.config.key_bindings.is(Action::Leave, key) => {
+                    self.send_disconnect().await;
                     info!("Moving from Lobby to CreateOrJoinLobby");
                     return Ok(Some(Box::new(CreateOrJoinLobby::new(self.config.clone())?)));
                 }
@@ -209,18 +286,31 @@ impl Update for Lobby {
 
 impl Render for Lobby {
     fn render(&self, frame: &mut Frame) {
+        let bindings = &self.config.key_bindings;
         let outer_rect = render_outer_rectangle(
             frame,
             " quadropong - Lobby ",
             vec![
                 " Leave Game ".into(),
-                "<Esc> ".light_blue().bold(),
+                format!("<{}> ", bindings.label(Action::Leave))
+                    .light_blue()
+                    .bold(),
                 "| Ready ".into(),
-                "<Enter> ".light_blue().bold(),
+                format!("<{}> ", bindings.label(Action::Ready))
+                    .light_blue()
+                    .bold(),
                 "| Add bot ".into(),
-                "<A> ".light_cyan().bold(),
+                format!("<{}> ", bindings.label(Action::AddBot))
+                    .light_cyan()
+                    .bold(),
                 "| Delete bot ".into(),
-                "<D> ".light_cyan().bold(),
+                format!("<{}> ", bindings.label(Action::RemoveBot))
+                    .light_cyan()
+                    .bold(),
+                "| Ready bots ".into(),
+                format!("<{}> ", bindings.label(Action::ReadyBots))
+                    .light_cyan()
+                    .bold(),
             ],
         );
         let inner_rect = outer_rect.inner(Margin {
@@ -263,14 +353,31 @@ impl Render for Lobby {
                 Line::from(vec![" Copy ".into(), "<TAB> ".green().bold()]).right_aligned(),
             );
             let inner_lobby_id_area = lobby_id_block.inner(lobby_id_area);
-            let lobby_id_paragraph = Paragraph::new(format!(" Game ID - {}", game.id));
+            let mut lobby_id_line = match &game.name {
+                Some(name) => Line::from(format!(" {} - {}", name, game.id)),
+                None => Line::from(format!(" Game ID - {}", game.id)),
+            };
+            if let Some(remaining_ms) = game.start_countdown_ms {
+                lobby_id_line.push_span(
+                    format!("   Starting in {}", countdown_text(remaining_ms))
+                        .yellow()
+                        .bold(),
+                );
+            }
+            let lobby_id_paragraph = Paragraph::new(lobby_id_line);
             frame.render_widget(lobby_id_paragraph, inner_lobby_id_area);
             frame.render_widget(lobby_id_block, lobby_id_area);
 
             render_player_list(frame, &list, lobby_area);
 
-            if self.disconnected.load(Ordering::Relaxed) {
-                render_disconnect_popup(frame, lobby_area);
+            if self.version_mismatch.load(Ordering::Relaxed) {
+                render_error_popup(frame, lobby_area, " VERSION MISMATCH, UPDATE YOUR CLIENT ");
+            } else {
+                match self.connection_state() {
+                    ConnectionState::Connected => {}
+                    ConnectionState::Reconnecting => render_reconnecting(frame, lobby_area),
+                    ConnectionState::Disconnected => render_disconnect_popup(frame, lobby_area),
+                }
             }
         } else {
             error!("Failed to lock game");
@@ -284,3 +391,142 @@ impl Drop for Lobby {
         self.cancellation_token.cancel();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::models::ServerInfo;
+    use crate::common::Player;
+    use std::time::Duration;
+    use tokio::net::UdpSocket;
+
+    fn joined(server_info: Option<ServerInfo>) -> JoinGameResponse {
+        JoinGameResponse {
+            player: Player::new("player".to_string(), false),
+            reconnect_token: "token".to_string(),
+            server_info,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_leave_sends_disconnect() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let config = config::Config {
+            socket_addr: server_socket.local_addr().unwrap().to_string(),
+            ..Default::default()
+        };
+        let game = Game::new();
+        let joined = joined(None);
+        let our_player_id = joined.player.id;
+        let mut lobby = Lobby::new(game.clone(), joined, config, None).unwrap();
+
+        let next = lobby.update(Some(KeyCode::Esc)).await.unwrap();
+        assert!(next.is_some());
+
+        // the join message comes first, skip anything that is not the disconnect
+        let mut buf = [0; 1024];
+        let disconnect = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let (len, _) = server_socket.recv_from(&mut buf).await.unwrap();
+                let input: ClientInput = rmp_serde::from_slice(&buf[..len]).unwrap();
+                if input.action == ClientInputType::Disconnect {
+                    return input;
+                }
+            }
+        })
+        .await
+        .expect("Expected a disconnect message");
+
+        assert_eq!(disconnect.game_id, game.id.to_string());
+        assert_eq!(disconnect.player_id, our_player_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_udp_goes_to_server_provided_address() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        // the default address is not where the server takes UDP
+        let config = config::Config::default();
+        let joined = joined(Some(ServerInfo {
+            udp_port: 1,
+            advertised_udp_addr: Some(server_socket.local_addr().unwrap().to_string()),
+        }));
+        let our_player_id = joined.player.id;
+        let game = Game::new();
+        let lobby = Lobby::new(game.clone(), joined, config, None).unwrap();
+
+        let mut buf = [0; 1024];
+        let (len, _) =
+            tokio::time::timeout(Duration::from_secs(2), server_socket.recv_from(&mut buf))
+                .await
+                .expect("Expected the join message on the advertised address")
+                .unwrap();
+        let join: ClientInput = rmp_serde::from_slice(&buf[..len]).unwrap();
+
+        assert_eq!(join.action, ClientInputType::JoinGame);
+        assert_eq!(join.player_id, our_player_id.to_string());
+        assert_eq!(join.reconnect_token.as_deref(), Some("token"));
+        assert_eq!(
+            lobby.config.udp_addr(),
+            server_socket.local_addr().unwrap().to_string()
+        );
+        // the advertised address is not saved with the settings
+        assert_eq!(lobby.config.socket_addr, config::default_socket_addr());
+    }
+
+    #[tokio::test]
+    async fn test_udp_keeps_address_set_by_user() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let config = config::Config {
+            socket_addr: server_socket.local_addr().unwrap().to_string(),
+            ..Default::default()
+        };
+        let joined = joined(Some(ServerInfo {
+            udp_port: 1,
+            advertised_udp_addr: Some("127.0.0.1:1".to_string()),
+        }));
+        let lobby = Lobby::new(Game::new(), joined, config, None).unwrap();
+
+        let mut buf = [0; 1024];
+        let (len, _) =
+            tokio::time::timeout(Duration::from_secs(2), server_socket.recv_from(&mut buf))
+                .await
+                .expect("Expected the join message on the address set by the user")
+                .unwrap();
+        let join: ClientInput = rmp_serde::from_slice(&buf[..len]).unwrap();
+
+        assert_eq!(join.action, ClientInputType::JoinGame);
+        assert_eq!(
+            lobby.config.udp_addr(),
+            server_socket.local_addr().unwrap().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_detects_protocol_version_mismatch() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let config = config::Config {
+            socket_addr: server_socket.local_addr().unwrap().to_string(),
+            ..Default::default()
+        };
+        let game = Game::new();
+        let lobby = Lobby::new(game.clone(), joined(None), config, None).unwrap();
+
+        let mut buf = [0; 1024];
+        let (_, client_addr) = server_socket.recv_from(&mut buf).await.unwrap();
+        // a state from a server before versioning
+        let mut old_state = GameDto::from(game);
+        old_state.protocol_version = 0;
+        server_socket
+            .send_to(&rmp_serde::to_vec(&old_state).unwrap(), client_addr)
+            .await
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while !lobby.version_mismatch.load(Ordering::Relaxed) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Expected the mismatch to be detected");
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops::Range;
+
+use super::create_or_join_lobby::CreateOrJoinLobby;
+use super::lobby::Lobby;
+use super::password_prompt::PasswordPrompt;
+use super::traits::{HasConfig, Render, State, Update};
+use super::utils::render::{render_inner_rectangle, render_list, render_outer_rectangle};
+use crate::client::config;
+use crate::client::error::ClientError;
+use crate::client::net::error::TcpError;
+use crate::client::net::tcp::TcpClient;
+use crate::common::models::GameState;
+use crate::common::Game;
+
+use axum::async_trait;
+use crossterm::event::KeyCode;
+use log::{error, info};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Lists the games on the server and joins the selected one
+pub struct LobbyBrowser {
+    games: Vec<Game>,
+    selected: usize,
+    error_message: Option<String>,
+    tcp_client: TcpClient,
+    config: config::Config,
+}
+
+impl LobbyBrowser {
+    pub fn new(games: Vec<Game>, config: config::Config) -> Result<Self, ClientError> {
+        let mut browser = Self {
+            games: Vec::new(),
+            selected: 0,
+            error_message: None,
+            tcp_client: TcpClient::new(&config.api_url),
+            config,
+        };
+        browser.set_games(games);
+        Ok(browser)
+    }
+
+    /// Open games first, the oldest of them on top as they waited the longest
+    fn set_games(&mut self, mut games: Vec<Game>) {
+        games.sort_by_key(|game| (game.state != GameState::WaitingForPlayers, game.created_at));
+        self.games = games;
+        self.selected = self.selected.min(self.games.len().saturating_sub(1));
+    }
+
+    fn next(&mut self) {
+        if !self.games.is_empty() {
+            self.selected = (self.selected + 1) % self.games.len();
+        }
+    }
+
+    fn previous(&mut self) {
+        if self.selected == 0 {
+            self.selected = self.games.len().saturating_sub(1);
+        } else {
+            self.selected -= 1;
+        }
+    }
+
+    async fn refresh(&mut self) {
+        match self.tcp_client.list_games().await {
+            Ok(games) => {
+                self.set_games(games);
+                self.error_message = None;
+            }
+            Err(e) => {
+                error!("Error listing games: {}", e);
+                self.error_message = Some("Failed to load the games".to_string());
+            }
+        }
+    }
+
+    async fn join_selected(&mut self) -> Result<Option<Box<dyn State>>, ClientError> {
+        let Some(game) = self.games.get(self.selected) else {
+            return Ok(None);
+        };
+        let game_id = game.id;
+        if game.is_private {
+            info!("Moving from LobbyBrowser to PasswordPrompt");
+            return Ok(Some(Box::new(PasswordPrompt::new(
+                game_id,
+                self.config.clone(),
+            )?)));
+        }
+
+        let joined = match self
+            .tcp_client
+            .join_game(game_id, Some(self.config.player_name.clone()), None)
+            .await
+        {
+            Ok(joined) => self
+                .tcp_client
+                .get_game(game_id)
+                .await
+                .map(|game| (game, joined)),
+            Err(e) => Err(e),
+        };
+
+        match joined {
+            Ok((game, joined)) => {
+                info!(
+                    "Moving from LobbyBrowser to Lobby, game id: {:?}, our player id: {:?}",
+                    game.id, joined.player.id
+                );
+                Ok(Some(Box::new(Lobby::new(
+                    game,
+                    joined,
+                    self.config.clone(),
+                    None,
+                )?)))
+            }
+            Err(e) => {
+                error!("Error joining game: {}", e);
+                self.error_message = Some(match e {
+                    TcpError::ServerError(err) => err,
+                    _ => "There was an issue joining the game, please try again".to_string(),
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn state_label(state: &GameState) -> &'static str {
+    match state {
+        GameState::WaitingForPlayers => "open",
+        GameState::Starting => "starting",
+        GameState::Active => "playing",
+        GameState::Paused => "paused",
+        GameState::Finished => "finished",
+    }
+}
+
+/// One line of the list, the start of the game id, player count, state and whether it needs a password
+fn game_entry(game: &Game) -> String {
+    let id = game.id.to_string();
+    format!(
+        "{}  {}/{} players  {}{}",
+        &id[..8],
+        game.players.len(),
+        game.settings.max_players,
+        state_label(&game.state),
+        if game.is_private { "  private" } else { "" }
+    )
+}
+
+/// Entries shown in a list `height` lines tall, scrolled just enough to keep `selected` visible
+fn visible_range(len: usize, selected: usize, height: usize) -> Range<usize> {
+    let height = height.max(1);
+    let start = (selected + 1).saturating_sub(height);
+    start..len.min(start + height)
+}
+
+impl State for LobbyBrowser {}
+
+impl HasConfig for LobbyBrowser {
+    fn config(&self) -> config::Config {
+        self.config.clone()
+    }
+}
+
+#[async_trait]
+impl Update for LobbyBrowser {
+    async fn update(
+        &mut self,
+        key_code: Option<KeyCode>,
+    ) -> Result<Option<Box<dyn State>>, ClientError> {
+        if let Some(key_code) = key_code {
+            match key_code {
+                KeyCode::Up => self.previous(),
+                KeyCode::Down => self.next(),
+                KeyCode::Char('r') => self.refresh().await,
+                KeyCode::Enter => return self.join_selected().await,
+                KeyCode::Esc => {
+                    info!("Moving from LobbyBrowser to CreateOrJoinLobby");
+                    return Ok(Some(Box::new(CreateOrJoinLobby::new(self.config.clone())?)));
+                }
+                _ => {}
+            };
+        }
+        Ok(None)
+    }
+}
+
+impl Render for LobbyBrowser {
+    fn render(&self, frame: &mut Frame) {
+        let outer_rect = render_outer_rectangle(
+            frame,
+            " quadropong - Lobbies ",
+            vec![
+                " Back".into(),
+                " <Esc> ".light_blue().bold(),
+                "| Up".into(),
+                " <\u{2191}> ".light_blue(),
+                "| Down".into(),
+                " <\u{2193}> ".light_blue(),
+                "| Join".into(),
+                " <Enter> ".light_blue(),
+                "| Refresh".into(),
+                " <R> ".light_blue(),
+            ],
+        );
+
+        let inner_rect = render_inner_rectangle(frame, outer_rect);
+
+        let [list_area, error_area] =
+            Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(2)]).areas(inner_rect);
+
+        if self.games.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Line::from("No games yet, create one or refresh")).centered(),
+                list_area,
+            );
+        } else {
+            let range = visible_range(self.games.len(), self.selected, list_area.height as usize);
+            let entries: Vec<String> = self.games[range.clone()].iter().map(game_entry).collect();
+            render_list(frame, &entries, self.selected - range.start, list_area);
+        }
+
+        if let Some(error_message) = &self.error_message {
+            frame.render_widget(
+                Paragraph::new(error_message.clone())
+                    .red()
+                    .centered()
+                    .wrap(Wrap { trim: true }),
+                error_area,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Player;
+
+    #[test]
+    fn test_visible_range() {
+        assert_eq!(visible_range(3, 0, 10), 0..3);
+        assert_eq!(visible_range(20, 0, 5), 0..5);
+        assert_eq!(visible_range(20, 4, 5), 0..5);
+        // the selection stays on the last visible line while scrolling down
+        assert_eq!(visible_range(20, 7, 5), 3..8);
+        assert_eq!(visible_range(20, 19, 5), 15..20);
+    }
+
+    #[test]
+    fn test_open_games_listed_first() {
+        let mut active = Game::new();
+        active.state = GameState::Active;
+        let mut open = Game::new();
+        open.add_player(Player::new("alice".to_string(), false))
+            .unwrap();
+        let open_id = open.id;
+
+        let browser = LobbyBrowser::new(vec![active, open], config::Config::default()).unwrap();
+
+        assert_eq!(browser.games[0].id, open_id);
+        assert!(game_entry(&browser.games[0]).ends_with("1/4 players  open"));
+        assert!(game_entry(&browser.games[1]).ends_with("playing"));
+
+        let mut private = Game::new();
+        private.set_password("secret");
+        assert!(game_entry(&private).ends_with("open  private"));
+    }
+}
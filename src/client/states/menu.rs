@@ -1,13 +1,15 @@
 use super::create_or_join_lobby::CreateOrJoinLobby;
 use super::quit::Quit;
+use super::replay::Replay;
 use super::settings::Settings;
-use super::training::Training;
+use super::training_setup::TrainingSetup;
 use super::traits::{HasConfig, Render, State, Update};
 use super::utils::render::{
     into_title, render_inner_rectangle, render_list, render_outer_rectangle,
 };
 use crate::client::config;
 use crate::client::error::ClientError;
+use crate::client::key_bindings::Action;
 
 use axum::async_trait;
 use crossterm::event::KeyCode;
@@ -18,6 +20,7 @@ use ratatui::Frame;
 pub enum Options {
     Online,
     Training,
+    Replay,
     Settings,
 }
 
@@ -26,6 +29,7 @@ impl std::fmt::Display for Options {
         match self {
             Options::Online => write!(f, " {} ", into_title("play with friends")),
             Options::Training => write!(f, " {} ", into_title("training")),
+            Options::Replay => write!(f, " {} ", into_title("replay")),
             Options::Settings => write!(f, " {} ", into_title("settings")),
         }
     }
@@ -40,7 +44,12 @@ pub struct Menu {
 impl Menu {
     pub fn new(selected: usize, config: config::Config) -> Result<Self, ClientError> {
         Ok(Self {
-            options: vec![Options::Online, Options::Training, Options::Settings],
+            options: vec![
+                Options::Online,
+                Options::Training,
+                Options::Replay,
+                Options::Settings,
+            ],
             selected,
             config,
         })
@@ -83,15 +92,19 @@ impl Update for Menu {
                         return Ok(Some(Box::new(CreateOrJoinLobby::new(self.config.clone())?)));
                     }
                     Options::Training => {
-                        info!("Moving from Menu to Training");
-                        return Ok(Some(Box::new(Training::new(self.config.clone())?)));
+                        info!("Moving from Menu to TrainingSetup");
+                        return Ok(Some(Box::new(TrainingSetup::new(self.config.clone())?)));
+                    }
+                    Options::Replay => {
+                        info!("Moving from Menu to Replay");
+                        return Ok(Some(Box::new(Replay::latest(self.config.clone())?)));
                     }
                     Options::Settings => {
                         info!("Moving from Menu to Settings");
                         return Ok(Some(Box::new(Settings::new(self.config.clone())?)));
                     }
                 },
-                KeyCode::Char('q') | KeyCode::Char('Q') => {
+                key if self.config.key_bindings.is(Action::Quit, key) => {
                     info!("Moving from Menu to Quit");
                     return Ok(Some(Box::new(Quit::new(self.config.clone())?)));
                 }
@@ -109,7 +122,9 @@ impl Render for Menu {
             " quadropong ",
             vec![
                 " Quit".into(),
-                " <Q> ".light_blue().bold(),
+                format!(" <{}> ", self.config.key_bindings.label(Action::Quit))
+                    .light_blue()
+                    .bold(),
                 "| Up".into(),
                 " <\u{2191}> ".light_blue(),
                 "| Down".into(),
@@ -2,9 +2,14 @@ pub mod create_or_join_lobby;
 pub mod game_board;
 pub mod game_end;
 pub mod lobby;
+pub mod lobby_browser;
 pub mod menu;
+pub mod password_prompt;
 pub mod quit;
+pub mod replay;
+pub mod server_busy;
 pub mod settings;
 pub mod training;
+pub mod training_setup;
 pub mod traits;
 pub mod utils;
@@ -0,0 +1,164 @@
+use super::create_or_join_lobby::CreateOrJoinLobby;
+use super::lobby::Lobby;
+use super::traits::{HasConfig, Render, State, Update};
+use super::utils::input::Input;
+use super::utils::render::{render_inner_rectangle, render_outer_rectangle};
+use super::utils::widget::WidgetTrait;
+use crate::client::config;
+use crate::client::error::ClientError;
+use crate::client::net::error::TcpError;
+use crate::client::net::tcp::TcpClient;
+
+use axum::async_trait;
+use crossterm::event::KeyCode;
+use log::{error, info};
+use ratatui::layout::{Constraint, Flex, Layout, Position};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::Frame;
+use uuid::Uuid;
+
+/// Asks for the password of a private game, then joins it
+pub struct PasswordPrompt {
+    game_id: Uuid,
+    password_input: Input,
+    error_message: Option<String>,
+    tcp_client: TcpClient,
+    config: config::Config,
+}
+
+impl PasswordPrompt {
+    pub fn new(game_id: Uuid, config: config::Config) -> Result<Self, ClientError> {
+        Ok(Self {
+            game_id,
+            password_input: Input::new(),
+            error_message: None,
+            tcp_client: TcpClient::new(&config.api_url),
+            config,
+        })
+    }
+
+    async fn join(&mut self) -> Result<Option<Box<dyn State>>, ClientError> {
+        let password = self.password_input.input.clone();
+        let joined = match self
+            .tcp_client
+            .join_game(
+                self.game_id,
+                Some(self.config.player_name.clone()),
+                Some(password.clone()),
+            )
+            .await
+        {
+            Ok(joined) => self
+                .tcp_client
+                .get_game(self.game_id)
+                .await
+                .map(|game| (game, joined)),
+            Err(e) => Err(e),
+        };
+
+        match joined {
+            Ok((game, joined)) => {
+                info!(
+                    "Moving from PasswordPrompt to Lobby, game id: {:?}, our player id: {:?}",
+                    game.id, joined.player.id
+                );
+                Ok(Some(Box::new(Lobby::new(
+                    game,
+                    joined,
+                    self.config.clone(),
+                    Some(password),
+                )?)))
+            }
+            Err(e) => {
+                error!("Error joining private game: {}", e);
+                self.error_message = Some(match e {
+                    TcpError::ServerError(err) => err,
+                    _ => "There was an issue joining the game, please try again".to_string(),
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl State for PasswordPrompt {}
+
+impl HasConfig for PasswordPrompt {
+    fn config(&self) -> config::Config {
+        self.config.clone()
+    }
+}
+
+#[async_trait]
+impl Update for PasswordPrompt {
+    async fn update(
+        &mut self,
+        key_code: Option<KeyCode>,
+    ) -> Result<Option<Box<dyn State>>, ClientError> {
+        if let Some(key_code) = key_code {
+            match key_code {
+                KeyCode::Esc => {
+                    info!("Moving from PasswordPrompt to CreateOrJoinLobby");
+                    return Ok(Some(Box::new(CreateOrJoinLobby::new(self.config.clone())?)));
+                }
+                KeyCode::Enter => return self.join().await,
+                key_code => self.password_input.handle_key_event(key_code),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Render for PasswordPrompt {
+    fn render(&self, frame: &mut Frame) {
+        let outer_rect = render_outer_rectangle(
+            frame,
+            " quadropong - Private Game ",
+            vec![
+                " Back".into(),
+                " <Esc> ".light_blue().bold(),
+                "| Join".into(),
+                " <Enter> ".light_blue(),
+            ],
+        );
+
+        let inner_rect = render_inner_rectangle(frame, outer_rect);
+
+        let [input_area, error_area] =
+            Layout::vertical(vec![Constraint::Length(3), Constraint::Length(2)])
+                .flex(Flex::Center)
+                .areas(inner_rect);
+        let [input_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(input_area);
+        let [error_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(error_area);
+
+        // the password itself is never shown
+        let input_block =
+            Block::bordered().title(Line::from(" This game needs a password ").centered());
+        let inner_input_area = input_block.inner(input_area);
+        frame.render_widget(input_block, input_area);
+        frame.render_widget(
+            Paragraph::new("*".repeat(self.password_input.input.chars().count())),
+            inner_input_area,
+        );
+        frame.set_cursor_position(Position::new(
+            inner_input_area.x + self.password_input.char_index as u16,
+            inner_input_area.y,
+        ));
+
+        if let Some(error_message) = &self.error_message {
+            frame.render_widget(
+                Paragraph::new(error_message.clone())
+                    .red()
+                    .centered()
+                    .wrap(Wrap { trim: true }),
+                error_area,
+            );
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use axum::async_trait;
+use chrono::Utc;
+use crossterm::event::KeyCode;
+use log::{error, info};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::client::config;
+use crate::client::error::ClientError;
+use crate::common::models::GameDto;
+
+use super::menu::Menu;
+use super::traits::{HasConfig, Render, State, Update};
+use super::utils::render::{render_game, render_text_in_center_of_rect};
+
+const REPLAY_EXTENSION: &str = "replay";
+const SEEK_STEP_MS: u64 = 5000;
+
+/// A received game snapshot and the time it arrived at since the recording started
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayFrame {
+    pub elapsed_ms: u64,
+    pub game: GameDto,
+}
+
+/// Replays are a plain sequence of MessagePack encoded frames
+pub fn write_frame<W: Write>(writer: &mut W, frame: &ReplayFrame) -> Result<(), ClientError> {
+    rmp_serde::encode::write(writer, frame).map_err(|e| ClientError::ReplayError(e.to_string()))
+}
+
+/// Reads frames until the end of the input, a frame cut off by an interrupted recording is dropped
+pub fn read_frames<R: Read>(reader: &mut R) -> Result<Vec<ReplayFrame>, ClientError> {
+    let mut frames = Vec::new();
+    loop {
+        match rmp_serde::from_read::<_, ReplayFrame>(&mut *reader) {
+            Ok(frame) => frames.push(frame),
+            Err(rmp_serde::decode::Error::InvalidMarkerRead(e))
+            | Err(rmp_serde::decode::Error::InvalidDataRead(e))
+                if e.kind() == io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            Err(e) => return Err(ClientError::ReplayError(e.to_string())),
+        }
+    }
+    Ok(frames)
+}
+
+/// Appends every game update received in GameBoard to a replay file
+pub struct ReplayRecorder {
+    writer: BufWriter<fs::File>,
+    started_at: Instant,
+}
+
+impl ReplayRecorder {
+    pub fn create(path: &Path) -> Result<Self, ClientError> {
+        Ok(Self {
+            writer: BufWriter::new(fs::File::create(path)?),
+            started_at: Instant::now(),
+        })
+    }
+
+    /// New replay file named after the current time, `None` without a data directory
+    pub fn create_new() -> Result<Option<Self>, ClientError> {
+        match config::Config::get_replays_dir() {
+            Some(dir) => {
+                let path = dir.join(format!(
+                    "{}.{}",
+                    Utc::now().format("%Y-%m-%d-%H-%M-%S"),
+                    REPLAY_EXTENSION
+                ));
+                info!("Recording replay to {}", path.display());
+                Ok(Some(Self::create(&path)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn record(&mut self, game: &GameDto) -> Result<(), ClientError> {
+        let frame = ReplayFrame {
+            elapsed_ms: self.started_at.elapsed().as_millis() as u64,
+            game: game.clone(),
+        };
+        write_frame(&mut self.writer, &frame)
+    }
+}
+
+/// Most recently recorded replay, names sort by the recording time
+pub fn latest_replay() -> Option<PathBuf> {
+    fs::read_dir(config::Config::get_replays_dir()?)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == REPLAY_EXTENSION))
+        .max()
+}
+
+pub struct Replay {
+    config: config::Config,
+    frames: Vec<ReplayFrame>,
+    position_ms: u64,
+    last_update: Instant,
+    paused: bool,
+    message: Option<String>, // shown instead of the game when there is nothing to play
+}
+
+impl Replay {
+    pub fn from_frames(config: config::Config, frames: Vec<ReplayFrame>) -> Self {
+        let message = frames.is_empty().then(|| "The replay is empty".to_string());
+        Self {
+            config,
+            frames,
+            position_ms: 0,
+            last_update: Instant::now(),
+            paused: false,
+            message,
+        }
+    }
+
+    /// Plays the latest recording, or explains why there is none
+    pub fn latest(config: config::Config) -> Result<Self, ClientError> {
+        let Some(path) = latest_replay() else {
+            let mut replay = Self::from_frames(config, Vec::new());
+            replay.message = Some(
+                "No replays recorded yet, enable record_replays in the config file".to_string(),
+            );
+            return Ok(replay);
+        };
+
+        info!("Loading replay {}", path.display());
+        match fs::File::open(&path)
+            .map_err(ClientError::from)
+            .and_then(|file| read_frames(&mut BufReader::new(file)))
+        {
+            Ok(frames) => Ok(Self::from_frames(config, frames)),
+            Err(e) => {
+                error!("Failed to load replay {}: {}", path.display(), e);
+                let mut replay = Self::from_frames(config, Vec::new());
+                replay.message = Some(format!("Failed to load the replay: {}", e));
+                Ok(replay)
+            }
+        }
+    }
+
+    fn duration_ms(&self) -> u64 {
+        self.frames.last().map_or(0, |frame| frame.elapsed_ms)
+    }
+
+    /// Moves the playback position, staying within the recording
+    pub fn advance(&mut self, delta_ms: i64) {
+        self.position_ms = self
+            .position_ms
+            .saturating_add_signed(delta_ms)
+            .min(self.duration_ms());
+    }
+
+    /// The last snapshot received at or before the playback position
+    pub fn current_frame(&self) -> Option<&ReplayFrame> {
+        let index = self
+            .frames
+            .partition_point(|frame| frame.elapsed_ms <= self.position_ms);
+        self.frames.get(index.saturating_sub(1))
+    }
+}
+
+impl State for Replay {}
+
+impl HasConfig for Replay {
+    fn config(&self) -> config::Config {
+        self.config.clone()
+    }
+}
+
+#[async_trait]
+impl Update for Replay {
+    async fn update(
+        &mut self,
+        key_code: Option<KeyCode>,
+    ) -> Result<Option<Box<dyn State>>, ClientError> {
+        let now = Instant::now();
+        if !self.paused {
+            self.advance(now.duration_since(self.last_update).as_millis() as i64);
+        }
+        self.last_update = now;
+
+        if let Some(key_code) = key_code {
+            match key_code {
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Left => self.advance(-(SEEK_STEP_MS as i64)),
+                KeyCode::Right => self.advance(SEEK_STEP_MS as i64),
+                KeyCode::Esc => {
+                    info!("Moving from Replay to Menu");
+                    return Ok(Some(Box::new(Menu::new(2, self.config.clone())?)));
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn format_time(ms: u64) -> String {
+    format!("{:02}:{:02}", ms / 60000, (ms / 1000) % 60)
+}
+
+impl Render for Replay {
+    fn render(&self, frame: &mut Frame) {
+        if let Some(message) = &self.message {
+            render_text_in_center_of_rect(
+                frame,
+                Paragraph::new(vec![
+                    Line::from(message.as_str()),
+                    Line::from(" Back <Esc> ".light_blue()),
+                ]),
+                frame.area(),
+            );
+            return;
+        }
+
+        if let Some(current) = self.current_frame() {
+            render_game(
+                &current.game,
+                Uuid::nil(),
+                |position| self.config.position_color(position),
+                None,
+                frame,
+            );
+        }
+
+        let [_, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let state = if self.paused { "paused" } else { "playing" };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                format!(
+                    " {} {} / {} ",
+                    state,
+                    format_time(self.position_ms),
+                    format_time(self.duration_ms())
+                )
+                .bold(),
+                "| Pause ".into(),
+                "<Space> ".light_blue(),
+                "| Seek ".into(),
+                "<\u{2190}\u{2192}> ".light_blue(),
+                "| Back ".into(),
+                "<Esc> ".light_blue(),
+            ]))
+            .centered(),
+            status_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Game;
+
+    fn frames(times: &[u64]) -> Vec<ReplayFrame> {
+        times
+            .iter()
+            .map(|elapsed_ms| ReplayFrame {
+                elapsed_ms: *elapsed_ms,
+                game: GameDto::from(Game::new()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_record_format() {
+        let recorded = frames(&[0, 16, 33]);
+
+        let mut buf = Vec::new();
+        for frame in &recorded {
+            write_frame(&mut buf, frame).unwrap();
+        }
+
+        // every frame is a single MessagePack value
+        let first: ReplayFrame = rmp_serde::from_slice(&buf).unwrap();
+        assert_eq!(first.elapsed_ms, 0);
+        assert_eq!(first.game.id, recorded[0].game.id);
+
+        let read = read_frames(&mut buf.as_slice()).unwrap();
+        assert_eq!(read.len(), 3);
+        for (read, recorded) in read.iter().zip(&recorded) {
+            assert_eq!(read.elapsed_ms, recorded.elapsed_ms);
+            assert_eq!(read.game.id, recorded.game.id);
+        }
+    }
+
+    #[test]
+    fn test_read_frames_truncated() {
+        let mut buf = Vec::new();
+        for frame in &frames(&[0, 16]) {
+            write_frame(&mut buf, frame).unwrap();
+        }
+        buf.truncate(buf.len() - 5);
+
+        assert_eq!(read_frames(&mut buf.as_slice()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_playback_in_order() {
+        let recorded = frames(&[0, 100, 200, 300]);
+        let ids: Vec<_> = recorded.iter().map(|frame| frame.game.id).collect();
+        let mut replay = Replay::from_frames(config::Config::default(), recorded);
+
+        let mut played = vec![replay.current_frame().unwrap().game.id];
+        for _ in 0..40 {
+            replay.advance(10);
+            let id = replay.current_frame().unwrap().game.id;
+            if played.last() != Some(&id) {
+                played.push(id);
+            }
+        }
+        assert_eq!(played, ids);
+
+        // seeking stays within the recording
+        replay.advance(10000);
+        assert_eq!(replay.position_ms, 300);
+        replay.advance(-150);
+        assert_eq!(replay.current_frame().unwrap().game.id, ids[1]);
+        replay.advance(-10000);
+        assert_eq!(replay.position_ms, 0);
+    }
+}
@@ -0,0 +1,150 @@
+use super::create_or_join_lobby::CreateOrJoinLobby;
+use super::quit::Quit;
+use super::settings::Settings;
+use super::traits::{HasConfig, Render, State, Update};
+use super::utils::render::{
+    into_title, render_inner_rectangle, render_list, render_outer_rectangle,
+};
+use crate::client::config;
+use crate::client::error::ClientError;
+
+use axum::async_trait;
+use crossterm::event::KeyCode;
+use log::info;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
+use ratatui::Frame;
+
+pub enum Options {
+    Retry,
+    ChangeServer,
+    Quit,
+}
+
+impl std::fmt::Display for Options {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Options::Retry => write!(f, " {} ", into_title("retry")),
+            Options::ChangeServer => write!(f, " {} ", into_title("change server")),
+            Options::Quit => write!(f, " {} ", into_title("quit")),
+        }
+    }
+}
+
+pub struct ServerBusy {
+    options: Vec<Options>,
+    selected: usize,
+    config: config::Config,
+}
+
+impl ServerBusy {
+    pub fn new(config: config::Config) -> Result<Self, ClientError> {
+        Ok(Self {
+            options: vec![Options::Retry, Options::ChangeServer, Options::Quit],
+            selected: 0,
+            config,
+        })
+    }
+
+    fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.options.len();
+    }
+
+    fn previous(&mut self) {
+        if self.selected == 0 {
+            self.selected = self.options.len() - 1;
+        } else {
+            self.selected -= 1;
+        }
+    }
+}
+
+impl State for ServerBusy {}
+
+impl HasConfig for ServerBusy {
+    fn config(&self) -> config::Config {
+        self.config.clone()
+    }
+}
+
+#[async_trait]
+impl Update for ServerBusy {
+    async fn update(
+        &mut self,
+        key_code: Option<KeyCode>,
+    ) -> Result<Option<Box<dyn State>>, ClientError> {
+        if let Some(key_code) = key_code {
+            match key_code {
+                KeyCode::Up => self.previous(),
+                KeyCode::Down => self.next(),
+                KeyCode::Enter => match self.options[self.selected] {
+                    Options::Retry => {
+                        info!("Moving from ServerBusy to CreateOrJoinLobby");
+                        return Ok(Some(Box::new(CreateOrJoinLobby::new(self.config.clone())?)));
+                    }
+                    Options::ChangeServer => {
+                        info!("Moving from ServerBusy to Settings");
+                        return Ok(Some(Box::new(Settings::new(self.config.clone())?)));
+                    }
+                    Options::Quit => {
+                        info!("Moving from ServerBusy to Quit");
+                        return Ok(Some(Box::new(Quit::new(self.config.clone())?)));
+                    }
+                },
+                _ => {}
+            };
+        }
+        Ok(None)
+    }
+}
+
+impl Render for ServerBusy {
+    fn render(&self, frame: &mut Frame) {
+        let outer_rect = render_outer_rectangle(
+            frame,
+            " quadropong - Server Unavailable ",
+            vec![
+                " Up".into(),
+                " <\u{2191}> ".light_blue(),
+                "| Down".into(),
+                " <\u{2193}> ".light_blue(),
+                "| Select".into(),
+                " <Enter> ".light_blue(),
+            ],
+        );
+
+        let inner_rect = render_inner_rectangle(frame, outer_rect);
+
+        let [message_area, options_area] =
+            Layout::vertical(vec![Constraint::Length(3), Constraint::Fill(1)]).areas(
+                inner_rect.inner(Margin {
+                    horizontal: 2,
+                    vertical: 1,
+                }),
+            );
+
+        frame.render_widget(
+            Paragraph::new(Line::from(format!(
+                "Server at {} appears to be down or busy",
+                self.config.api_url
+            )))
+            .red()
+            .centered()
+            .wrap(Wrap { trim: true }),
+            message_area,
+        );
+
+        render_list(
+            frame,
+            &self
+                .options
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>(),
+            self.selected,
+            options_area,
+        );
+    }
+}
@@ -1,27 +1,35 @@
 use std::sync::Mutex;
 
 use crate::client::config;
+use crate::client::palette::ColorPalette;
 
 use super::menu::Menu;
 use super::traits::{HasConfig, Render, State, Update};
 use super::utils::input::Input;
+use super::utils::key_binding_editor::KeyBindingEditor;
 use super::utils::render::{into_title, render_outer_rectangle, render_settings};
 use super::utils::slider::Slider;
-use super::utils::widget::{Widget, WidgetTrait};
+use super::utils::toggle::Toggle;
+use super::utils::widget::{get_widget_text, Widget, WidgetTrait};
 use crate::client::error::ClientError;
 
 use axum::async_trait;
 use crossterm::event::KeyCode;
 use log::{error, info};
-use ratatui::layout::Margin;
+use ratatui::layout::{Margin, Rect};
 use ratatui::style::Stylize;
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 pub enum Options {
     PlayerName(Widget),
     PlayerColor(Widget),
-    OtherPlayersColor(Widget),
+    ColorblindPalette(Widget),
     FPS(Widget),
+    ReducedMotion(Widget),
+    KeyBindings(Widget),
+    ServerUrl(Widget),
+    UdpAddress(Widget),
 }
 
 impl std::fmt::Display for Options {
@@ -29,8 +37,12 @@ impl std::fmt::Display for Options {
         match self {
             Options::PlayerName(_) => write!(f, " {} ", into_title("player name")),
             Options::PlayerColor(_) => write!(f, " {} ", into_title("plyer color")),
-            Options::OtherPlayersColor(_) => write!(f, " {} ", into_title("other player color")),
+            Options::ColorblindPalette(_) => write!(f, " {} ", into_title("colorblind palette")),
             Options::FPS(_) => write!(f, " {} ", into_title("fps")),
+            Options::ReducedMotion(_) => write!(f, " {} ", into_title("reduced motion")),
+            Options::KeyBindings(_) => write!(f, " {} ", into_title("key bindings")),
+            Options::ServerUrl(_) => write!(f, " {} ", into_title("server url")),
+            Options::UdpAddress(_) => write!(f, " {} ", into_title("udp address")),
         }
     }
 }
@@ -39,6 +51,7 @@ pub struct Settings {
     options: Vec<Options>,
     selected: usize,
     config: Mutex<config::Config>,
+    error: Option<&'static str>, // why the edited address is not saved
 }
 
 impl Settings {
@@ -48,6 +61,7 @@ impl Settings {
             options,
             selected: 0,
             config: Mutex::new(config),
+            error: None,
         })
     }
 
@@ -57,10 +71,16 @@ impl Settings {
             Options::PlayerColor(Widget::Slider(Slider::from(
                 settings.player_color.to_string(),
             ))),
-            Options::OtherPlayersColor(Widget::Slider(Slider::from(
-                settings.other_players_color.to_string(),
+            Options::ColorblindPalette(Widget::Toggle(Toggle::from(
+                settings.color_palette == ColorPalette::Colorblind,
             ))),
             Options::FPS(Widget::Input(Input::from(settings.fps.to_string()))),
+            Options::ReducedMotion(Widget::Toggle(Toggle::from(settings.reduced_motion))),
+            Options::KeyBindings(Widget::KeyBindings(Box::new(KeyBindingEditor::from(
+                settings.key_bindings.clone(),
+            )))),
+            Options::ServerUrl(Widget::Input(Input::from(settings.api_url.clone()))),
+            Options::UdpAddress(Widget::Input(Input::from(settings.socket_addr.clone()))),
         ]
     }
 
@@ -68,8 +88,12 @@ impl Settings {
         match &self.options[self.selected] {
             Options::PlayerName(widget) => widget,
             Options::PlayerColor(widget) => widget,
-            Options::OtherPlayersColor(widget) => widget,
+            Options::ColorblindPalette(widget) => widget,
             Options::FPS(widget) => widget,
+            Options::ReducedMotion(widget) => widget,
+            Options::KeyBindings(widget) => widget,
+            Options::ServerUrl(widget) => widget,
+            Options::UdpAddress(widget) => widget,
         }
     }
 
@@ -79,8 +103,12 @@ impl Settings {
             .map(|option| match option {
                 Options::PlayerName(widget) => widget,
                 Options::PlayerColor(widget) => widget,
-                Options::OtherPlayersColor(widget) => widget,
+                Options::ColorblindPalette(widget) => widget,
                 Options::FPS(widget) => widget,
+                Options::ReducedMotion(widget) => widget,
+                Options::KeyBindings(widget) => widget,
+                Options::ServerUrl(widget) => widget,
+                Options::UdpAddress(widget) => widget,
             })
             .collect()
     }
@@ -89,8 +117,21 @@ impl Settings {
         match &mut self.options[self.selected] {
             Options::PlayerName(widget) => widget,
             Options::PlayerColor(widget) => widget,
-            Options::OtherPlayersColor(widget) => widget,
+            Options::ColorblindPalette(widget) => widget,
             Options::FPS(widget) => widget,
+            Options::ReducedMotion(widget) => widget,
+            Options::KeyBindings(widget) => widget,
+            Options::ServerUrl(widget) => widget,
+            Options::UdpAddress(widget) => widget,
+        }
+    }
+
+    /// Checks the edited server address, the others are always valid
+    fn validate_active(&self) -> Result<(), &'static str> {
+        match &self.options[self.selected] {
+            Options::ServerUrl(widget) => config::validate_api_url(&get_widget_text(widget)),
+            Options::UdpAddress(widget) => config::validate_socket_addr(&get_widget_text(widget)),
+            _ => Ok(()),
         }
     }
 
@@ -124,14 +165,34 @@ impl Update for Settings {
         let active_widget = self.get_widget_active_as_mut();
 
         if let Some(key_code) = key_code {
+            // a key binding being captured takes any key, including the navigation ones
+            if let Widget::KeyBindings(ref mut editor) = active_widget {
+                if editor.capturing {
+                    editor.handle_key_event(key_code);
+                    if let Ok(mut settings) = self.config.lock() {
+                        settings.save_option(&self.options[self.selected]);
+                    } else {
+                        error!("Failed to lock settings");
+                    }
+                    return Ok(None);
+                }
+            }
+
             match key_code {
-                KeyCode::Up => self.previous(),
-                KeyCode::Down => self.next(),
+                KeyCode::Up => {
+                    self.previous();
+                    self.error = self.validate_active().err();
+                }
+                KeyCode::Down => {
+                    self.next();
+                    self.error = self.validate_active().err();
+                }
                 KeyCode::Left
                 | KeyCode::Right
                 | KeyCode::Char(_)
                 | KeyCode::Backspace
-                | KeyCode::Tab => {
+                | KeyCode::Tab
+                | KeyCode::Enter => {
                     match active_widget {
                         Widget::Slider(ref mut slider) => {
                             slider.handle_key_event(key_code);
@@ -139,6 +200,17 @@ impl Update for Settings {
                         Widget::Input(ref mut input) => {
                             input.handle_key_event(key_code);
                         }
+                        Widget::Toggle(ref mut toggle) => {
+                            toggle.handle_key_event(key_code);
+                        }
+                        Widget::KeyBindings(ref mut editor) => {
+                            editor.handle_key_event(key_code);
+                        }
+                    }
+                    // an invalid address is kept in the input but not saved
+                    self.error = self.validate_active().err();
+                    if self.error.is_some() {
+                        return Ok(None);
                     }
                     if let Ok(mut settings) = self.config.lock() {
                         // save selected option to settings
@@ -153,7 +225,7 @@ impl Update for Settings {
                         let _ = settings.save_config();
                         info!("Config saved");
                         info!("Moving from Settings to Menu");
-                        return Ok(Some(Box::new(Menu::new(2, settings.clone())?)));
+                        return Ok(Some(Box::new(Menu::new(3, settings.clone())?)));
                     }
                 }
                 KeyCode::End => {
@@ -161,6 +233,7 @@ impl Update for Settings {
                         // load default settings
                         *settings = config::Config::default();
                         self.options = Self::fill_settings(config::Config::default());
+                        self.error = None;
                     }
                 }
                 _ => {}
@@ -207,5 +280,24 @@ impl Render for Settings {
                 inner_rect,
             );
         }
+
+        if let Some(error) = self.error {
+            let error_area = Rect::new(
+                inner_rect.x,
+                inner_rect.bottom().saturating_sub(1),
+                inner_rect.width,
+                1.min(inner_rect.height),
+            );
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "{}: {}",
+                    self.options[self.selected].to_string().trim(),
+                    error
+                ))
+                .light_red()
+                .centered(),
+                error_area,
+            );
+        }
     }
 }
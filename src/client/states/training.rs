@@ -1,43 +1,179 @@
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::client::config;
 use crate::client::error::ClientError;
 use crate::client::states::game_end::GameEnd;
-use crate::common::models::{Direction, GameDto, GameState};
+use crate::common::models::{ticks_elapsed, Direction, GameConfig, GameDto, GameState};
 use crate::common::{Game, Player, PlayerPosition};
 
 use super::menu::Menu;
 use super::traits::{HasConfig, Render, State, Update};
+use super::utils::path_overlay::render_path_overlay;
 use super::utils::render::render_game;
 
 use axum::async_trait;
 use crossterm::event::KeyCode;
 use log::{error, info};
 use rand::seq::SliceRandom;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
+use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+pub const MIN_BOTS: u8 = 1;
+pub const MAX_BOTS: u8 = 3;
+const TICK_INTERVAL: Duration = Duration::from_millis(1000 / 60);
+const SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 1.5, 2.0];
+const NORMAL_SPEED: usize = 2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BotDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl BotDifficulty {
+    pub const ALL: [BotDifficulty; 3] = [
+        BotDifficulty::Easy,
+        BotDifficulty::Normal,
+        BotDifficulty::Hard,
+    ];
+
+    // bots move a fraction of their paddle delta per tick, so a bigger delta makes them faster
+    fn paddle_delta(self) -> f32 {
+        match self {
+            BotDifficulty::Easy => 0.2,
+            BotDifficulty::Normal => 0.3,
+            BotDifficulty::Hard => 0.45,
+        }
+    }
+}
+
+impl std::fmt::Display for BotDifficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let difficulty = match self {
+            BotDifficulty::Easy => "easy",
+            BotDifficulty::Normal => "normal",
+            BotDifficulty::Hard => "hard",
+        };
+        write!(f, "{}", difficulty)
+    }
+}
+
+/// Last training setup picked by the player
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrainingConfig {
+    pub bots: u8,
+    pub difficulty: BotDifficulty,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self {
+            bots: MAX_BOTS,
+            difficulty: BotDifficulty::default(),
+        }
+    }
+}
+
+/// How fast the training physics run, paused it only advances one tick per step
+#[derive(Debug)]
+pub struct TrainingClock {
+    speed: usize, // index into `SPEEDS`
+    paused: bool,
+    pending_steps: u32,
+}
+
+impl Default for TrainingClock {
+    fn default() -> Self {
+        Self {
+            speed: NORMAL_SPEED,
+            paused: false,
+            pending_steps: 0,
+        }
+    }
+}
+
+impl TrainingClock {
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.pending_steps = 0;
+    }
+
+    /// Queues a single tick, only while paused
+    pub fn step(&mut self) {
+        if self.paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    /// Game time to simulate for `elapsed` ticks of real time, `None` when the game stands still
+    pub fn advance(&mut self, elapsed: f32) -> Option<f32> {
+        if !self.paused {
+            return Some(elapsed * self.speed());
+        }
+        if self.pending_steps > 0 {
+            self.pending_steps -= 1;
+            return Some(1.0);
+        }
+        None
+    }
+
+    /// Shown in the corner, nothing at the normal speed
+    fn status(&self) -> Option<String> {
+        if self.paused {
+            Some(" PAUSED - <N> step | <P> resume ".to_string())
+        } else if self.speed != NORMAL_SPEED {
+            Some(format!(" {}x ", self.speed()))
+        } else {
+            None
+        }
+    }
+}
+
 pub struct Training {
     config: config::Config,
     game: Arc<Mutex<Game>>,
+    clock: Arc<Mutex<TrainingClock>>,
     our_player_id: Uuid,
+    show_path: bool, // the balls' straight paths and where the bots expect them
     cancellation_token: CancellationToken,
     _game_tick_handle: tokio::task::JoinHandle<()>,
 }
 
 impl Training {
-    pub fn new(config: config::Config) -> Result<Self, ClientError> {
+    pub fn new(
+        config: config::Config,
+        bots: u8,
+        difficulty: BotDifficulty,
+    ) -> Result<Self, ClientError> {
         let mut game = Game::new();
         let mut our_player = Player::new(config.player_name.clone(), false);
         our_player.is_ready = true;
         let our_player_id = our_player.id;
-        let mut players = [
-            our_player,
-            Player::new("bot".to_string(), true),
-            Player::new("bot".to_string(), true),
-            Player::new("bot".to_string(), true),
-        ];
+        let mut players = vec![our_player];
+        for _ in 0..bots.clamp(MIN_BOTS, MAX_BOTS) {
+            players.push(Player::new("bot".to_string(), true));
+        }
         let mut rng = rand::rng();
         players.shuffle(&mut rng);
 
@@ -45,30 +181,36 @@ impl Training {
             if let Some(position) = game.assign_position() {
                 player.position = Some(position);
             }
+            let player_id = player.id;
             let _ = game.add_player(player);
+            // the difficulty overrides the game's paddle speed for bots
+            if let Some(bot) = game.get_player_mut(&player_id).filter(|p| p.is_ai) {
+                bot.paddle_delta = difficulty.paddle_delta();
+            }
         }
 
         let game = Arc::new(Mutex::new(game));
+        let clock = Arc::new(Mutex::new(TrainingClock::default()));
         let cancellation_token = CancellationToken::new();
 
         let game_clone = game.clone();
+        let clock_clone = clock.clone();
         let cancellation_token_clone = cancellation_token.clone();
         let game_tick_handle = tokio::spawn(async move {
             let _ = game_clone.lock().expect("Failed to lock game").start_game();
+            let mut last_tick = Instant::now();
             loop {
                 tokio::select! {
                      _ = cancellation_token_clone.cancelled() => break,
-                    _ = tokio::time::sleep(tokio::time::Duration::from_millis(1000 / 60)) => {
+                    _ = tokio::time::sleep(TICK_INTERVAL) => {
+                        let now = Instant::now();
+                        let elapsed = ticks_elapsed(now.duration_since(last_tick));
+                        last_tick = now;
+                        let Some(dt) = clock_clone.lock().ok().and_then(|mut clock| clock.advance(elapsed)) else {
+                            continue;
+                        };
                         if let Ok(mut g) = game_clone.lock() {
-                            g.game_tick();
-                            let ball = g.ball.clone();
-                            for player in g.players.values_mut() {
-                                if player.is_ai {
-                                    if let Some(ref ball) = ball {
-                                        player.ai(ball.clone());
-                                    }
-                                }
-                            }
+                            g.game_tick(&GameConfig::default(), dt);
                         }
                     }
                 }
@@ -78,7 +220,9 @@ impl Training {
         Ok(Self {
             config,
             game,
+            clock,
             our_player_id,
+            show_path: false,
             cancellation_token,
             _game_tick_handle: game_tick_handle,
         })
@@ -107,6 +251,7 @@ impl Update for Training {
                     GameDto::from(game.clone()),
                     self.our_player_id,
                     self.config.clone(),
+                    None,
                 )?)));
             }
         } else {
@@ -119,8 +264,23 @@ impl Update for Training {
                     log::info!("Moving from Training to Menu");
                     return Ok(Some(Box::new(Menu::new(1, self.config.clone())?)));
                 }
+                KeyCode::Char('v') => self.show_path = !self.show_path,
+                KeyCode::Char('p')
+                | KeyCode::Char('n')
+                | KeyCode::Char('+')
+                | KeyCode::Char('-') => {
+                    if let Ok(mut clock) = self.clock.lock() {
+                        match key_code {
+                            KeyCode::Char('p') => clock.toggle_pause(),
+                            KeyCode::Char('n') => clock.step(),
+                            KeyCode::Char('+') => clock.faster(),
+                            _ => clock.slower(),
+                        }
+                    }
+                }
                 _ => {
                     if let Ok(mut game) = self.game.lock() {
+                        let board = game.board();
                         if let Some(player) = game
                             .players
                             .values_mut()
@@ -131,10 +291,10 @@ impl Update for Training {
                                     PlayerPosition::Top | PlayerPosition::Bottom => {
                                         match key_code {
                                             KeyCode::Left => {
-                                                player.move_paddle(Direction::Negative);
+                                                player.move_paddle(Direction::Negative, board);
                                             }
                                             KeyCode::Right => {
-                                                player.move_paddle(Direction::Positive);
+                                                player.move_paddle(Direction::Positive, board);
                                             }
                                             _ => {}
                                         }
@@ -142,10 +302,10 @@ impl Update for Training {
                                     PlayerPosition::Left | PlayerPosition::Right => {
                                         match key_code {
                                             KeyCode::Up => {
-                                                player.move_paddle(Direction::Negative);
+                                                player.move_paddle(Direction::Negative, board);
                                             }
                                             KeyCode::Down => {
-                                                player.move_paddle(Direction::Positive);
+                                                player.move_paddle(Direction::Positive, board);
                                             }
                                             _ => {}
                                         }
@@ -167,13 +327,29 @@ impl Render for Training {
             render_game(
                 &GameDto::from(game.clone()),
                 self.our_player_id,
-                self.config.player_color,
-                self.config.other_players_color,
+                |position| self.config.position_color(position),
+                None,
                 frame,
             );
+            if self.show_path {
+                render_path_overlay(frame, &game);
+            }
         } else {
             error!("Failed to lock game");
         }
+
+        if let Some(status) = self.clock.lock().ok().and_then(|clock| clock.status()) {
+            let area = frame.area();
+            frame.render_widget(
+                Paragraph::new(status).yellow(),
+                Rect::new(
+                    area.x,
+                    area.bottom().saturating_sub(1),
+                    area.width,
+                    1.min(area.height),
+                ),
+            );
+        }
     }
 }
 
@@ -182,3 +358,75 @@ impl Drop for Training {
         self.cancellation_token.cancel();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::models::Vec2;
+
+    #[tokio::test]
+    async fn test_training_with_one_bot() {
+        let training = Training::new(config::Config::default(), 1, BotDifficulty::Easy).unwrap();
+
+        let game = training.game.lock().unwrap();
+        assert_eq!(game.players.len(), 2);
+
+        let us = game.get_player(&training.our_player_id).unwrap();
+        assert!(!us.is_ai);
+        assert!(us.is_ready);
+        assert!(us.position.is_some());
+
+        let bot = game.players.values().find(|p| p.is_ai).unwrap();
+        assert_eq!(bot.paddle_delta, BotDifficulty::Easy.paddle_delta());
+        assert!(bot.position.is_some());
+    }
+
+    fn moving_ball_game() -> Game {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        game.balls[0].velocity = Vec2 { x: 0.1, y: 0.0 };
+        game
+    }
+
+    #[test]
+    fn test_clock_speed_scales_game_advance() {
+        let mut clock = TrainingClock::default();
+        clock.slower();
+        clock.slower();
+        assert_eq!(clock.speed(), 0.25);
+
+        let mut game = moving_ball_game();
+        let start = game.balls[0].position.x;
+        for _ in 0..4 {
+            let dt = clock.advance(1.0).unwrap();
+            game.game_tick(&GameConfig::default(), dt);
+        }
+        // four real ticks at a quarter of the speed are a single tick of movement
+        assert!((game.balls[0].position.x - (start + 0.1)).abs() < 1e-5);
+
+        for _ in 0..10 {
+            clock.faster();
+        }
+        assert_eq!(clock.speed(), 2.0);
+        assert_eq!(clock.advance(1.0), Some(2.0));
+    }
+
+    #[test]
+    fn test_clock_single_step() {
+        let mut clock = TrainingClock::default();
+        clock.step(); // running, nothing to step
+        clock.toggle_pause();
+        assert_eq!(clock.advance(1.0), None);
+
+        let mut game = moving_ball_game();
+        let start = game.balls[0].position.x;
+        clock.step();
+        while let Some(dt) = clock.advance(1.0) {
+            game.game_tick(&GameConfig::default(), dt);
+        }
+        assert!((game.balls[0].position.x - (start + 0.1)).abs() < 1e-5);
+
+        clock.toggle_pause();
+        assert_eq!(clock.advance(1.0), Some(1.0));
+    }
+}
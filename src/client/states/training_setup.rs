@@ -0,0 +1,163 @@
+use super::menu::Menu;
+use super::training::{BotDifficulty, Training, MAX_BOTS, MIN_BOTS};
+use super::traits::{HasConfig, Render, State, Update};
+use super::utils::render::{
+    into_title, render_inner_rectangle, render_list, render_outer_rectangle,
+};
+use crate::client::config;
+use crate::client::error::ClientError;
+
+use axum::async_trait;
+use crossterm::event::KeyCode;
+use log::info;
+use ratatui::style::Stylize;
+use ratatui::Frame;
+
+#[derive(PartialEq)]
+pub enum Options {
+    Bots,
+    Difficulty,
+    Start,
+}
+
+pub struct TrainingSetup {
+    options: Vec<Options>,
+    selected: usize,
+    config: config::Config,
+}
+
+impl TrainingSetup {
+    pub fn new(config: config::Config) -> Result<Self, ClientError> {
+        Ok(Self {
+            options: vec![Options::Bots, Options::Difficulty, Options::Start],
+            selected: 0,
+            config,
+        })
+    }
+
+    fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.options.len();
+    }
+
+    fn previous(&mut self) {
+        if self.selected == 0 {
+            self.selected = self.options.len() - 1;
+        } else {
+            self.selected -= 1;
+        }
+    }
+
+    fn change_bots(&mut self, delta: i8) {
+        let training = &mut self.config.training;
+        training.bots = training
+            .bots
+            .saturating_add_signed(delta)
+            .clamp(MIN_BOTS, MAX_BOTS);
+    }
+
+    fn change_difficulty(&mut self, delta: isize) {
+        let training = &mut self.config.training;
+        let all = BotDifficulty::ALL;
+        let current = all
+            .iter()
+            .position(|d| *d == training.difficulty)
+            .unwrap_or(0) as isize;
+        training.difficulty = all[(current + delta).rem_euclid(all.len() as isize) as usize];
+    }
+
+    fn option_text(&self, option: &Options) -> String {
+        match option {
+            Options::Bots => format!(
+                " {}: \u{2190} {} \u{2192} ",
+                into_title("bots"),
+                self.config.training.bots
+            ),
+            Options::Difficulty => format!(
+                " {}: \u{2190} {} \u{2192} ",
+                into_title("difficulty"),
+                into_title(&self.config.training.difficulty.to_string())
+            ),
+            Options::Start => format!(" {} ", into_title("start")),
+        }
+    }
+}
+
+impl State for TrainingSetup {}
+
+impl HasConfig for TrainingSetup {
+    fn config(&self) -> config::Config {
+        self.config.clone()
+    }
+}
+
+#[async_trait]
+impl Update for TrainingSetup {
+    async fn update(
+        &mut self,
+        key_code: Option<KeyCode>,
+    ) -> Result<Option<Box<dyn State>>, ClientError> {
+        if let Some(key_code) = key_code {
+            match key_code {
+                KeyCode::Up => self.previous(),
+                KeyCode::Down => self.next(),
+                KeyCode::Left | KeyCode::Right => {
+                    let delta = if key_code == KeyCode::Left { -1 } else { 1 };
+                    match self.options[self.selected] {
+                        Options::Bots => self.change_bots(delta),
+                        Options::Difficulty => self.change_difficulty(delta as isize),
+                        Options::Start => {}
+                    }
+                }
+                KeyCode::Enter if self.options[self.selected] == Options::Start => {
+                    // remember the setup for the next training
+                    let _ = self.config.save_config();
+                    info!("Moving from TrainingSetup to Training");
+                    let training = self.config.training;
+                    return Ok(Some(Box::new(Training::new(
+                        self.config.clone(),
+                        training.bots,
+                        training.difficulty,
+                    )?)));
+                }
+                KeyCode::Esc => {
+                    info!("Moving from TrainingSetup to Menu");
+                    return Ok(Some(Box::new(Menu::new(1, self.config.clone())?)));
+                }
+                _ => {}
+            };
+        }
+        Ok(None)
+    }
+}
+
+impl Render for TrainingSetup {
+    fn render(&self, frame: &mut Frame) {
+        let outer_rect = render_outer_rectangle(
+            frame,
+            " quadropong - Training ",
+            vec![
+                " Back".into(),
+                " <Esc> ".light_blue().bold(),
+                "| Up".into(),
+                " <\u{2191}> ".light_blue(),
+                "| Down".into(),
+                " <\u{2193}> ".light_blue(),
+                "| Change".into(),
+                " <\u{2190}\u{2192}> ".light_blue(),
+            ],
+        );
+
+        let inner_rect = render_inner_rectangle(frame, outer_rect);
+
+        render_list(
+            frame,
+            &self
+                .options
+                .iter()
+                .map(|option| self.option_text(option))
+                .collect::<Vec<String>>(),
+            self.selected,
+            inner_rect,
+        );
+    }
+}
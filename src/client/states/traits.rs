@@ -8,6 +8,9 @@ use crate::client::{config, error::ClientError};
 
 pub trait Render {
     fn render(&self, frame: &mut Frame);
+
+    /// Frame rate measured by the render loop, only states showing it need to keep it
+    fn set_measured_fps(&mut self, _fps: f64) {}
 }
 
 #[async_trait]
@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant};
+
+use crate::common::models::{Direction, MAX_PADDLE_BOOST};
+
+/// Longest gap between key events still counted as holding the key, covers the key repeat delay
+const HOLD_WINDOW: Duration = Duration::from_millis(600);
+/// Time of holding a key until the paddle reaches its top speed
+const RAMP_UP: Duration = Duration::from_millis(800);
+
+/// Paddle speed multiplier after holding a movement key for `held`
+pub fn acceleration_curve(held: Duration) -> f32 {
+    let progress = (held.as_secs_f32() / RAMP_UP.as_secs_f32()).min(1.0);
+    1.0 + progress * (MAX_PADDLE_BOOST - 1.0)
+}
+
+/// Tracks how long a movement key has been held from its repeated key events
+#[derive(Default)]
+pub struct KeyHold {
+    direction: Option<Direction>,
+    pressed_at: Option<Instant>,
+    last_event: Option<Instant>,
+}
+
+impl KeyHold {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a key event and returns the speed multiplier for the move it triggers
+    pub fn press(&mut self, direction: &Direction, now: Instant) -> f32 {
+        let held = self.direction.as_ref() == Some(direction)
+            && self
+                .last_event
+                .is_some_and(|last| now.duration_since(last) <= HOLD_WINDOW);
+        if !held {
+            self.direction = Some(direction.clone());
+            self.pressed_at = Some(now);
+        }
+        self.last_event = Some(now);
+
+        acceleration_curve(
+            self.pressed_at
+                .map_or(Duration::ZERO, |pressed_at| now.duration_since(pressed_at)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acceleration_curve() {
+        assert_eq!(acceleration_curve(Duration::ZERO), 1.0);
+        assert_eq!(
+            acceleration_curve(RAMP_UP / 2),
+            1.0 + (MAX_PADDLE_BOOST - 1.0) / 2.0
+        );
+        assert_eq!(acceleration_curve(RAMP_UP), MAX_PADDLE_BOOST);
+        // capped
+        assert_eq!(acceleration_curve(RAMP_UP * 10), MAX_PADDLE_BOOST);
+    }
+
+    #[test]
+    fn test_key_hold() {
+        let start = Instant::now();
+        let mut hold = KeyHold::new();
+
+        assert_eq!(hold.press(&Direction::Positive, start), 1.0);
+        let held = hold.press(&Direction::Positive, start + RAMP_UP / 2);
+        assert!(held > 1.0);
+
+        // turning around starts over
+        let turned = start + RAMP_UP / 2 + Duration::from_millis(30);
+        assert_eq!(hold.press(&Direction::Negative, turned), 1.0);
+
+        // so does letting go of the key
+        let later = turned + HOLD_WINDOW * 2;
+        assert_eq!(hold.press(&Direction::Negative, later), 1.0);
+    }
+}
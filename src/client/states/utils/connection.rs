@@ -0,0 +1,124 @@
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// Silence after which the connection is shown as dropping, longer than the server's
+/// resend interval of unchanged games
+pub const RECONNECTING_AFTER: Duration = Duration::from_millis(1500);
+/// Silence after which the server is given up on
+pub const DISCONNECTED_AFTER: Duration = Duration::from_secs(6);
+/// How often the receive task looks at the silence while no packets come
+pub const CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting, // packets stopped coming, they may well resume
+    Disconnected,
+}
+
+impl ConnectionState {
+    pub fn after_silence(silence: Duration) -> Self {
+        if silence >= DISCONNECTED_AFTER {
+            ConnectionState::Disconnected
+        } else if silence >= RECONNECTING_AFTER {
+            ConnectionState::Reconnecting
+        } else {
+            ConnectionState::Connected
+        }
+    }
+}
+
+/// State of the connection to the server, kept up to date by the receive task
+pub struct ConnectionWatch {
+    last_received: Instant,
+    state: ConnectionState,
+}
+
+impl ConnectionWatch {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_received: now,
+            state: ConnectionState::Connected,
+        }
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// A packet arrived, whatever the state was the connection is back
+    pub fn record(&mut self, now: Instant) {
+        self.last_received = now;
+        self.transition(ConnectionState::Connected);
+    }
+
+    /// Moves on to the state the silence since the last packet calls for
+    pub fn update(&mut self, now: Instant) -> ConnectionState {
+        self.transition(ConnectionState::after_silence(
+            now.duration_since(self.last_received),
+        ));
+        self.state
+    }
+
+    fn transition(&mut self, state: ConnectionState) {
+        if state == self.state {
+            return;
+        }
+        match state {
+            ConnectionState::Connected => info!("Connection to the server recovered"),
+            ConnectionState::Reconnecting => warn!("No updates from the server, reconnecting"),
+            ConnectionState::Disconnected => warn!("Lost the connection to the server"),
+        }
+        self.state = state;
+    }
+}
+
+/// Line over the top of `area` while packets are missing, the game stays visible under it
+pub fn render_reconnecting(frame: &mut Frame, area: Rect) {
+    frame.render_widget(
+        Paragraph::new(" RECONNECTING... ")
+            .black()
+            .on_yellow()
+            .bold()
+            .centered(),
+        Rect::new(area.x, area.y, area.width, 1.min(area.height)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_states() {
+        let start = Instant::now();
+        let mut watch = ConnectionWatch::new(start);
+
+        assert_eq!(watch.update(start), ConnectionState::Connected);
+        assert_eq!(
+            watch.update(start + RECONNECTING_AFTER - Duration::from_millis(1)),
+            ConnectionState::Connected
+        );
+        assert_eq!(
+            watch.update(start + RECONNECTING_AFTER),
+            ConnectionState::Reconnecting
+        );
+
+        // a packet clears it right away
+        watch.record(start + Duration::from_secs(2));
+        assert_eq!(watch.state(), ConnectionState::Connected);
+
+        assert_eq!(
+            watch.update(start + Duration::from_secs(2) + DISCONNECTED_AFTER),
+            ConnectionState::Disconnected
+        );
+        // even a server given up on may come back
+        watch.record(start + Duration::from_secs(20));
+        assert_eq!(watch.state(), ConnectionState::Connected);
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Timing of received game updates, filled in by the receive task
+pub struct ReceiveStats {
+    pub last_received: Instant,
+    window_start: Instant,
+    window_packets: u32,
+    pub packets_per_sec: f64,
+}
+
+impl ReceiveStats {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_received: now,
+            window_start: now,
+            window_packets: 0,
+            packets_per_sec: 0.0,
+        }
+    }
+
+    pub fn record(&mut self, now: Instant) {
+        self.last_received = now;
+        self.window_packets += 1;
+
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= RATE_WINDOW {
+            self.packets_per_sec = self.window_packets as f64 / elapsed.as_secs_f64();
+            self.window_start = now;
+            self.window_packets = 0;
+        }
+    }
+}
+
+/// Counts rendered frames and reports the frame rate once per second
+pub struct FpsCounter {
+    window_start: Instant,
+    frames: u32,
+    fps: f64,
+}
+
+impl FpsCounter {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            frames: 0,
+            fps: 0.0,
+        }
+    }
+
+    pub fn frame(&mut self, now: Instant) -> f64 {
+        self.frames += 1;
+
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= RATE_WINDOW {
+            self.fps = self.frames as f64 / elapsed.as_secs_f64();
+            self.window_start = now;
+            self.frames = 0;
+        }
+        self.fps
+    }
+}
+
+pub fn format_stats(fps: f64, since_last_packet: Duration, packets_per_sec: f64) -> String {
+    format!(
+        " FPS {:.0} | last packet {} ms ago | {:.0} packets/s ",
+        fps,
+        since_last_packet.as_millis(),
+        packets_per_sec
+    )
+}
+
+pub fn render_debug_overlay(frame: &mut Frame, stats: String) {
+    let area = frame.area();
+    let width = (stats.chars().count() as u16).min(area.width);
+    frame.render_widget(
+        Paragraph::new(stats).black().on_yellow(),
+        // bottom row, the top one holds the scoreboard
+        Rect::new(
+            area.x,
+            area.bottom().saturating_sub(1),
+            width,
+            1.min(area.height),
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_stats() {
+        assert_eq!(
+            format_stats(59.6, Duration::from_millis(17), 60.2),
+            " FPS 60 | last packet 17 ms ago | 60 packets/s "
+        );
+    }
+
+    #[test]
+    fn test_receive_stats() {
+        let start = Instant::now();
+        let mut stats = ReceiveStats::new(start);
+
+        for i in 1..=30 {
+            stats.record(start + Duration::from_millis(i * 50));
+        }
+
+        // 20 packets arrived within the first second
+        assert_eq!(stats.packets_per_sec, 20.0);
+        assert_eq!(stats.last_received, start + Duration::from_millis(1500));
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Stylize};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+
+use super::render::calculate_game_area;
+use crate::common::models::BoardSize;
+
+/// Whether a goal scored at `last_goal_at` is still recent enough to flash at `now`,
+/// the board flashes for as long as the game pauses after a goal
+pub fn show_goal_flash(
+    last_goal_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    goal_timeout_ms: u64,
+) -> bool {
+    last_goal_at.is_some_and(|goal_at| {
+        let elapsed = now.signed_duration_since(goal_at).num_milliseconds();
+        (0..goal_timeout_ms as i64).contains(&elapsed)
+    })
+}
+
+/// Lights up the border of the board and shows a banner in its middle
+pub fn render_goal_flash(frame: &mut Frame, board: BoardSize) {
+    // the same areas `render_game` draws the board in
+    let [_, board_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(frame.area());
+    let (bounding_box, _, _, _) = calculate_game_area(board_area, board);
+
+    frame.render_widget(Block::bordered().fg(Color::Yellow).bold(), bounding_box);
+
+    let banner = " GOAL! ";
+    let width = (banner.len() as u16).min(bounding_box.width);
+    let banner_area = Rect::new(
+        bounding_box.x + (bounding_box.width - width) / 2,
+        bounding_box.y + bounding_box.height / 2,
+        width,
+        1.min(bounding_box.height),
+    );
+    frame.render_widget(
+        Paragraph::new(banner).black().on_yellow().bold(),
+        banner_area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_show_goal_flash() {
+        let goal_at = Utc::now();
+        let timeout = 2000;
+
+        assert!(show_goal_flash(Some(goal_at), goal_at, timeout));
+        assert!(show_goal_flash(
+            Some(goal_at),
+            goal_at + Duration::milliseconds(timeout as i64 - 1),
+            timeout
+        ));
+        assert!(!show_goal_flash(
+            Some(goal_at),
+            goal_at + Duration::milliseconds(timeout as i64),
+            timeout
+        ));
+        // a goal from the future is a clock glitch, not something to flash for
+        assert!(!show_goal_flash(
+            Some(goal_at),
+            goal_at - Duration::milliseconds(1),
+            timeout
+        ));
+        assert!(!show_goal_flash(None, goal_at, timeout));
+        // no pause after goals, nothing to flash through
+        assert!(!show_goal_flash(Some(goal_at), goal_at, 0));
+    }
+}
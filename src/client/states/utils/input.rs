@@ -1,3 +1,6 @@
+use ratatui::text::Span;
+
+/// Single line text input, `char_index` counts characters, not bytes, so any UTF-8 text works
 #[derive(Clone)]
 pub struct Input {
     pub input: String,
@@ -20,8 +23,8 @@ impl Input {
 
     pub fn from(string: String) -> Self {
         Self {
-            input: string.clone(),
-            char_index: string.len(),
+            char_index: string.chars().count(),
+            input: string,
         }
     }
 
@@ -33,38 +36,56 @@ impl Input {
         s.retain(|c| !c.is_whitespace());
     }
 
+    fn len(&self) -> usize {
+        self.input.chars().count()
+    }
+
+    /// Byte offset of the cursor in `input`
+    fn byte_index(&self) -> usize {
+        self.input
+            .char_indices()
+            .nth(self.char_index)
+            .map_or(self.input.len(), |(index, _)| index)
+    }
+
+    /// Terminal column of the cursor, wide characters take two
+    pub fn cursor_column(&self) -> u16 {
+        Span::raw(&self.input[..self.byte_index()]).width() as u16
+    }
+
     pub fn move_left(&mut self) {
         let cursor_left = self.char_index.saturating_sub(1);
         self.char_index = cursor_left;
     }
 
     pub fn move_right(&mut self) {
-        if self.char_index < self.input.len() {
+        if self.char_index < self.len() {
             self.char_index = self.char_index.saturating_add(1);
         }
     }
 
     fn move_right_multiple(&mut self, num: usize) {
-        if self.char_index < self.input.len() {
-            self.char_index = self.char_index.saturating_add(num);
-        }
+        self.char_index = self.char_index.saturating_add(num).min(self.len());
     }
 
     pub fn insert_char(&mut self, new_char: char) {
-        self.input.insert(self.char_index, new_char);
+        let index = self.byte_index();
+        self.input.insert(index, new_char);
         self.move_right();
     }
 
     pub fn insert_clipboard(&mut self, mut new_string: String) {
         Self::remove_whitespace(&mut new_string);
-        self.input.insert_str(self.char_index, &new_string);
+        let index = self.byte_index();
+        self.input.insert_str(index, &new_string);
         self.move_right_multiple(new_string.chars().count());
     }
 
     pub fn delete_char(&mut self) {
         if self.char_index != 0 {
             self.char_index -= 1;
-            self.input.remove(self.char_index);
+            let index = self.byte_index();
+            self.input.remove(index);
         }
     }
 }
@@ -166,6 +187,49 @@ mod tests {
         assert_eq!(input.char_index, 0);
     }
 
+    #[test]
+    fn test_multi_byte_characters() {
+        let mut input = Input::from(String::from("Žofia"));
+        assert_eq!(input.char_index, 5);
+
+        input.move_left();
+        input.move_left();
+        input.insert_char('é');
+        assert_eq!(input.input, "Žoféia");
+        assert_eq!(input.char_index, 4);
+
+        input.insert_clipboard(String::from("🏓 ü"));
+        assert_eq!(input.input, "Žofé🏓üia");
+        assert_eq!(input.char_index, 6);
+
+        input.delete_char();
+        input.delete_char();
+        assert_eq!(input.input, "Žoféia");
+        assert_eq!(input.char_index, 4);
+
+        input.char_index = 1;
+        input.delete_char();
+        assert_eq!(input.input, "oféia");
+        assert_eq!(input.char_index, 0);
+        input.delete_char();
+        assert_eq!(input.input, "oféia");
+
+        input.char_index = 5;
+        input.move_right(); // at the end of 5 characters, not of 6 bytes
+        assert_eq!(input.char_index, 5);
+    }
+
+    #[test]
+    fn test_cursor_column() {
+        let mut input = Input::from(String::from("ñ🏓a"));
+        // the emoji is two columns wide
+        assert_eq!(input.cursor_column(), 4);
+        input.move_left();
+        assert_eq!(input.cursor_column(), 3);
+        input.move_left();
+        assert_eq!(input.cursor_column(), 1);
+    }
+
     #[test]
     fn test_remove_whitespace() {
         let mut input = Input::from(String::from("hello world"));
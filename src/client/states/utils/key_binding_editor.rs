@@ -0,0 +1,84 @@
+use crossterm::event::KeyCode;
+
+use crate::client::key_bindings::{Action, KeyBindings};
+
+#[derive(Clone)]
+pub struct KeyBindingEditor {
+    pub bindings: KeyBindings,
+    pub selected: usize,
+    pub capturing: bool, // the next key pressed gets bound to the selected action
+}
+
+impl KeyBindingEditor {
+    pub fn from(bindings: KeyBindings) -> Self {
+        Self {
+            bindings,
+            selected: 0,
+            capturing: false,
+        }
+    }
+
+    pub fn action(&self) -> Action {
+        Action::ALL[self.selected]
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % Action::ALL.len();
+    }
+
+    pub fn previous(&mut self) {
+        if self.selected == 0 {
+            self.selected = Action::ALL.len() - 1;
+        } else {
+            self.selected -= 1;
+        }
+    }
+
+    pub fn start_capture(&mut self) {
+        self.capturing = true;
+    }
+
+    pub fn capture(&mut self, key: KeyCode) {
+        self.bindings.bind(self.action(), key);
+        self.capturing = false;
+    }
+
+    pub fn get_text(self) -> String {
+        let action = self.action();
+        if self.capturing {
+            format!("{}: press a key", action)
+        } else {
+            let keys = self
+                .bindings
+                .keys(action)
+                .iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>()
+                .join(" / ");
+            format!("\u{2190} {}: {} \u{2192}", action, keys)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let mut editor = KeyBindingEditor::from(KeyBindings::default());
+        editor.next();
+        assert_eq!(editor.action(), Action::MoveDown);
+
+        editor.start_capture();
+        assert!(editor.capturing);
+        editor.capture(KeyCode::Char('k'));
+        assert!(!editor.capturing);
+        assert!(editor.bindings.is(Action::MoveDown, KeyCode::Char('k')));
+        assert!(!editor.bindings.is(Action::MoveDown, KeyCode::Down));
+
+        editor.previous();
+        editor.previous();
+        assert_eq!(editor.action(), Action::Quit);
+    }
+}
@@ -1,4 +1,15 @@
+pub mod acceleration;
+pub mod connection;
+pub mod debug_overlay;
+pub mod goal_flash;
 pub mod input;
+pub mod key_binding_editor;
+pub mod path_overlay;
+pub mod prediction;
 pub mod render;
+pub mod sequence;
 pub mod slider;
+pub mod sound;
+pub mod toggle;
+pub mod trail;
 pub mod widget;
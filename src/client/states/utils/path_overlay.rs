@@ -0,0 +1,191 @@
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use super::render::calculate_game_area;
+use super::trail::to_cell;
+use crate::common::models::{BoardSize, Vec2};
+use crate::common::{Game, PlayerPosition};
+
+/// Upper bound of the points sampled along a path, a path never has more cells than that
+const MAX_PATH_SAMPLES: usize = 1024;
+
+/// Cells the ball passes going straight from `position` until it leaves the board,
+/// in order and without the cell the ball is in
+pub fn path_cells(
+    position: &Vec2,
+    velocity: &Vec2,
+    board: BoardSize,
+    game_area: Rect,
+    scale_x: f32,
+    scale_y: f32,
+) -> Vec<Position> {
+    let time_to_edge = |position: f32, velocity: f32, size: f32| {
+        if velocity > 0.0 {
+            (size - position) / velocity
+        } else if velocity < 0.0 {
+            -position / velocity
+        } else {
+            f32::INFINITY
+        }
+    };
+    let time = time_to_edge(position.x, velocity.x, board.width).min(time_to_edge(
+        position.y,
+        velocity.y,
+        board.height,
+    ));
+    if !time.is_finite() || time <= 0.0 {
+        return Vec::new();
+    }
+
+    // two samples per crossed cell so diagonal steps don't skip any
+    let cells_crossed = (velocity.x * time * scale_x)
+        .abs()
+        .max((velocity.y * time * scale_y).abs());
+    let samples = ((cells_crossed * 2.0).ceil() as usize).clamp(1, MAX_PATH_SAMPLES);
+
+    let start = to_cell(position, game_area, scale_x, scale_y);
+    let mut cells: Vec<Position> = Vec::new();
+    for step in 1..=samples {
+        let t = time * step as f32 / samples as f32;
+        let point = Vec2 {
+            x: position.x + velocity.x * t,
+            y: position.y + velocity.y * t,
+        };
+        if let Some(cell) = to_cell(&point, game_area, scale_x, scale_y) {
+            if Some(cell) != start && cells.last() != Some(&cell) {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
+
+/// Points on their sides where the bots expect the ball they follow to arrive
+pub fn predicted_intercepts(game: &Game) -> Vec<Vec2> {
+    let board = game.board();
+    let occupied: Vec<PlayerPosition> = game
+        .players
+        .values()
+        .filter_map(|player| player.position)
+        .collect();
+
+    game.players
+        .values()
+        .filter(|player| player.is_ai)
+        .filter_map(|player| {
+            let position = player.position?;
+            let ball = game.nearest_ball(position)?;
+            let along = player.calculate_ball_position(ball.clone(), 0, board, &occupied)?;
+            Some(match position {
+                PlayerPosition::Top => Vec2 { x: along, y: 0.0 },
+                PlayerPosition::Bottom => Vec2 {
+                    x: along,
+                    y: board.height,
+                },
+                PlayerPosition::Left => Vec2 { x: 0.0, y: along },
+                PlayerPosition::Right => Vec2 {
+                    x: board.width,
+                    y: along,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Dims the cells the balls are heading through and marks where the bots will meet them
+pub fn render_path_overlay(frame: &mut Frame, game: &Game) {
+    let board = game.board();
+    // the same areas `render_game` draws the board in
+    let [_, board_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(frame.area());
+    let (_, game_area, scale_x, scale_y) = calculate_game_area(board_area, board);
+    if game_area.is_empty() {
+        return;
+    }
+
+    let dim = Style::default().fg(Color::DarkGray);
+    for ball in &game.balls {
+        for cell in path_cells(
+            &ball.position,
+            &ball.velocity,
+            board,
+            game_area,
+            scale_x,
+            scale_y,
+        ) {
+            frame.render_widget(
+                Paragraph::new("·").style(dim),
+                Rect::new(cell.x, cell.y, 1, 1),
+            );
+        }
+    }
+
+    for intercept in predicted_intercepts(game) {
+        // the far sides lie on the edge of the area, keep their marks inside it
+        let cell = Position::new(
+            (game_area.x + (intercept.x * scale_x) as u16).min(game_area.right() - 1),
+            (game_area.y + (intercept.y * scale_y) as u16).min(game_area.bottom() - 1),
+        );
+        frame.render_widget(
+            Paragraph::new("×").style(dim),
+            Rect::new(cell.x, cell.y, 1, 1),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_cells() {
+        let game_area = Rect::new(2, 1, 10, 10);
+        let position = Vec2 { x: 5.5, y: 5.5 };
+
+        assert_eq!(
+            path_cells(
+                &position,
+                &Vec2 { x: 1.0, y: 0.0 },
+                BoardSize::square(10.0),
+                game_area,
+                1.0,
+                1.0
+            ),
+            vec![
+                Position::new(8, 6),
+                Position::new(9, 6),
+                Position::new(10, 6),
+                Position::new(11, 6),
+            ]
+        );
+
+        // diagonal up and to the left, one cell per step
+        assert_eq!(
+            path_cells(
+                &position,
+                &Vec2 { x: -0.3, y: -0.3 },
+                BoardSize::square(10.0),
+                game_area,
+                1.0,
+                1.0
+            ),
+            (0..5)
+                .rev()
+                .map(|i| Position::new(2 + i, 1 + i))
+                .collect::<Vec<_>>()
+        );
+
+        // a ball standing still goes nowhere
+        assert!(path_cells(
+            &position,
+            &Vec2 { x: 0.0, y: 0.0 },
+            BoardSize::square(10.0),
+            game_area,
+            1.0,
+            1.0
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::common::models::{clamp_boost, BoardSize, Direction, PlayerDto};
+
+/// How long a move is assumed to be on its way to the server and not in the received state yet
+const PENDING_TIMEOUT: Duration = Duration::from_millis(150);
+/// Predictions further off than this from the server are dropped instead of kept
+pub const SNAP_THRESHOLD: f32 = 1.0;
+
+/// Moves of our paddle shown right away, before the server confirms them
+#[derive(Default)]
+pub struct PaddlePrediction {
+    pending: VecDeque<(Instant, f32)>,
+    /// Where the server had the paddle in the last received state
+    authoritative: Option<f32>,
+}
+
+impl PaddlePrediction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the locally shown paddle the way the server will once it gets the input
+    pub fn predict(
+        &mut self,
+        player: &mut PlayerDto,
+        direction: &Direction,
+        boost: f32,
+        board: BoardSize,
+    ) {
+        let step = player.paddle_delta * clamp_boost(boost);
+        let delta = match direction {
+            Direction::Positive => step,
+            Direction::Negative => -step,
+        };
+        self.pending.push_back((Instant::now(), delta));
+        player.paddle_position = clamp_paddle(player, player.paddle_position + delta, board);
+    }
+
+    /// Replaces the authoritative position of a received state with the predicted one
+    pub fn apply(&mut self, player: &mut PlayerDto, board: BoardSize) {
+        self.authoritative = Some(player.paddle_position);
+        let predicted = self.reconcile(Instant::now(), player.paddle_position);
+        player.paddle_position = clamp_paddle(player, predicted, board);
+    }
+
+    /// Where the server had the paddle before `apply` replaced it with the prediction
+    pub fn authoritative(&self) -> Option<f32> {
+        self.authoritative
+    }
+
+    /// The server position plus the moves it has likely not applied yet,
+    /// snaps to the server when the prediction diverged too much
+    pub fn reconcile(&mut self, now: Instant, authoritative: f32) -> f32 {
+        while self
+            .pending
+            .front()
+            .is_some_and(|(sent_at, _)| now.duration_since(*sent_at) > PENDING_TIMEOUT)
+        {
+            self.pending.pop_front();
+        }
+
+        let predicted = authoritative + self.pending.iter().map(|(_, delta)| delta).sum::<f32>();
+        if (predicted - authoritative).abs() > SNAP_THRESHOLD {
+            self.pending.clear();
+            return authoritative;
+        }
+        predicted
+    }
+}
+
+/// Keeps the paddle within its side of the board like the server does
+fn clamp_paddle(player: &PlayerDto, position: f32, board: BoardSize) -> f32 {
+    let side_length = player
+        .position
+        .map_or(board.width, |position| board.side_length(position));
+    position.clamp(
+        player.paddle_width / 2.0,
+        side_length - player.paddle_width / 2.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prediction(deltas: &[f32], sent_at: Instant) -> PaddlePrediction {
+        PaddlePrediction {
+            pending: deltas.iter().map(|delta| (sent_at, *delta)).collect(),
+            authoritative: None,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_keeps_pending_moves() {
+        let now = Instant::now();
+        let mut prediction = prediction(&[0.3, 0.3], now);
+
+        // the server has not seen our two moves yet
+        assert!((prediction.reconcile(now, 5.0) - 5.6).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_reconcile_drops_confirmed_moves() {
+        let now = Instant::now();
+        let mut prediction = prediction(&[0.3], now - Duration::from_millis(500));
+
+        assert_eq!(prediction.reconcile(now, 5.3), 5.3);
+        assert!(prediction.pending.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_snaps_to_server() {
+        let now = Instant::now();
+        let mut prediction = prediction(&[0.3; 5], now);
+
+        assert_eq!(prediction.reconcile(now, 5.0), 5.0);
+        assert!(prediction.pending.is_empty());
+    }
+}
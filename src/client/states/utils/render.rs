@@ -9,12 +9,11 @@ use ratatui::{
 };
 use uuid::Uuid;
 
-use crate::common::models::{BallDto, GameDto, PlayerDto, PlayerPosition};
+use crate::common::models::{BallDto, BoardSize, GameDto, GameMode, PlayerDto, PlayerPosition};
 
+use super::trail::{render_ball_trail, BallTrail};
 use super::widget::{get_widget_text, Widget};
 
-const SERVER_GAME_BOARD_SIZE: f32 = 10.0;
-
 pub fn into_title(input: &str) -> String {
     input
         .to_uppercase()
@@ -24,8 +23,40 @@ pub fn into_title(input: &str) -> String {
         .join(" ")
 }
 
+/// Smallest terminal the game is drawn in, anything smaller only gets asked to grow
+pub const MIN_TERMINAL_SIZE: (u16, u16) = (40, 20);
+
+/// Whether `area` fits the board and the scoreboard above it
+pub fn is_large_enough(area: Rect) -> bool {
+    area.width >= MIN_TERMINAL_SIZE.0 && area.height >= MIN_TERMINAL_SIZE.1
+}
+
+/// Shown instead of the current screen while the terminal is below `MIN_TERMINAL_SIZE`
+pub fn render_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let text = format!(
+        "Please enlarge your terminal (min {}x{}, now {}x{})",
+        MIN_TERMINAL_SIZE.0, MIN_TERMINAL_SIZE.1, area.width, area.height
+    );
+    let [line_area] = Layout::vertical([Constraint::Length(3)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .yellow()
+            .centered()
+            .wrap(Wrap { trim: true }),
+        line_area,
+    );
+}
+
 /// Render disconnect popup
 pub fn render_disconnect_popup(frame: &mut Frame, area: Rect) {
+    render_error_popup(frame, area, " DISCONNECTED ");
+}
+
+/// Red popup in the middle of `area` with `title`, left with the leave key
+pub fn render_error_popup(frame: &mut Frame, area: Rect, title: &str) {
     let [popup_area] = Layout::horizontal(vec![Constraint::Percentage(50)])
         .flex(Flex::Center)
         .areas(area);
@@ -47,7 +78,7 @@ pub fn render_disconnect_popup(frame: &mut Frame, area: Rect) {
 
     frame.render_widget(
         Block::new()
-            .title(Line::from(" DISCONNECTED ".white()).centered())
+            .title(Line::from(title.white()).centered())
             .title_bottom(Line::from(vec![" Leave ".white(), "<Esc> ".white()]).centered()),
         popup_text_area,
     );
@@ -92,16 +123,17 @@ pub fn render_inner_rectangle(frame: &mut Frame, outer_rect: Rect) -> Rect {
 
 /// Helper function to calculate evenly distributed rectangles within a given rectangle
 pub fn evenly_distanced_rects(rect: Rect, num_rects: usize) -> Rc<[Rect]> {
-    Layout::vertical(
-        std::iter::repeat(Constraint::Percentage(100 / num_rects as u16)).take(num_rects),
-    )
+    Layout::vertical(std::iter::repeat_n(
+        Constraint::Percentage(100 / num_rects as u16),
+        num_rects,
+    ))
     .split(rect)
 }
 
 /// Renders a list of strings evenly distributed and centered within a rectangle.
 /// The selected item is highlighted.
 pub fn render_list(frame: &mut Frame, items: &[String], selected_index: usize, rect: Rect) {
-    let layout = Layout::vertical(std::iter::repeat(Constraint::Length(1)).take(items.len()))
+    let layout = Layout::vertical(std::iter::repeat_n(Constraint::Length(1), items.len()))
         .flex(Flex::SpaceAround)
         .split(rect);
 
@@ -126,7 +158,7 @@ pub fn render_player_list(
     items: &[(String, bool, Option<PlayerPosition>)],
     rect: Rect,
 ) {
-    let layout = Layout::vertical(std::iter::repeat(Constraint::Length(1)).take(4))
+    let layout = Layout::vertical(std::iter::repeat_n(Constraint::Length(1), 4))
         .flex(Flex::SpaceAround)
         .split(rect);
 
@@ -185,6 +217,15 @@ pub fn render_settings(
         " \u{2192} ".green(),
     ])
     .right_aligned();
+    let toggle_instructions =
+        Line::from(vec![" Toggle ".into(), "<Space> ".green().bold()]).right_aligned();
+    let key_bindings_instructions = Line::from(vec![
+        " Action".into(),
+        " \u{2190}\u{2192} ".green(),
+        "| Rebind".into(),
+        " <Enter> ".green().bold(),
+    ])
+    .right_aligned();
 
     for (i, ((text, widget_area), widget)) in items
         .iter()
@@ -208,7 +249,7 @@ pub fn render_settings(
                 if let Widget::Input(active_input) = active_widget {
                     if std::ptr::eq(input, active_input) {
                         frame.set_cursor_position(Position::new(
-                            inner_input_area.x + input.char_index as u16,
+                            inner_input_area.x + input.cursor_column(),
                             inner_input_area.y,
                         ));
                     }
@@ -226,6 +267,18 @@ pub fn render_settings(
                 style = Style::default().bg(slider.clone().get_color());
                 color_check = "     ";
             }
+            Widget::Toggle(_) => {
+                frame.render_widget(
+                    widget_block.title_bottom(toggle_instructions.clone()),
+                    *widget_area,
+                );
+            }
+            Widget::KeyBindings(_) => {
+                frame.render_widget(
+                    widget_block.title_bottom(key_bindings_instructions.clone()),
+                    *widget_area,
+                );
+            }
         }
 
         frame.render_widget(
@@ -239,6 +292,11 @@ pub fn render_settings(
     }
 }
 
+/// "3…" style countdown label, rounding the remaining time up to whole seconds
+pub fn countdown_text(remaining_ms: u64) -> String {
+    format!("{}\u{2026}", remaining_ms.div_ceil(1000).max(1))
+}
+
 pub fn render_text_in_center_of_rect(frame: &mut Frame, text: Paragraph, rect: Rect) {
     frame.render_widget(text.centered(), evenly_distanced_rects(rect, 2)[1]);
 }
@@ -254,29 +312,35 @@ pub fn centered_rect(area: Rect, horizontal_percent: u16, vertical_percent: u16)
     rect
 }
 
-/// Helper function to scale dimensions such that width is exactly 2 times height,
+/// Helper function to scale dimensions such that width is exactly 2 times height times
+/// the `aspect` (width over height) of the board, cells being about twice as tall as wide,
 /// while ensuring neither exceeds the original dimensions.
-fn scale_dimensions(original_height: u16, original_width: u16) -> (u16, u16) {
-    // Target ratio is width = 2 * height
-    let target_ratio = 2;
+/// A terminal too narrow for the ratio still gets a single cell rather than an empty board
+fn scale_dimensions(original_height: u16, original_width: u16, aspect: f32) -> (u16, u16) {
+    // Target ratio is width = 2 * aspect * height
+    let target_ratio = 2.0 * aspect;
 
     // Calculate the maximum possible height and width based on the original dimensions
     let max_height = original_height;
     let max_width = original_width;
 
-    // Calculate the height and width that satisfy width = 2 * height
+    // Calculate the height and width that satisfy the ratio
     // while ensuring neither exceeds the original dimensions
-    let new_height = max_height.min(max_width / target_ratio);
-    let new_width = target_ratio * new_height;
+    let new_height = max_height.min(((max_width as f32 / target_ratio) as u16).max(1));
+    let new_width = ((target_ratio * new_height as f32).round() as u16).min(max_width);
 
     (new_height, new_width)
 }
 
-/// Helper function to calculate the game area and scaling factors
-pub fn calculate_game_area(frame: &Frame) -> (Rect, Rect, f32, f32) {
-    let terminal_size = frame.area();
-    let (game_area_bounding_box_height, game_area_bounding_box_width) =
-        scale_dimensions(terminal_size.height, terminal_size.width);
+/// Helper function to calculate the game area and scaling factors for a `board`,
+/// the width and the height are scaled on their own
+pub fn calculate_game_area(area: Rect, board: BoardSize) -> (Rect, Rect, f32, f32) {
+    let terminal_size = area;
+    let (game_area_bounding_box_height, game_area_bounding_box_width) = scale_dimensions(
+        terminal_size.height,
+        terminal_size.width,
+        board.width / board.height,
+    );
 
     let game_area_bounding_box = Rect {
         x: terminal_size.x.saturating_add(
@@ -300,118 +364,216 @@ pub fn calculate_game_area(frame: &Frame) -> (Rect, Rect, f32, f32) {
         vertical: 1,
     });
 
-    let scale_x = game_area.width as f32 / SERVER_GAME_BOARD_SIZE;
-    let scale_y = game_area.height as f32 / SERVER_GAME_BOARD_SIZE;
+    let scale_x = game_area.width as f32 / board.width;
+    let scale_y = game_area.height as f32 / board.height;
 
     (game_area_bounding_box, game_area, scale_x, scale_y)
 }
 
-/// Render a single player's paddle
-pub fn render_player(
-    player: &PlayerDto,
-    player_color: ratatui::style::Color,
-    frame: &mut Frame,
+/// Order of the players in the scoreboard, clockwise from the top
+fn scoreboard_order(position: PlayerPosition) -> u8 {
+    match position {
+        PlayerPosition::Top => 0,
+        PlayerPosition::Right => 1,
+        PlayerPosition::Bottom => 2,
+        PlayerPosition::Left => 3,
+    }
+}
+
+/// Builds the scoreboard line with a color swatch, name and standing in `mode` for every player,
+/// names are shortened evenly so that the line fits into `width`.
+/// The player who last touched the ball is shown in bold
+pub fn scoreboard_line(
+    players: &[&PlayerDto],
+    mode: GameMode,
+    position_color: impl Fn(PlayerPosition) -> Color,
+    last_touched_by: Option<Uuid>,
+    width: usize,
+) -> Line<'static> {
+    const SWATCH: &str = "\u{25A0} ";
+    const SEPARATOR: &str = " | ";
+
+    let mut players: Vec<_> = players
+        .iter()
+        .filter_map(|player| player.position.map(|position| (position, *player)))
+        .collect();
+    players.sort_by_key(|(position, _)| scoreboard_order(*position));
+
+    if players.is_empty() {
+        return Line::default();
+    }
+
+    // everything but the names has to fit
+    let fixed_width: usize = players
+        .iter()
+        .map(|(_, player)| SWATCH.chars().count() + 1 + player.standing(mode).to_string().len())
+        .sum::<usize>()
+        + SEPARATOR.len() * (players.len() - 1);
+    let name_width = width.saturating_sub(fixed_width) / players.len();
+
+    let mut spans = Vec::new();
+    for (i, (position, player)) in players.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(SEPARATOR));
+        }
+        let name = if player.name.chars().count() <= name_width {
+            player.name.clone()
+        } else if name_width > 0 {
+            let mut name: String = player.name.chars().take(name_width - 1).collect();
+            name.push('\u{2026}');
+            name
+        } else {
+            String::new()
+        };
+        spans.push(Span::styled(
+            SWATCH,
+            Style::default().fg(position_color(position)),
+        ));
+        let text = format!("{} {}", name, player.standing(mode));
+        if last_touched_by == Some(player.id) {
+            spans.push(Span::styled(text, Style::default().bold()));
+        } else {
+            spans.push(Span::raw(text));
+        }
+    }
+
+    Line::from(spans)
+}
+
+/// Cells a paddle at `paddle_position` covers, it lies along the side the player defends
+pub fn paddle_rect(
+    position: PlayerPosition,
+    paddle_position: f32,
+    paddle_width: f32,
     game_area: &Rect,
     scale_x: f32,
     scale_y: f32,
-) {
-    const PLAYER_VERTICAL_BODY: &str = "█";
-    const PLAYER_UP_BODY: &str = "▄";
-    const PLAYER_BOTTOM_BODY: &str = "▀";
-
-    let player_style = ratatui::style::Style::default().fg(player_color);
-
-    match player.position {
-        Some(PlayerPosition::Top) | Some(PlayerPosition::Bottom) => {
+) -> Rect {
+    let paddle_thickness = 1; // Paddle depth is 1 character
+    match position {
+        PlayerPosition::Top | PlayerPosition::Bottom => {
             // Calculate horizontal paddle dimensions and position
-            let paddle_length = (player.paddle_width * scale_x) as u16;
-            let paddle_thickness = 1; // Paddle depth is 1 character
-
-            let paddle_center = (player.paddle_position * scale_x) as u16;
+            let paddle_length = (paddle_width * scale_x) as u16;
+            let paddle_center = (paddle_position * scale_x) as u16;
             let paddle_x = game_area
                 .x
                 .saturating_add(paddle_center)
                 .saturating_sub(paddle_length / 2);
-            match player.position {
-                Some(PlayerPosition::Top) => {
-                    let paddle_y = game_area.y;
-                    frame.render_widget(
-                        Paragraph::new(PLAYER_UP_BODY.repeat(paddle_length as usize))
-                            .style(player_style),
-                        Rect {
-                            x: paddle_x,
-                            y: paddle_y,
-                            width: paddle_length,
-                            height: paddle_thickness,
-                        },
-                    );
-                }
-                Some(PlayerPosition::Bottom) => {
-                    let paddle_y = game_area.y + game_area.height - paddle_thickness;
-                    frame.render_widget(
-                        Paragraph::new(PLAYER_BOTTOM_BODY.repeat(paddle_length as usize))
-                            .style(player_style),
-                        Rect {
-                            x: paddle_x,
-                            y: paddle_y,
-                            width: paddle_length,
-                            height: paddle_thickness,
-                        },
-                    );
-                }
-                _ => {}
+            let paddle_y = match position {
+                PlayerPosition::Top => game_area.y,
+                _ => game_area.y + game_area.height - paddle_thickness,
+            };
+            Rect {
+                x: paddle_x,
+                y: paddle_y,
+                width: paddle_length,
+                height: paddle_thickness,
             }
         }
-        Some(PlayerPosition::Left) | Some(PlayerPosition::Right) => {
+        PlayerPosition::Left | PlayerPosition::Right => {
             // Calculate vertical paddle dimensions and position
-            let paddle_length = (player.paddle_width * scale_y) as u16;
-            let paddle_thickness = 1; // Paddle depth is 1 character
-            let paddle_center = (player.paddle_position * scale_y) as u16;
+            let paddle_length = (paddle_width * scale_y) as u16;
+            let paddle_center = (paddle_position * scale_y) as u16;
             let paddle_y = game_area
                 .y
                 .saturating_add(paddle_center)
                 .saturating_sub(paddle_length / 2);
-            match player.position {
-                Some(PlayerPosition::Left) => {
-                    let paddle_x = game_area.x;
-                    frame.render_widget(
-                        Paragraph::new(
-                            format!("{}\n", PLAYER_VERTICAL_BODY).repeat(paddle_length as usize),
-                        )
-                        .style(player_style),
-                        Rect {
-                            x: paddle_x,
-                            y: paddle_y,
-                            width: paddle_thickness,
-                            height: paddle_length,
-                        },
-                    );
-                }
-                Some(PlayerPosition::Right) => {
-                    let paddle_x = game_area
-                        .x
-                        .saturating_add(game_area.width)
-                        .saturating_sub(paddle_thickness);
-                    frame.render_widget(
-                        Paragraph::new(
-                            format!("{}\n", PLAYER_VERTICAL_BODY).repeat(paddle_length as usize),
-                        )
-                        .style(player_style),
-                        Rect {
-                            x: paddle_x,
-                            y: paddle_y,
-                            width: paddle_thickness,
-                            height: paddle_length,
-                        },
-                    );
-                }
-                _ => {}
+            let paddle_x = match position {
+                PlayerPosition::Left => game_area.x,
+                _ => game_area
+                    .x
+                    .saturating_add(game_area.width)
+                    .saturating_sub(paddle_thickness),
+            };
+            Rect {
+                x: paddle_x,
+                y: paddle_y,
+                width: paddle_thickness,
+                height: paddle_length,
             }
         }
-        None => {}
     }
 }
 
+/// The characters a paddle of `length` cells is drawn with
+fn paddle_body(position: PlayerPosition, length: u16) -> String {
+    const PLAYER_VERTICAL_BODY: &str = "█";
+    const PLAYER_UP_BODY: &str = "▄";
+    const PLAYER_BOTTOM_BODY: &str = "▀";
+
+    match position {
+        PlayerPosition::Top => PLAYER_UP_BODY.repeat(length as usize),
+        PlayerPosition::Bottom => PLAYER_BOTTOM_BODY.repeat(length as usize),
+        PlayerPosition::Left | PlayerPosition::Right => {
+            format!("{}\n", PLAYER_VERTICAL_BODY).repeat(length as usize)
+        }
+    }
+}
+
+/// Render a single player's paddle
+pub fn render_player(
+    player: &PlayerDto,
+    player_color: ratatui::style::Color,
+    frame: &mut Frame,
+    game_area: &Rect,
+    scale_x: f32,
+    scale_y: f32,
+) {
+    let Some(position) = player.position else {
+        return;
+    };
+    let rect = paddle_rect(
+        position,
+        player.paddle_position,
+        player.paddle_width,
+        game_area,
+        scale_x,
+        scale_y,
+    );
+    let length = rect.width.max(rect.height);
+    frame.render_widget(
+        Paragraph::new(paddle_body(position, length))
+            .style(ratatui::style::Style::default().fg(player_color)),
+        rect,
+    );
+}
+
+/// Dim paddle where the server last had ours, drawn under the predicted one
+/// so only the part the prediction is ahead of the server shows
+pub fn render_ghost_paddle(
+    player: &PlayerDto,
+    authoritative_position: f32,
+    board: BoardSize,
+    frame: &mut Frame,
+) {
+    let Some(position) = player.position else {
+        return;
+    };
+    // the same areas `render_game` draws the board in
+    let [_, board_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(frame.area());
+    let (_, game_area, scale_x, scale_y) = calculate_game_area(board_area, board);
+    if game_area.is_empty() {
+        return;
+    }
+    let rect = paddle_rect(
+        position,
+        authoritative_position,
+        player.paddle_width,
+        &game_area,
+        scale_x,
+        scale_y,
+    )
+    .intersection(game_area);
+    let length = rect.width.max(rect.height);
+    frame.render_widget(
+        Paragraph::new(paddle_body(position, length))
+            .dark_gray()
+            .dim(),
+        rect,
+    );
+}
+
 /// Render the ball
 pub fn render_ball(
     ball: &BallDto,
@@ -428,82 +590,174 @@ pub fn render_ball(
         .y
         .saturating_add((ball.position.y * scale_y) as u16);
 
-    // Render the ball as a single character
+    // A ball spanning several cells is drawn as a block of characters, kept within the board
+    let (half_width, half_height) = (
+        ball_half_extent(ball.radius, scale_x),
+        ball_half_extent(ball.radius, scale_y),
+    );
+    let left = ball_x.saturating_sub(half_width).max(game_area.left());
+    let top = ball_y.saturating_sub(half_height).max(game_area.top());
+    let right = (ball_x + half_width + 1)
+        .min(game_area.right())
+        .max(left + 1);
+    let bottom = (ball_y + half_height + 1)
+        .min(game_area.bottom())
+        .max(top + 1);
+    let (width, height) = (right - left, bottom - top);
+
     frame.render_widget(
-        Paragraph::new("●")
+        Paragraph::new("●".repeat(width as usize * height as usize))
+            .wrap(Wrap { trim: false })
             .style(ratatui::style::Style::default().fg(ratatui::style::Color::White)),
         Rect {
-            x: ball_x,
-            y: ball_y,
-            width: 1,
-            height: 1,
+            x: left,
+            y: top,
+            width,
+            height,
         },
     );
 }
 
+/// Cells a ball of `radius` covers on each side of its center, a small ball is a single character
+pub fn ball_half_extent(radius: f32, scale: f32) -> u16 {
+    const MAX_HALF_EXTENT: u16 = 2;
+    ((radius * scale - 0.5).max(0.0).floor() as u16).min(MAX_HALF_EXTENT)
+}
+
+/// Arrow pointing where a ball is about to be served, with the cell offset it is drawn at
+pub fn serve_arrow(ball: &BallDto) -> (&'static str, i32, i32) {
+    let velocity = &ball.velocity;
+    if velocity.x.abs() > velocity.y.abs() {
+        if velocity.x > 0.0 {
+            ("\u{2192}", 1, 0)
+        } else {
+            ("\u{2190}", -1, 0)
+        }
+    } else if velocity.y > 0.0 {
+        ("\u{2193}", 0, 1)
+    } else {
+        ("\u{2191}", 0, -1)
+    }
+}
+
+fn render_serve_arrow(
+    ball: &BallDto,
+    frame: &mut Frame,
+    game_area: &Rect,
+    scale_x: f32,
+    scale_y: f32,
+) {
+    let (arrow, dx, dy) = serve_arrow(ball);
+    let x = game_area.x as i32 + (ball.position.x * scale_x) as i32 + dx;
+    let y = game_area.y as i32 + (ball.position.y * scale_y) as i32 + dy;
+    let (Ok(x), Ok(y)) = (u16::try_from(x), u16::try_from(y)) else {
+        return;
+    };
+    if game_area.contains(Position::new(x, y)) {
+        frame.render_widget(Paragraph::new(arrow).yellow(), Rect::new(x, y, 1, 1));
+    }
+}
+
 pub fn render_game(
     game: &GameDto,
-    our_player_id: Uuid,
-    player_color: ratatui::style::Color,
-    other_players_color: ratatui::style::Color,
+    focused_player_id: Uuid,
+    position_color: impl Fn(PlayerPosition) -> ratatui::style::Color,
+    trail: Option<&BallTrail>,
     frame: &mut Frame,
 ) {
+    // Keep the top row for the scoreboard
+    let [scoreboard_area, board_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(frame.area());
+
+    frame.render_widget(
+        Paragraph::new(scoreboard_line(
+            &game.players.values().collect::<Vec<_>>(),
+            game.mode,
+            &position_color,
+            game.last_touched_by(),
+            scoreboard_area.width as usize,
+        ))
+        .centered(),
+        scoreboard_area,
+    );
+
     // Calculate the game area and scaling factors once
-    let (game_area_bounding_box, game_area, scale_x, scale_y) = calculate_game_area(frame);
+    let (game_area_bounding_box, game_area, scale_x, scale_y) =
+        calculate_game_area(board_area, game.board());
 
     // Render the game area border
     frame.render_widget(Block::bordered(), game_area_bounding_box);
 
+    // a terminal this small leaves no room inside the border
+    if game_area.is_empty() {
+        return;
+    }
+
     // Render players scores
     for player in game.players.values() {
-        let desc = format!(" {} {} ", player.name, player.score);
+        let desc = format!(" {} {} ", player.name, player.standing(game.mode));
+        let mut desc_style = match player.position {
+            Some(position) => Style::default().fg(position_color(position)),
+            None => Style::default(),
+        };
+        if player.id == focused_player_id {
+            desc_style = desc_style.bold();
+        }
         let desc_len = desc
             .len()
-            .min(frame.area().width as usize)
-            .min(frame.area().height as usize) as u16; // Limit to the frame size
+            .min(game_area_bounding_box.width as usize)
+            .min(game_area_bounding_box.height as usize) as u16; // Limit to the board size
+        let center_x = game_area_bounding_box.x + game_area_bounding_box.width / 2;
+        let center_y = game_area_bounding_box.y + game_area_bounding_box.height / 2;
 
         match player.position {
             Some(PlayerPosition::Top) => {
                 // Position at top-center of the game area
-                let x = game_area_bounding_box.x + game_area_bounding_box.width / 2 - desc_len / 2;
+                let x = center_x - desc_len / 2;
                 let y = game_area_bounding_box.y;
                 frame.render_widget(
-                    Paragraph::new(desc).alignment(Alignment::Center),
+                    Paragraph::new(desc)
+                        .style(desc_style)
+                        .alignment(Alignment::Center),
                     Rect::new(x, y, desc_len, 1),
                 );
             }
             Some(PlayerPosition::Bottom) => {
                 // Position at bottom-center of the game area
-                let x = game_area_bounding_box.x + game_area_bounding_box.width / 2 - desc_len / 2;
+                let x = center_x - desc_len / 2;
                 let y = game_area_bounding_box.y + game_area_bounding_box.height - 1;
                 frame.render_widget(
-                    Paragraph::new(desc).alignment(Alignment::Center),
+                    Paragraph::new(desc)
+                        .style(desc_style)
+                        .alignment(Alignment::Center),
                     Rect::new(x, y, desc_len, 1),
                 );
             }
             Some(PlayerPosition::Left) => {
                 // Vertical text on the left side
                 let x = game_area_bounding_box.x;
-                let y = game_area_bounding_box.y + game_area_bounding_box.height / 2 - desc_len / 2;
+                let y = center_y - desc_len / 2;
                 frame.render_widget(
                     Paragraph::new(
                         desc.chars()
                             .map(|c| Line::from(c.to_string()))
                             .collect::<Vec<Line>>(),
-                    ),
+                    )
+                    .style(desc_style),
                     Rect::new(x, y, 1, desc_len),
                 );
             }
             Some(PlayerPosition::Right) => {
                 // Vertical text on the right side
                 let x = game_area_bounding_box.x + game_area_bounding_box.width - 1;
-                let y = game_area_bounding_box.y + game_area_bounding_box.height / 2 - desc_len / 2;
+                let y = center_y - desc_len / 2;
                 frame.render_widget(
                     Paragraph::new(
                         desc.chars()
                             .map(|c| Line::from(c.to_string()))
                             .collect::<Vec<Line>>(),
-                    ),
+                    )
+                    .style(desc_style),
                     Rect::new(x, y, 1, desc_len),
                 );
             }
@@ -513,16 +767,290 @@ pub fn render_game(
 
     // Render players
     for player in game.players.values() {
-        let player_color = if player.id == our_player_id {
-            player_color
-        } else {
-            other_players_color
-        };
-        render_player(player, player_color, frame, &game_area, scale_x, scale_y);
+        if let Some(position) = player.position {
+            render_player(
+                player,
+                position_color(position),
+                frame,
+                &game_area,
+                scale_x,
+                scale_y,
+            );
+        }
     }
 
-    // Render the ball
-    if let Some(ball) = &game.ball {
+    // Render the trail under the balls
+    if let Some(trail) = trail {
+        render_ball_trail(trail, frame, &game_area, scale_x, scale_y);
+    }
+
+    // Render the balls
+    for ball in game.balls() {
         render_ball(ball, frame, &game_area, scale_x, scale_y);
+        if game.serving {
+            render_serve_arrow(ball, frame, &game_area, scale_x, scale_y);
+        }
+    }
+
+    if game.sudden_death {
+        frame.render_widget(
+            Paragraph::new(" SUDDEN DEATH - next goal wins ")
+                .red()
+                .bold()
+                .centered(),
+            Rect::new(
+                game_area.x,
+                game_area.y,
+                game_area.width,
+                1.min(game_area.height),
+            ),
+        );
+    }
+
+    if let Some(remaining_ms) = game.start_countdown_ms {
+        render_text_in_center_of_rect(
+            frame,
+            Paragraph::new(countdown_text(remaining_ms)).bold(),
+            game_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::models::Vec2;
+    use ratatui::style::Modifier;
+
+    fn player(name: &str, score: u32, position: PlayerPosition) -> PlayerDto {
+        PlayerDto {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            joined_at: chrono::Utc::now(),
+            score,
+            position: Some(position),
+            paddle_position: 5.0,
+            paddle_delta: 0.3,
+            paddle_width: 1.0,
+            is_ready: true,
+            returns: 0,
+            lives: 0,
+            last_score_at: None,
+        }
+    }
+
+    fn color(position: PlayerPosition) -> Color {
+        match position {
+            PlayerPosition::Top => Color::Red,
+            PlayerPosition::Right => Color::Green,
+            PlayerPosition::Bottom => Color::Blue,
+            PlayerPosition::Left => Color::Yellow,
+        }
+    }
+
+    #[test]
+    fn test_paddle_rect_predicted_and_authoritative() {
+        let game_area = Rect::new(1, 1, 20, 20);
+        // a board of 10 units drawn over 20 cells, a paddle of 2 units is 4 cells long
+        let predicted = paddle_rect(PlayerPosition::Bottom, 5.0, 2.0, &game_area, 2.0, 2.0);
+        let authoritative = paddle_rect(PlayerPosition::Bottom, 4.0, 2.0, &game_area, 2.0, 2.0);
+        assert_eq!(predicted, Rect::new(9, 20, 4, 1));
+        assert_eq!(authoritative, Rect::new(7, 20, 4, 1));
+
+        let predicted = paddle_rect(PlayerPosition::Left, 5.0, 2.0, &game_area, 2.0, 2.0);
+        let authoritative = paddle_rect(PlayerPosition::Left, 6.0, 2.0, &game_area, 2.0, 2.0);
+        assert_eq!(predicted, Rect::new(1, 9, 1, 4));
+        assert_eq!(authoritative, Rect::new(1, 11, 1, 4));
+
+        assert_eq!(
+            paddle_rect(PlayerPosition::Top, 5.0, 2.0, &game_area, 2.0, 2.0),
+            Rect::new(9, 1, 4, 1)
+        );
+        assert_eq!(
+            paddle_rect(PlayerPosition::Right, 5.0, 2.0, &game_area, 2.0, 2.0),
+            Rect::new(20, 9, 1, 4)
+        );
+    }
+
+    #[test]
+    fn test_scoreboard_line() {
+        let left = player("dave", 0, PlayerPosition::Left);
+        let top = player("alice", 3, PlayerPosition::Top);
+        let bottom = player("carol", 10, PlayerPosition::Bottom);
+        let right = player("bob", 1, PlayerPosition::Right);
+        let bob_id = right.id;
+
+        let line = scoreboard_line(
+            &[&left, &top, &bottom, &right],
+            GameMode::Score,
+            color,
+            Some(bob_id),
+            80,
+        );
+
+        assert_eq!(
+            line.to_string(),
+            "\u{25A0} alice 3 | \u{25A0} bob 1 | \u{25A0} carol 10 | \u{25A0} dave 0"
+        );
+        let swatch_colors: Vec<_> = line
+            .spans
+            .iter()
+            .filter(|span| span.content.starts_with('\u{25A0}'))
+            .map(|span| span.style.fg)
+            .collect();
+        assert_eq!(
+            swatch_colors,
+            vec![
+                Some(Color::Red),
+                Some(Color::Green),
+                Some(Color::Blue),
+                Some(Color::Yellow)
+            ]
+        );
+        let bold: Vec<_> = line
+            .spans
+            .iter()
+            .filter(|span| span.style.add_modifier.contains(Modifier::BOLD))
+            .map(|span| span.content.to_string())
+            .collect();
+        assert_eq!(bold, vec!["bob 1"]);
+    }
+
+    #[test]
+    fn test_scoreboard_line_truncates_names() {
+        let top = player("alexandria", 3, PlayerPosition::Top);
+        let bottom = player("bartholomew", 10, PlayerPosition::Bottom);
+
+        // fixed part is 2 + 2 + 2 + 3 + 3 = 12, leaving 4 characters per name
+        let line = scoreboard_line(&[&top, &bottom], GameMode::Score, color, None, 20);
+
+        assert_eq!(
+            line.to_string(),
+            "\u{25A0} ale\u{2026} 3 | \u{25A0} bar\u{2026} 10"
+        );
+        assert!(line.width() <= 20);
+    }
+
+    #[test]
+    fn test_serve_arrow() {
+        let ball = |x, y| BallDto {
+            position: Vec2 { x: 5.0, y: 5.0 },
+            velocity: Vec2 { x, y },
+            radius: 0.125,
+            last_touched_by: None,
+        };
+
+        assert_eq!(serve_arrow(&ball(0.0, -0.1)), ("\u{2191}", 0, -1));
+        assert_eq!(serve_arrow(&ball(0.0, 0.1)), ("\u{2193}", 0, 1));
+        assert_eq!(serve_arrow(&ball(-0.1, 0.0)), ("\u{2190}", -1, 0));
+        assert_eq!(serve_arrow(&ball(0.1, 0.02)), ("\u{2192}", 1, 0));
+    }
+
+    #[test]
+    fn test_countdown_text() {
+        assert_eq!(countdown_text(3000), "3\u{2026}");
+        assert_eq!(countdown_text(2001), "3\u{2026}");
+        assert_eq!(countdown_text(2000), "2\u{2026}");
+        assert_eq!(countdown_text(0), "1\u{2026}");
+    }
+
+    #[test]
+    fn test_is_large_enough() {
+        let (width, height) = MIN_TERMINAL_SIZE;
+        assert!(is_large_enough(Rect::new(0, 0, width, height)));
+        assert!(is_large_enough(Rect::new(5, 5, 200, 60)));
+        assert!(!is_large_enough(Rect::new(0, 0, width - 1, height)));
+        assert!(!is_large_enough(Rect::new(0, 0, width, height - 1)));
+        assert!(!is_large_enough(Rect::default()));
+    }
+
+    #[test]
+    fn test_scale_dimensions() {
+        assert_eq!(scale_dimensions(20, 100, 1.0), (20, 40));
+        assert_eq!(scale_dimensions(50, 30, 1.0), (15, 30));
+        assert_eq!(
+            scale_dimensions(u16::MAX, u16::MAX, 1.0),
+            (u16::MAX / 2, u16::MAX - 1)
+        );
+        // too narrow for the ratio, but not empty
+        assert_eq!(scale_dimensions(5, 1, 1.0), (1, 1));
+        assert_eq!(scale_dimensions(1, 1, 1.0), (1, 1));
+        assert_eq!(scale_dimensions(0, 10, 1.0), (0, 0));
+        assert_eq!(scale_dimensions(10, 0, 1.0), (1, 0));
+    }
+
+    #[test]
+    fn test_calculate_game_area_extreme_sizes() {
+        let (bounding_box, game_area, scale_x, scale_y) =
+            calculate_game_area(Rect::new(0, 1, 200, 60), BoardSize::square(10.0));
+        assert_eq!(bounding_box, Rect::new(40, 1, 120, 60));
+        assert_eq!(game_area, Rect::new(42, 2, 116, 58));
+        assert_eq!((scale_x, scale_y), (11.6, 5.8));
+
+        for area in [
+            Rect::new(0, 0, 0, 0),
+            Rect::new(0, 1, 1, 1),
+            Rect::new(3, 3, 4, 2),
+            Rect::new(0, 0, u16::MAX, u16::MAX),
+        ] {
+            let (bounding_box, game_area, scale_x, scale_y) =
+                calculate_game_area(area, BoardSize::square(10.0));
+            assert!(area.contains(bounding_box.as_position()) || area.is_empty());
+            assert!(bounding_box.right() <= area.right() && bounding_box.bottom() <= area.bottom());
+            assert!(scale_x >= 0.0 && scale_y >= 0.0);
+            assert!(scale_x.is_finite() && scale_y.is_finite());
+            assert!(game_area.width <= bounding_box.width);
+        }
+    }
+
+    #[test]
+    fn test_calculate_game_area_rectangular_board() {
+        let board = BoardSize {
+            width: 20.0,
+            height: 10.0,
+        };
+        let (bounding_box, game_area, scale_x, scale_y) =
+            calculate_game_area(Rect::new(0, 1, 200, 60), board);
+        // twice as wide as a square board of the same height
+        assert_eq!(bounding_box, Rect::new(0, 6, 200, 50));
+        assert_eq!(game_area, Rect::new(2, 7, 196, 48));
+        assert_eq!((scale_x, scale_y), (9.8, 4.8));
+    }
+
+    #[test]
+    fn test_render_game_on_tiny_terminals() {
+        use crate::common::{Game, Player};
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut game = Game::new();
+        for position in [
+            PlayerPosition::Top,
+            PlayerPosition::Bottom,
+            PlayerPosition::Left,
+            PlayerPosition::Right,
+        ] {
+            let mut player = Player::new("a rather long name".to_string(), false);
+            player.position = Some(position);
+            game.add_player(player).unwrap();
+        }
+        let game = GameDto::from(&game);
+
+        for (width, height) in [(1, 1), (2, 2), (5, 4), (7, 5), (12, 6), (300, 3)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|frame| render_game(&game, Uuid::nil(), color, None, frame))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ball_half_extent() {
+        // the default ball is a single character on a usual terminal
+        assert_eq!(ball_half_extent(0.125, 7.2), 0);
+        assert_eq!(ball_half_extent(0.125, 3.6), 0);
+        assert_eq!(ball_half_extent(0.5, 7.2), 2);
+        assert_eq!(ball_half_extent(0.5, 3.6), 1);
+        // clamped however large it gets
+        assert_eq!(ball_half_extent(1.0, 20.0), 2);
     }
 }
@@ -0,0 +1,48 @@
+use crate::common::models::GameDto;
+
+/// Replaces `game` with `updated` unless an equal or newer snapshot was already applied,
+/// UDP may deliver the server's broadcasts out of order
+pub fn apply_if_newer(game: &mut GameDto, last_sequence: &mut u64, updated: GameDto) -> bool {
+    if updated.sequence <= *last_sequence {
+        return false;
+    }
+    *last_sequence = updated.sequence;
+    *game = updated;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Game;
+
+    fn snapshot(sequence: u64) -> GameDto {
+        let mut game = Game::new();
+        game.sequence = sequence;
+        GameDto::from(game)
+    }
+
+    #[test]
+    fn test_newer_snapshot_applied() {
+        let mut game = snapshot(1);
+        let mut last_sequence = 1;
+
+        let newer = snapshot(3);
+        let newer_id = newer.id;
+        assert!(apply_if_newer(&mut game, &mut last_sequence, newer));
+        assert_eq!(game.id, newer_id);
+        assert_eq!(last_sequence, 3);
+    }
+
+    #[test]
+    fn test_older_snapshot_ignored() {
+        let mut game = snapshot(5);
+        let game_id = game.id;
+        let mut last_sequence = 5;
+
+        assert!(!apply_if_newer(&mut game, &mut last_sequence, snapshot(4)));
+        assert!(!apply_if_newer(&mut game, &mut last_sequence, snapshot(5))); // duplicate
+        assert_eq!(game.id, game_id);
+        assert_eq!(last_sequence, 5);
+    }
+}
@@ -0,0 +1,132 @@
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use log::debug;
+use uuid::Uuid;
+
+use crate::common::models::GameDto;
+use crate::common::PlayerPosition;
+
+/// Cues closer together than this are dropped, a bell per snapshot would be a buzz
+const MIN_CUE_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SoundCue {
+    Goal,
+    Deflect, // our paddle sent a ball back
+}
+
+/// Cue for what happened between two consecutive snapshots, a goal wins over a deflection
+pub fn detect_cue(previous: &GameDto, current: &GameDto, our_player_id: Uuid) -> Option<SoundCue> {
+    if current.last_goal_at.is_some() && current.last_goal_at != previous.last_goal_at {
+        return Some(SoundCue::Goal);
+    }
+
+    let our_position = current.players.get(&our_player_id)?.position?;
+    // velocity away from our side, only a paddle on it turns the ball that way
+    let away = |velocity_x: f32, velocity_y: f32| match our_position {
+        PlayerPosition::Top => velocity_y > 0.0,
+        PlayerPosition::Bottom => velocity_y < 0.0,
+        PlayerPosition::Left => velocity_x > 0.0,
+        PlayerPosition::Right => velocity_x < 0.0,
+    };
+    let deflected = previous
+        .balls()
+        .zip(current.balls())
+        .any(|(before, after)| {
+            after.last_touched_by == Some(our_player_id)
+                && !away(before.velocity.x, before.velocity.y)
+                && away(after.velocity.x, after.velocity.y)
+        });
+    deflected.then_some(SoundCue::Deflect)
+}
+
+/// Rings the terminal bell for cues, at most one per `MIN_CUE_INTERVAL`
+#[derive(Default)]
+pub struct SoundCues {
+    last_played_at: Option<Instant>,
+}
+
+impl SoundCues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a cue at `now` is far enough from the last one to be played
+    pub fn should_play(&mut self, now: Instant) -> bool {
+        if self
+            .last_played_at
+            .is_some_and(|last| now.duration_since(last) < MIN_CUE_INTERVAL)
+        {
+            return false;
+        }
+        self.last_played_at = Some(now);
+        true
+    }
+
+    pub fn play(&mut self, cue: SoundCue, now: Instant) {
+        if !self.should_play(now) {
+            return;
+        }
+        debug!("Playing {:?} cue", cue);
+        let mut stdout = std::io::stdout();
+        // BEL moves no cursor, it leaves the drawn frame alone
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::models::Vec2;
+    use crate::common::{Game, Player};
+
+    fn game_with_us() -> (Game, Uuid) {
+        let mut game = Game::new();
+        let mut us = Player::new("us".to_string(), false);
+        us.position = Some(PlayerPosition::Left);
+        let our_id = us.id;
+        game.add_player(us).unwrap();
+        (game, our_id)
+    }
+
+    #[test]
+    fn test_detect_cue() {
+        let (mut game, our_id) = game_with_us();
+        game.balls[0].velocity = Vec2 { x: -0.15, y: 0.0 };
+        let incoming = GameDto::from(&game);
+
+        assert_eq!(detect_cue(&incoming, &incoming, our_id), None);
+
+        // our paddle sends the ball back
+        game.balls[0].velocity = Vec2 { x: 0.15, y: 0.05 };
+        game.balls[0].last_touched_by = Some(our_id);
+        let deflected = GameDto::from(&game);
+        assert_eq!(
+            detect_cue(&incoming, &deflected, our_id),
+            Some(SoundCue::Deflect)
+        );
+        // still flying away, no new deflection
+        assert_eq!(detect_cue(&deflected, &deflected, our_id), None);
+
+        // a wall turning a ball we touched last is not our deflection
+        game.balls[0].velocity = Vec2 { x: 0.15, y: -0.05 };
+        assert_eq!(detect_cue(&deflected, &GameDto::from(&game), our_id), None);
+
+        game.last_goal_at = Some(chrono::Utc::now());
+        assert_eq!(
+            detect_cue(&incoming, &GameDto::from(&game), our_id),
+            Some(SoundCue::Goal)
+        );
+    }
+
+    #[test]
+    fn test_cues_debounced() {
+        let mut cues = SoundCues::new();
+        let now = Instant::now();
+
+        assert!(cues.should_play(now));
+        assert!(!cues.should_play(now + MIN_CUE_INTERVAL / 2));
+        assert!(cues.should_play(now + MIN_CUE_INTERVAL));
+    }
+}
@@ -0,0 +1,43 @@
+#[derive(Clone)]
+pub struct Toggle {
+    pub value: bool,
+}
+
+impl Default for Toggle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toggle {
+    pub fn new() -> Self {
+        Self { value: false }
+    }
+
+    pub fn from(value: bool) -> Self {
+        Self { value }
+    }
+
+    pub fn get_text(self) -> String {
+        if self.value { "on" } else { "off" }.to_string()
+    }
+
+    pub fn toggle(&mut self) {
+        self.value = !self.value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle() {
+        let mut toggle = Toggle::new();
+        assert!(!toggle.value);
+        assert_eq!(toggle.clone().get_text(), "off");
+        toggle.toggle();
+        assert!(toggle.value);
+        assert_eq!(toggle.get_text(), "on");
+    }
+}
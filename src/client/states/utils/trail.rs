@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::common::models::{GameDto, Vec2};
+
+/// Ball positions of the last few received game updates, oldest first,
+/// `length` is the number of positions drawn behind the current one
+pub struct BallTrail {
+    length: usize,
+    history: VecDeque<Vec<Vec2>>,
+}
+
+impl BallTrail {
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            history: VecDeque::with_capacity(length + 1),
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn push(&mut self, game: &GameDto) {
+        if self.length == 0 {
+            return;
+        }
+        let positions: Vec<Vec2> = game.balls().map(|ball| ball.position.clone()).collect();
+        // a ball was added or scored, the old positions belong to other balls
+        if self
+            .history
+            .back()
+            .is_some_and(|last| last.len() != positions.len())
+        {
+            self.history.clear();
+        }
+        self.history.push_back(positions);
+        while self.history.len() > self.length + 1 {
+            self.history.pop_front();
+        }
+    }
+}
+
+/// Terminal cell of a board position, `None` outside the game area
+pub fn to_cell(position: &Vec2, game_area: Rect, scale_x: f32, scale_y: f32) -> Option<Position> {
+    if position.x < 0.0 || position.y < 0.0 {
+        return None;
+    }
+    let cell = Position::new(
+        game_area.x.saturating_add((position.x * scale_x) as u16),
+        game_area.y.saturating_add((position.y * scale_y) as u16),
+    );
+    game_area.contains(cell).then_some(cell)
+}
+
+/// Cells of the trail with their age, 1 being the newest, the current positions are left to the balls
+pub fn trail_cells(
+    history: &VecDeque<Vec<Vec2>>,
+    game_area: Rect,
+    scale_x: f32,
+    scale_y: f32,
+) -> Vec<(Position, usize)> {
+    history
+        .iter()
+        .rev()
+        .enumerate()
+        .skip(1)
+        .flat_map(|(age, positions)| {
+            positions
+                .iter()
+                .filter_map(move |position| to_cell(position, game_area, scale_x, scale_y))
+                .map(move |cell| (cell, age))
+        })
+        .collect()
+}
+
+pub fn render_ball_trail(
+    trail: &BallTrail,
+    frame: &mut Frame,
+    game_area: &Rect,
+    scale_x: f32,
+    scale_y: f32,
+) {
+    for (cell, age) in trail_cells(&trail.history, *game_area, scale_x, scale_y) {
+        // the older half of the trail fades out
+        let color = if age * 2 <= trail.length {
+            Color::Gray
+        } else {
+            Color::DarkGray
+        };
+        frame.render_widget(
+            Paragraph::new("·").style(Style::default().fg(color)),
+            Rect::new(cell.x, cell.y, 1, 1),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trail_cells() {
+        let game_area = Rect::new(2, 1, 10, 10);
+        let history: VecDeque<Vec<Vec2>> = [
+            Vec2 { x: -0.5, y: 3.0 }, // left the board through a goal
+            Vec2 { x: 1.0, y: 2.0 },
+            Vec2 { x: 2.0, y: 3.5 },
+            Vec2 { x: 3.0, y: 5.0 }, // current position, drawn by the ball itself
+        ]
+        .into_iter()
+        .map(|position| vec![position])
+        .collect();
+
+        assert_eq!(
+            trail_cells(&history, game_area, 1.0, 1.0),
+            vec![(Position::new(4, 4), 1), (Position::new(3, 3), 2)]
+        );
+
+        // scaled past the right edge
+        assert_eq!(
+            trail_cells(&history, game_area, 5.0, 1.0),
+            vec![(Position::new(7, 3), 2)]
+        );
+    }
+}
@@ -2,17 +2,23 @@ use arboard::Clipboard;
 use crossterm::event::KeyCode;
 
 use super::input::Input;
+use super::key_binding_editor::KeyBindingEditor;
 use super::slider::Slider;
+use super::toggle::Toggle;
 
 pub enum Widget {
     Slider(Slider),
     Input(Input),
+    Toggle(Toggle),
+    KeyBindings(Box<KeyBindingEditor>), // boxed, the bindings dwarf the other widgets
 }
 
 pub fn get_widget_text(widget: &Widget) -> String {
     match widget {
         Widget::Input(input) => input.input.clone(),
         Widget::Slider(slider) => slider.clone().get_text(),
+        Widget::Toggle(toggle) => toggle.clone().get_text(),
+        Widget::KeyBindings(editor) => editor.clone().get_text(),
     }
 }
 
@@ -30,6 +36,30 @@ impl WidgetTrait for Slider {
     }
 }
 
+impl WidgetTrait for Toggle {
+    fn handle_key_event(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => self.toggle(),
+            _ => (),
+        }
+    }
+}
+
+impl WidgetTrait for KeyBindingEditor {
+    fn handle_key_event(&mut self, key: KeyCode) {
+        if self.capturing {
+            self.capture(key);
+            return;
+        }
+        match key {
+            KeyCode::Left => self.previous(),
+            KeyCode::Right => self.next(),
+            KeyCode::Enter => self.start_capture(),
+            _ => (),
+        }
+    }
+}
+
 impl WidgetTrait for Input {
     fn handle_key_event(&mut self, key: KeyCode) {
         match key {
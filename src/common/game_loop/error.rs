@@ -0,0 +1,66 @@
+use std::net::SocketAddr;
+
+use log::Level;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::common::models::{ClientInputType, GameState};
+
+/// Why an input received over UDP was not applied
+#[derive(Error, Debug, PartialEq)]
+pub enum GameLoopError {
+    #[error("protocol version {received}, expected {expected}")]
+    ProtocolMismatch { received: u16, expected: u16 },
+    #[error("invalid game_id {game_id:?} or player_id {player_id:?}")]
+    InvalidId { game_id: String, player_id: String },
+    #[error("game {0} not found")]
+    GameNotFound(Uuid),
+    #[error("player {player_id} not found in game {game_id}")]
+    PlayerNotFound { game_id: Uuid, player_id: Uuid },
+    #[error("game {game_id}: {action:?} is not allowed while {state:?}")]
+    InvalidState {
+        game_id: Uuid,
+        action: ClientInputType,
+        state: GameState,
+    },
+    #[error("game {game_id}: join as player {player_id} without a valid reconnect token")]
+    InvalidReconnectToken { game_id: Uuid, player_id: Uuid },
+    #[error(
+        "game {game_id}: input for player {player_id} from {addr}, neither their address nor token"
+    )]
+    NotPlayerAddress {
+        game_id: Uuid,
+        player_id: Uuid,
+        addr: SocketAddr,
+    },
+    #[error("game {game_id}: stale input {seq} of player {player_id}, already at {last_seq}")]
+    StaleInput {
+        game_id: Uuid,
+        player_id: Uuid,
+        seq: u64,
+        last_seq: u64,
+    },
+    #[error("game {game_id}: only the host may do that, {player_id} is not the host")]
+    NotHost { game_id: Uuid, player_id: Uuid },
+    #[error("unsupported action {0:?}")]
+    UnsupportedAction(ClientInputType),
+}
+
+impl GameLoopError {
+    /// How loudly the caller should log it, reordered packets and
+    /// inputs racing a state change are part of normal play
+    pub fn level(&self) -> Level {
+        match self {
+            GameLoopError::InvalidState { .. }
+            | GameLoopError::StaleInput { .. }
+            | GameLoopError::NotHost { .. } => Level::Debug,
+            GameLoopError::ProtocolMismatch { .. }
+            | GameLoopError::InvalidId { .. }
+            | GameLoopError::InvalidReconnectToken { .. }
+            | GameLoopError::NotPlayerAddress { .. } => Level::Warn,
+            GameLoopError::GameNotFound(_)
+            | GameLoopError::PlayerNotFound { .. }
+            | GameLoopError::UnsupportedAction(_) => Level::Error,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::VecDeque;
+
+use crate::common::models::ClientInputWithAddr;
+
+pub const DEFAULT_MAX_QUEUED_INPUTS: usize = 10_000;
+
+/// Inputs waiting for the next tick, capped so a flood of packets cannot exhaust memory.
+/// The oldest inputs give way when it is full, newer moves supersede them anyway
+pub struct InputQueue {
+    inputs: VecDeque<ClientInputWithAddr>,
+    capacity: usize,
+    dropped: u64, // inputs pushed out by a full queue since the server started
+}
+
+impl Default for InputQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_QUEUED_INPUTS)
+    }
+}
+
+impl InputQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inputs: VecDeque::new(),
+            capacity: capacity.max(1),
+            dropped: 0,
+        }
+    }
+
+    /// Queues `input`, false when the oldest one had to be dropped to make room
+    pub fn push(&mut self, input: ClientInputWithAddr) -> bool {
+        let dropped = self.inputs.len() >= self.capacity;
+        if dropped {
+            self.inputs.pop_front();
+            self.dropped += 1;
+        }
+        self.inputs.push_back(input);
+        !dropped
+    }
+
+    pub fn pop(&mut self) -> Option<ClientInputWithAddr> {
+        self.inputs.pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::models::{ClientInput, ClientInputType};
+
+    fn input(player_id: &str) -> ClientInputWithAddr {
+        ClientInputWithAddr {
+            addr: "127.0.0.1:34254".parse().unwrap(),
+            input: ClientInput::new(
+                "game".to_string(),
+                player_id.to_string(),
+                ClientInputType::Ping,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest() {
+        let mut queue = InputQueue::new(2);
+
+        assert!(queue.push(input("first")));
+        assert!(queue.push(input("second")));
+        assert!(!queue.push(input("third")));
+        assert!(!queue.push(input("fourth")));
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 2);
+        assert_eq!(queue.pop().unwrap().input.player_id, "third");
+        assert_eq!(queue.pop().unwrap().input.player_id, "fourth");
+        assert!(queue.is_empty());
+
+        // room again once the tick drained it, the count is kept
+        assert!(queue.push(input("fifth")));
+        assert_eq!(queue.dropped(), 2);
+    }
+}
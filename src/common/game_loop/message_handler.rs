@@ -1,31 +1,61 @@
 use std::{net::SocketAddr, sync::Arc};
 
-use log::{debug, error, info};
+use log::{debug, info};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use super::GameLoopError;
 use crate::common::{
-    models::{Ball, ClientInput, ClientInputType, GameState},
+    models::{ClientInput, ClientInputType, GameState, PROTOCOL_VERSION},
     GameRooms,
 };
 
-fn validate_game_state(action: &ClientInputType, game_state: &GameState) -> bool {
+/// Whether `action` makes sense in `game_state`, paddles also move in the lobby with `warmup`
+fn validate_game_state(action: &ClientInputType, game_state: &GameState, warmup: bool) -> bool {
     match action {
-        ClientInputType::MovePaddle(_) => *game_state == GameState::Active,
+        ClientInputType::MovePaddle(_) | ClientInputType::MovePaddleBy(..) => {
+            *game_state == GameState::Active
+                || (warmup && *game_state == GameState::WaitingForPlayers)
+        }
         ClientInputType::JoinGame => *game_state == GameState::WaitingForPlayers,
+        ClientInputType::ToggleBotsReady => {
+            matches!(
+                game_state,
+                GameState::WaitingForPlayers | GameState::Starting
+            )
+        }
         _ => true, // No validation needed for other actions
     }
 }
 
-pub async fn process_input(input: ClientInput, lobbies: Arc<Mutex<GameRooms>>, addr: SocketAddr) {
+/// Applies an input received from `addr`, the error tells the caller why it was not
+pub async fn process_input(
+    input: ClientInput,
+    lobbies: Arc<Mutex<GameRooms>>,
+    addr: SocketAddr,
+) -> Result<(), GameLoopError> {
+    let mut game_rooms = lobbies.lock().await;
+
+    // fields of another version would be read as something else than they mean
+    if input.protocol_version != PROTOCOL_VERSION {
+        game_rooms.rejected_inputs += 1;
+        return Err(GameLoopError::ProtocolMismatch {
+            received: input.protocol_version,
+            expected: PROTOCOL_VERSION,
+        });
+    }
+
     let (game_id, player_id) = match (
         Uuid::parse_str(&input.game_id),
         Uuid::parse_str(&input.player_id),
     ) {
         (Ok(game_id), Ok(player_id)) => (game_id, player_id),
         _ => {
-            info!("Invalid UUIDs of game_id or player_id");
-            return;
+            game_rooms.rejected_inputs += 1;
+            return Err(GameLoopError::InvalidId {
+                game_id: input.game_id,
+                player_id: input.player_id,
+            });
         }
     };
 
@@ -34,38 +64,77 @@ pub async fn process_input(input: ClientInput, lobbies: Arc<Mutex<GameRooms>>, a
         player_id, game_id
     );
 
-    let mut game_rooms = lobbies.lock().await;
+    let game = game_rooms
+        .lobbies
+        .get_mut(&game_id)
+        .ok_or(GameLoopError::GameNotFound(game_id))?;
 
-    let game = match game_rooms.lobbies.get_mut(&game_id) {
-        Some(game) => game,
-        None => {
-            error!("Game {} not found", game_id);
-            return;
-        }
-    };
+    if !validate_game_state(&input.action, &game.state, game.settings.warmup) {
+        return Err(GameLoopError::InvalidState {
+            game_id,
+            action: input.action,
+            state: game.state.clone(),
+        });
+    }
 
-    if !validate_game_state(&input.action, &game.state) {
-        debug!("Invalid action for game state");
-        return;
+    // joining binds the player to this address, only its owner may do that
+    if input.action == ClientInputType::JoinGame
+        && game
+            .get_player(&player_id)
+            .is_some_and(|player| !player.accepts_reconnect_token(input.reconnect_token.as_deref()))
+    {
+        game_rooms.rejected_inputs += 1;
+        return Err(GameLoopError::InvalidReconnectToken { game_id, player_id });
     }
 
-    let player = match game.get_player_mut(&player_id) {
-        Some(player) => player,
-        None => {
-            error!("Player {} not found", player_id);
-            return;
+    // anything else comes from the address the player joined from, or carries their token
+    if input.action != ClientInputType::JoinGame
+        && game.get_player(&player_id).is_some_and(|player| {
+            player.addr != Some(addr)
+                && !player.accepts_reconnect_token(input.reconnect_token.as_deref())
+        })
+    {
+        game_rooms.rejected_inputs += 1;
+        return Err(GameLoopError::NotPlayerAddress {
+            game_id,
+            player_id,
+            addr,
+        });
+    }
+
+    let player = game
+        .get_player_mut(&player_id)
+        .ok_or(GameLoopError::PlayerNotFound { game_id, player_id })?;
+
+    // UDP may deliver an older input after a newer one, a late join must not let those back in,
+    // only a join from a new address is a new client counting from scratch
+    if input.action == ClientInputType::JoinGame {
+        if player.addr == Some(addr) {
+            player.last_input_seq = player.last_input_seq.max(input.seq);
+        } else {
+            player.last_input_seq = input.seq;
         }
-    };
+    } else if input.seq < player.last_input_seq {
+        return Err(GameLoopError::StaleInput {
+            game_id,
+            player_id,
+            seq: input.seq,
+            last_seq: player.last_input_seq,
+        });
+    } else {
+        player.last_input_seq = input.seq;
+    }
 
     match input.action {
         ClientInputType::JoinGame => {
             player.addr = Some(addr);
             player.ping_timestamp = Some(chrono::Utc::now());
-            info!("game {}: {} ({}) joined", player.name, game_id, player_id);
+            info!("game {}: {} ({}) joined", game_id, player.name, player_id);
         }
         ClientInputType::PlayerReady => {
             player.is_ready = !player.is_ready;
-            if player.is_ready {
+            let is_ready = player.is_ready;
+            if is_ready {
                 info!("game {}: {} ({}) is ready", player.name, game_id, player_id);
             } else {
                 info!(
@@ -75,8 +144,22 @@ pub async fn process_input(input: ClientInput, lobbies: Arc<Mutex<GameRooms>>, a
             }
 
             if game.start_game().is_ok() {
-                info!("game {}: started", game_id);
-                game.ball = Some(Ball::new());
+                info!("game {}: starting", game_id);
+            } else if !is_ready && game.cancel_start().is_ok() {
+                info!("game {}: countdown cancelled", game_id);
+            }
+        }
+        ClientInputType::ToggleBotsReady => {
+            if game.host() != Some(player_id) {
+                return Err(GameLoopError::NotHost { game_id, player_id });
+            }
+            game.toggle_bots_ready();
+            info!("game {}: host toggled the bots ready", game_id);
+
+            if game.start_game().is_ok() {
+                info!("game {}: starting", game_id);
+            } else if game.cancel_start().is_ok() {
+                info!("game {}: countdown cancelled", game_id);
             }
         }
         ClientInputType::PauseGame => {
@@ -85,21 +168,727 @@ pub async fn process_input(input: ClientInput, lobbies: Arc<Mutex<GameRooms>>, a
             }
         }
         ClientInputType::MovePaddle(direction) => {
-            player.move_paddle(direction);
+            // only the latest input counts, the paddle is moved in the game tick
+            player.pending_direction = Some(direction);
+            player.pending_boost = None;
+        }
+        ClientInputType::MovePaddleBy(direction, boost) => {
+            player.pending_direction = Some(direction);
+            player.pending_boost = Some(boost);
         }
         ClientInputType::Disconnect => {
             info!(
                 "game {}: {} ({}) disconnected",
-                player.name, game_id, player_id
+                game_id, player.name, player_id
             );
-            game.remove_player(player_id);
+            game.forfeit(player_id);
         }
         ClientInputType::Ping => {
             debug!("Pong from player {}", player_id);
             player.ping_timestamp = Some(chrono::Utc::now());
         }
-        _ => {
-            error!("Unhandled action: {:?}", input.action);
+        action => return Err(GameLoopError::UnsupportedAction(action)),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::models::{Direction, GameConfig};
+    use crate::common::Player;
+
+    // creates a game with a single human player in the given state
+    async fn setup(state: GameState) -> (Arc<Mutex<GameRooms>>, Uuid, Player) {
+        let lobbies = Arc::new(Mutex::new(GameRooms::new()));
+
+        let (game_id, player) = {
+            let mut rooms = lobbies.lock().await;
+            let game_id = rooms.create_game();
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            let player = Player::new("Player 1".to_string(), false);
+            game.add_player(player.clone()).unwrap();
+            game.state = state;
+            (game_id, player)
+        };
+
+        (lobbies, game_id, player)
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_removes_player() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+
+        let input = ClientInput {
+            game_id: game_id.to_string(),
+            player_id: player.id.to_string(),
+            action: ClientInputType::Disconnect,
+            reconnect_token: None,
+            protocol_version: PROTOCOL_VERSION,
+            seq: 0,
+        };
+        process_input(input, lobbies.clone(), addr).await.unwrap();
+
+        let rooms = lobbies.lock().await;
+        assert!(rooms.lobbies[&game_id].players.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_other_protocol_version() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+
+        for protocol_version in [0, PROTOCOL_VERSION + 1] {
+            let mut input = ClientInput::new(
+                game_id.to_string(),
+                player.id.to_string(),
+                ClientInputType::Disconnect,
+            );
+            input.protocol_version = protocol_version;
+            assert!(matches!(
+                process_input(input, lobbies.clone(), addr).await,
+                Err(GameLoopError::ProtocolMismatch { .. })
+            ));
+        }
+
+        let rooms = lobbies.lock().await;
+        assert_eq!(rooms.rejected_inputs, 2);
+        assert!(rooms.lobbies[&game_id].players.contains_key(&player.id));
+    }
+
+    #[tokio::test]
+    async fn test_join_requires_reconnect_token() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let hijacker: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        let token = lobbies
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .get_player_mut(&player.id)
+            .unwrap()
+            .issue_reconnect_token();
+        let join = || {
+            ClientInput::new(
+                game_id.to_string(),
+                player.id.to_string(),
+                ClientInputType::JoinGame,
+            )
+        };
+
+        process_input(
+            join().with_reconnect_token(token.clone()),
+            lobbies.clone(),
+            addr,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            lobbies.lock().await.lobbies[&game_id].players[&player.id].addr,
+            Some(addr)
+        );
+
+        // someone who only knows the player id cannot take over the slot
+        assert!(matches!(
+            process_input(join(), lobbies.clone(), hijacker).await,
+            Err(GameLoopError::InvalidReconnectToken { .. })
+        ));
+        assert!(matches!(
+            process_input(
+                join().with_reconnect_token("guessed".to_string()),
+                lobbies.clone(),
+                hijacker,
+            )
+            .await,
+            Err(GameLoopError::InvalidReconnectToken { .. })
+        ));
+        {
+            let rooms = lobbies.lock().await;
+            assert_eq!(rooms.lobbies[&game_id].players[&player.id].addr, Some(addr));
+            assert_eq!(rooms.rejected_inputs, 2);
+        }
+
+        // the owner reconnects from a new address with its token
+        let new_addr: SocketAddr = "127.0.0.1:4001".parse().unwrap();
+        process_input(
+            join().with_reconnect_token(token),
+            lobbies.clone(),
+            new_addr,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            lobbies.lock().await.lobbies[&game_id].players[&player.id].addr,
+            Some(new_addr)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_join_records_addr_of_the_joining_player() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        let other = Player::new("Player 2".to_string(), false);
+        lobbies
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(other.clone())
+            .unwrap();
+        let join = |player_id: Uuid| {
+            ClientInput::new(
+                game_id.to_string(),
+                player_id.to_string(),
+                ClientInputType::JoinGame,
+            )
+        };
+
+        process_input(join(player.id), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        {
+            let rooms = lobbies.lock().await;
+            let game = &rooms.lobbies[&game_id];
+            assert_eq!(game.players[&player.id].addr, Some(addr));
+            assert!(game.players[&player.id].ping_timestamp.is_some());
+            // the other player is only reachable once they join themselves
+            assert_eq!(game.players[&other.id].addr, None);
+            assert_eq!(game.players[&other.id].ping_timestamp, None);
+        }
+
+        // a player id that is not in the game binds nothing
+        let stranger: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+        assert!(matches!(
+            process_input(join(Uuid::new_v4()), lobbies.clone(), stranger).await,
+            Err(GameLoopError::PlayerNotFound { .. })
+        ));
+        let rooms = lobbies.lock().await;
+        let game = &rooms.lobbies[&game_id];
+        assert!(game
+            .players
+            .values()
+            .all(|player| player.addr != Some(stranger)));
+        assert_eq!(game.players[&player.id].addr, Some(addr));
+    }
+
+    #[tokio::test]
+    async fn test_join_from_new_addr_without_token() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let new_addr: SocketAddr = "127.0.0.1:4001".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        let join = ClientInput::new(
+            game_id.to_string(),
+            player.id.to_string(),
+            ClientInputType::JoinGame,
+        );
+
+        // players without a token, e.g. added before tokens were issued, rebind freely
+        process_input(join.clone(), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        process_input(join, lobbies.clone(), new_addr)
+            .await
+            .unwrap();
+        assert_eq!(
+            lobbies.lock().await.lobbies[&game_id].players[&player.id].addr,
+            Some(new_addr)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_input_in_lobby_only_with_warmup() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        let move_paddle = || {
+            ClientInput::new(
+                game_id.to_string(),
+                player.id.to_string(),
+                ClientInputType::MovePaddle(Direction::Positive),
+            )
+        };
+        let pending = || async {
+            lobbies.lock().await.lobbies[&game_id].players[&player.id]
+                .pending_direction
+                .clone()
+        };
+
+        assert!(matches!(
+            process_input(move_paddle(), lobbies.clone(), addr).await,
+            Err(GameLoopError::InvalidState { .. })
+        ));
+        assert_eq!(pending().await, None);
+
+        lobbies
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .settings
+            .warmup = true;
+        process_input(move_paddle(), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        assert_eq!(pending().await, Some(Direction::Positive));
+    }
+
+    #[tokio::test]
+    async fn test_move_inputs_clamped_per_tick() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::Active).await;
+
+        // a burst of inputs within a single tick
+        for _ in 0..20 {
+            let input = ClientInput {
+                game_id: game_id.to_string(),
+                player_id: player.id.to_string(),
+                action: ClientInputType::MovePaddle(Direction::Positive),
+                reconnect_token: None,
+                protocol_version: PROTOCOL_VERSION,
+                seq: 0,
+            };
+            process_input(input, lobbies.clone(), addr).await.unwrap();
+        }
+
+        let mut rooms = lobbies.lock().await;
+        let game = rooms.lobbies.get_mut(&game_id).unwrap();
+        // nothing moves until the tick
+        assert_eq!(
+            game.players[&player.id].paddle_position,
+            player.paddle_position
+        );
+
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(
+            game.players[&player.id].paddle_position,
+            player.paddle_position + player.paddle_delta
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inputs_only_from_the_player() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let hijacker: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::Active).await;
+        let token = {
+            let mut rooms = lobbies.lock().await;
+            let player = rooms
+                .find_lobby_mut(game_id)
+                .unwrap()
+                .get_player_mut(&player.id)
+                .unwrap();
+            player.addr = Some(addr);
+            player.issue_reconnect_token()
+        };
+        let input = |action: ClientInputType| {
+            ClientInput::new(game_id.to_string(), player.id.to_string(), action)
+        };
+
+        // the player ids are public, that is not enough to act for someone
+        for action in [
+            ClientInputType::MovePaddle(Direction::Positive),
+            ClientInputType::MovePaddleBy(Direction::Positive, 2.0),
+            ClientInputType::PlayerReady,
+            ClientInputType::PauseGame,
+            ClientInputType::Disconnect,
+        ] {
+            assert!(matches!(
+                process_input(input(action), lobbies.clone(), hijacker).await,
+                Err(GameLoopError::NotPlayerAddress { .. })
+            ));
+        }
+        {
+            let rooms = lobbies.lock().await;
+            let game = &rooms.lobbies[&game_id];
+            assert_eq!(game.state, GameState::Active);
+            assert!(game.players[&player.id].pending_direction.is_none());
+            assert!(!game.players[&player.id].is_ready);
+            assert_eq!(rooms.rejected_inputs, 5);
+        }
+
+        // the player's own address, or their token from anywhere
+        let move_paddle = input(ClientInputType::MovePaddle(Direction::Positive));
+        process_input(move_paddle.clone(), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        process_input(
+            move_paddle.with_reconnect_token(token),
+            lobbies.clone(),
+            hijacker,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_late_join_keeps_seq_watermark() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        lobbies
+            .lock()
+            .await
+            .lobbies
+            .get_mut(&game_id)
+            .unwrap()
+            .settings
+            .warmup = true;
+        let input = |action: ClientInputType, seq: u64| ClientInput {
+            seq,
+            ..ClientInput::new(game_id.to_string(), player.id.to_string(), action)
+        };
+        let last_seq =
+            || async { lobbies.lock().await.lobbies[&game_id].players[&player.id].last_input_seq };
+
+        process_input(input(ClientInputType::JoinGame, 1), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        process_input(
+            input(ClientInputType::MovePaddle(Direction::Positive), 5),
+            lobbies.clone(),
+            addr,
+        )
+        .await
+        .unwrap();
+
+        // a repeated join overtaken by newer inputs lets no older ones back in
+        process_input(input(ClientInputType::JoinGame, 2), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        assert_eq!(last_seq().await, 5);
+        assert!(matches!(
+            process_input(
+                input(ClientInputType::MovePaddle(Direction::Negative), 3),
+                lobbies.clone(),
+                addr
+            )
+            .await,
+            Err(GameLoopError::StaleInput { .. })
+        ));
+
+        // a client joining from a new address counts from scratch
+        let new_addr: SocketAddr = "127.0.0.1:4001".parse().unwrap();
+        process_input(
+            input(ClientInputType::JoinGame, 1),
+            lobbies.clone(),
+            new_addr,
+        )
+        .await
+        .unwrap();
+        assert_eq!(last_seq().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_move_input_ignored() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::Active).await;
+        let pending = || async {
+            lobbies.lock().await.lobbies[&game_id].players[&player.id]
+                .pending_direction
+                .clone()
+        };
+        let move_paddle = |direction: Direction, seq: u64| ClientInput {
+            seq,
+            ..ClientInput::new(
+                game_id.to_string(),
+                player.id.to_string(),
+                ClientInputType::MovePaddle(direction),
+            )
+        };
+
+        process_input(move_paddle(Direction::Positive, 2), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        assert_eq!(pending().await, Some(Direction::Positive));
+
+        // sent before the previous one but arrived after it
+        assert!(matches!(
+            process_input(move_paddle(Direction::Negative, 1), lobbies.clone(), addr).await,
+            Err(GameLoopError::StaleInput {
+                seq: 1,
+                last_seq: 2,
+                ..
+            })
+        ));
+        assert_eq!(pending().await, Some(Direction::Positive));
+
+        process_input(move_paddle(Direction::Negative, 3), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        assert_eq!(pending().await, Some(Direction::Negative));
+        assert_eq!(
+            lobbies.lock().await.lobbies[&game_id].players[&player.id].last_input_seq,
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_inputs_stay_on_board() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::Active).await;
+        let board_size = {
+            let mut rooms = lobbies.lock().await;
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            game.balls.clear(); // no goals ending the game midway
+            game.settings.board_size
+        };
+        let min = player.paddle_width / 2.0;
+        let max = board_size - player.paddle_width / 2.0;
+
+        let actions = [
+            (
+                Direction::Positive,
+                ClientInputType::MovePaddle(Direction::Positive),
+            ),
+            (
+                Direction::Negative,
+                ClientInputType::MovePaddle(Direction::Negative),
+            ),
+            (
+                Direction::Positive,
+                ClientInputType::MovePaddleBy(Direction::Positive, f32::MAX),
+            ),
+            (
+                Direction::Negative,
+                ClientInputType::MovePaddleBy(Direction::Negative, f32::INFINITY),
+            ),
+            (
+                Direction::Positive,
+                ClientInputType::MovePaddleBy(Direction::Positive, f32::NAN),
+            ),
+        ];
+        for (direction, action) in actions {
+            for _ in 0..100 {
+                let input = ClientInput {
+                    game_id: game_id.to_string(),
+                    player_id: player.id.to_string(),
+                    action: action.clone(),
+                    reconnect_token: None,
+                    protocol_version: PROTOCOL_VERSION,
+                    seq: 0,
+                };
+                process_input(input, lobbies.clone(), addr).await.unwrap();
+
+                let mut rooms = lobbies.lock().await;
+                let game = rooms.lobbies.get_mut(&game_id).unwrap();
+                game.game_tick(&GameConfig::default(), 1.0);
+                let position = game.players[&player.id].paddle_position;
+                assert!(
+                    (min..=max).contains(&position),
+                    "{:?} moved the paddle to {}",
+                    action,
+                    position
+                );
+            }
+
+            // enough inputs pin the paddle to the edge they push towards
+            let position =
+                lobbies.lock().await.lobbies[&game_id].players[&player.id].paddle_position;
+            let edge = match direction {
+                Direction::Positive => max,
+                Direction::Negative => min,
+            };
+            assert_eq!(position, edge);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unready_cancels_countdown() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        {
+            let mut rooms = lobbies.lock().await;
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            let mut other = Player::new("Player 2".to_string(), false);
+            other.is_ready = true;
+            game.add_player(other).unwrap();
         }
+
+        let ready = || ClientInput {
+            game_id: game_id.to_string(),
+            player_id: player.id.to_string(),
+            action: ClientInputType::PlayerReady,
+            reconnect_token: None,
+            protocol_version: PROTOCOL_VERSION,
+            seq: 0,
+        };
+
+        process_input(ready(), lobbies.clone(), addr).await.unwrap();
+        assert_eq!(
+            lobbies.lock().await.lobbies[&game_id].state,
+            GameState::Starting
+        );
+
+        process_input(ready(), lobbies.clone(), addr).await.unwrap();
+        let rooms = lobbies.lock().await;
+        assert_eq!(rooms.lobbies[&game_id].state, GameState::WaitingForPlayers);
+        assert!(rooms.lobbies[&game_id].start_countdown_until.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_only_host_readies_bots() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, host) = setup(GameState::WaitingForPlayers).await;
+        let guest = {
+            let mut rooms = lobbies.lock().await;
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            game.settings.bots_auto_ready = false;
+            game.add_player(Player::new("bot".to_string(), true))
+                .unwrap();
+            let mut guest = Player::new("Player 2".to_string(), false);
+            guest.joined_at = host.joined_at + chrono::Duration::seconds(1);
+            game.add_player(guest.clone()).unwrap();
+            guest
+        };
+        let bots_ready = |rooms: &GameRooms| {
+            rooms.lobbies[&game_id]
+                .players
+                .values()
+                .filter(|player| player.is_ai)
+                .all(|player| player.is_ready)
+        };
+        let toggle = |player: &Player| {
+            ClientInput::new(
+                game_id.to_string(),
+                player.id.to_string(),
+                ClientInputType::ToggleBotsReady,
+            )
+        };
+
+        assert!(matches!(
+            process_input(toggle(&guest), lobbies.clone(), addr).await,
+            Err(GameLoopError::NotHost { .. })
+        ));
+        assert!(!bots_ready(&*lobbies.lock().await));
+
+        process_input(toggle(&host), lobbies.clone(), addr)
+            .await
+            .unwrap();
+        assert!(bots_ready(&*lobbies.lock().await));
+    }
+
+    #[tokio::test]
+    async fn test_errors_name_the_problem() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        let input = |game_id: String, player_id: String, action: ClientInputType| {
+            ClientInput::new(game_id, player_id, action)
+        };
+
+        let missing_game = Uuid::new_v4();
+        assert_eq!(
+            process_input(
+                input(
+                    missing_game.to_string(),
+                    player.id.to_string(),
+                    ClientInputType::Ping
+                ),
+                lobbies.clone(),
+                addr
+            )
+            .await,
+            Err(GameLoopError::GameNotFound(missing_game))
+        );
+
+        let missing_player = Uuid::new_v4();
+        assert_eq!(
+            process_input(
+                input(
+                    game_id.to_string(),
+                    missing_player.to_string(),
+                    ClientInputType::Ping
+                ),
+                lobbies.clone(),
+                addr
+            )
+            .await,
+            Err(GameLoopError::PlayerNotFound {
+                game_id,
+                player_id: missing_player
+            })
+        );
+
+        assert_eq!(
+            process_input(
+                input(
+                    "bad".to_string(),
+                    player.id.to_string(),
+                    ClientInputType::Ping
+                ),
+                lobbies.clone(),
+                addr
+            )
+            .await,
+            Err(GameLoopError::InvalidId {
+                game_id: "bad".to_string(),
+                player_id: player.id.to_string()
+            })
+        );
+
+        assert_eq!(
+            process_input(
+                input(
+                    game_id.to_string(),
+                    player.id.to_string(),
+                    ClientInputType::MovePaddle(Direction::Positive)
+                ),
+                lobbies.clone(),
+                addr
+            )
+            .await,
+            Err(GameLoopError::InvalidState {
+                game_id,
+                action: ClientInputType::MovePaddle(Direction::Positive),
+                state: GameState::WaitingForPlayers
+            })
+        );
+
+        assert_eq!(
+            process_input(
+                input(
+                    game_id.to_string(),
+                    player.id.to_string(),
+                    ClientInputType::ResumeGame
+                ),
+                lobbies.clone(),
+                addr
+            )
+            .await,
+            Err(GameLoopError::UnsupportedAction(
+                ClientInputType::ResumeGame
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_ids_rejected() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let (lobbies, game_id, player) = setup(GameState::WaitingForPlayers).await;
+        let before = lobbies.lock().await.lobbies[&game_id].clone();
+
+        let inputs = [
+            ("not-a-uuid".to_string(), player.id.to_string()),
+            (game_id.to_string(), "".to_string()),
+            (game_id.to_string(), "\u{0}\u{ffff}".to_string()),
+            ("x".repeat(1000), "{}".to_string()),
+        ];
+        for (game, player) in inputs {
+            let input = ClientInput {
+                game_id: game,
+                player_id: player,
+                action: ClientInputType::PlayerReady,
+                reconnect_token: None,
+                protocol_version: PROTOCOL_VERSION,
+                seq: 0,
+            };
+            assert!(matches!(
+                process_input(input, lobbies.clone(), addr).await,
+                Err(GameLoopError::InvalidId { .. })
+            ));
+        }
+
+        let rooms = lobbies.lock().await;
+        assert_eq!(rooms.rejected_inputs, 4);
+        assert_eq!(rooms.lobbies[&game_id], before);
     }
 }
@@ -1,3 +1,7 @@
+mod error;
+mod input_queue;
 mod message_handler;
 
+pub use error::GameLoopError;
+pub use input_queue::{InputQueue, DEFAULT_MAX_QUEUED_INPUTS};
 pub use message_handler::process_input;
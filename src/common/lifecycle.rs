@@ -0,0 +1,127 @@
+use log::info;
+use uuid::Uuid;
+
+/// Target of the lifecycle lines, lets them be filtered from the rest of the log
+pub const LIFECYCLE_TARGET: &str = "quadropong::lifecycle";
+
+/// Milestones of a game worth following across the server log
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    Created,
+    PlayerJoined,
+    PlayerLeft,
+    Started,
+    Goal,
+    Finished,
+}
+
+impl GameEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameEvent::Created => "game_created",
+            GameEvent::PlayerJoined => "player_joined",
+            GameEvent::PlayerLeft => "player_left",
+            GameEvent::Started => "game_started",
+            GameEvent::Goal => "goal",
+            GameEvent::Finished => "game_finished",
+        }
+    }
+}
+
+/// One `key=value` line per event, the detail is quoted as it may contain spaces
+pub fn format_event(
+    event: GameEvent,
+    game_id: Uuid,
+    player_id: Option<Uuid>,
+    detail: &str,
+) -> String {
+    let player_id = player_id.map_or("-".to_string(), |id| id.to_string());
+    format!(
+        "event={} game_id={} player_id={} detail={:?}",
+        event.as_str(),
+        game_id,
+        player_id,
+        detail
+    )
+}
+
+pub fn log_event(event: GameEvent, game_id: Uuid, player_id: Option<Uuid>, detail: &str) {
+    info!(
+        target: LIFECYCLE_TARGET,
+        "{}",
+        format_event(event, game_id, player_id, detail)
+    );
+}
+
+/// Collects the lifecycle lines logged on the current thread, tests run on threads of their own
+#[cfg(test)]
+pub mod capture {
+    use std::cell::RefCell;
+
+    use log::{LevelFilter, Log, Metadata, Record};
+
+    use super::LIFECYCLE_TARGET;
+
+    thread_local! {
+        static CAPTURED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    struct CapturingLogger;
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.target() == LIFECYCLE_TARGET
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                CAPTURED.with(|captured| captured.borrow_mut().push(record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger;
+
+    /// Runs `f` and returns the lifecycle lines it logged
+    pub fn events(f: impl FnOnce()) -> Vec<String> {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(LevelFilter::Info);
+        }
+        CAPTURED.with(|captured| captured.borrow_mut().clear());
+        f();
+        CAPTURED.with(|captured| captured.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_event() {
+        let game_id = Uuid::nil();
+        assert_eq!(
+            format_event(GameEvent::Goal, game_id, Some(game_id), "scored on Top"),
+            format!(
+                "event=goal game_id={0} player_id={0} detail=\"scored on Top\"",
+                game_id
+            )
+        );
+        assert_eq!(
+            format_event(GameEvent::Created, game_id, None, ""),
+            format!(
+                "event=game_created game_id={} player_id=- detail=\"\"",
+                game_id
+            )
+        );
+    }
+
+    #[test]
+    fn test_log_event_captured() {
+        let events = capture::events(|| log_event(GameEvent::Started, Uuid::nil(), None, ""));
+        assert_eq!(events.len(), 1);
+        assert!(events[0].starts_with("event=game_started "));
+    }
+}
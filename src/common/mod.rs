@@ -1,6 +1,11 @@
 pub mod game_error;
 pub mod game_loop;
+pub mod lifecycle;
 pub mod models;
+pub mod settings_error;
 
 pub use game_error::GameError;
-pub use models::{Game, GameRooms, JoinGameRequest, Player, PlayerPosition};
+pub use models::{
+    Game, GameRooms, GameSettings, JoinGameRequest, JoinGameResponse, Player, PlayerPosition,
+};
+pub use settings_error::SettingsError;
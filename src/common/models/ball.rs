@@ -1,10 +1,21 @@
 use rand::seq::IndexedRandom;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::PlayerPosition;
+use super::game_settings::DEFAULT_BOARD_SIZE;
+
+/// Velocities are distances per tick at this rate, other tick rates scale the movement
+pub const BASE_TICK_RATE: f32 = 60.0;
+const SERVE_SPEED: f32 = 0.125; // until the first paddle hit sets the game's ball speed
+/// Distance past the board no tick can carry a ball, even a scoring one
+const OUT_OF_BOUNDS_MARGIN: f32 = 5.0;
 
-const GAME_SIZE: f32 = 10.0;
+/// Time passed expressed in ticks of `BASE_TICK_RATE`
+pub fn ticks_elapsed(elapsed: std::time::Duration) -> f32 {
+    elapsed.as_secs_f32() * BASE_TICK_RATE
+}
+use super::PlayerPosition;
 
 #[derive(Serialize, Clone, Debug, Deserialize, PartialEq)]
 pub struct Ball {
@@ -20,6 +31,43 @@ pub struct Vec2 {
     pub y: f32,
 }
 
+/// Extent of the board, the ball moves within `0..width` across and `0..height` down
+#[derive(Serialize, Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct BoardSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        Self::square(DEFAULT_BOARD_SIZE)
+    }
+}
+
+impl BoardSize {
+    pub fn square(size: f32) -> Self {
+        Self {
+            width: size,
+            height: size,
+        }
+    }
+
+    /// Length of the side a player at `position` defends, their paddle moves along it
+    pub fn side_length(&self, position: PlayerPosition) -> f32 {
+        match position {
+            PlayerPosition::Top | PlayerPosition::Bottom => self.width,
+            PlayerPosition::Left | PlayerPosition::Right => self.height,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        Vec2 {
+            x: self.width / 2.0,
+            y: self.height / 2.0,
+        }
+    }
+}
+
 impl Default for Ball {
     fn default() -> Self {
         Self::new()
@@ -28,64 +76,140 @@ impl Default for Ball {
 
 impl Ball {
     pub fn new() -> Self {
+        Self::centered(BoardSize::default())
+    }
+
+    pub fn centered(board: BoardSize) -> Self {
         Self {
-            position: Vec2 { x: 5.0, y: 5.0 },
-            velocity: Vec2 { x: 0.0, y: 0.125 },
+            position: board.center(),
+            velocity: Vec2 {
+                x: 0.0,
+                y: SERVE_SPEED,
+            },
             radius: 0.125,
             last_touched_by: None,
         }
     }
 
-    pub fn reset(&mut self, player_positions: Vec<PlayerPosition>) {
+    /// Puts the ball back in the center, aimed at one of the `player_positions` picked by `rng`,
+    /// so it doesn't bounce off an empty side's wall right away
+    pub fn reset(
+        &mut self,
+        player_positions: Vec<PlayerPosition>,
+        board: BoardSize,
+        rng: &mut impl Rng,
+    ) {
         self.last_touched_by = None;
-        self.position = Vec2 { x: 5.0, y: 5.0 };
+        self.position = board.center();
 
-        let initial_speed = 0.125;
+        match player_positions.choose(rng) {
+            Some(position) => self.serve_toward(*position),
+            None => {
+                self.velocity = Vec2 {
+                    x: 0.0,
+                    y: SERVE_SPEED,
+                }
+            }
+        }
+    }
 
-        self.velocity = match player_positions.choose(&mut rand::rng()) {
-            Some(PlayerPosition::Top) => Vec2 {
+    /// Aims the ball straight at the side of `position`
+    pub fn serve_toward(&mut self, position: PlayerPosition) {
+        self.velocity = match position {
+            PlayerPosition::Top => Vec2 {
                 x: 0.0,
-                y: initial_speed,
+                y: -SERVE_SPEED,
             },
-            Some(PlayerPosition::Bottom) => Vec2 {
+            PlayerPosition::Bottom => Vec2 {
                 x: 0.0,
-                y: -initial_speed,
+                y: SERVE_SPEED,
             },
-            Some(PlayerPosition::Left) => Vec2 {
-                x: initial_speed,
+            PlayerPosition::Left => Vec2 {
+                x: -SERVE_SPEED,
                 y: 0.0,
             },
-            Some(PlayerPosition::Right) => Vec2 {
-                x: -initial_speed,
+            PlayerPosition::Right => Vec2 {
+                x: SERVE_SPEED,
                 y: 0.0,
             },
-            None => Vec2 {
-                x: 0.0,
-                y: initial_speed,
-            },
         };
     }
 
-    pub fn update_position(&mut self) {
-        self.position.x += self.velocity.x;
-        self.position.y += self.velocity.y;
+    /// Adds a small perpendicular component to the velocity while keeping the speed
+    pub fn nudge(&mut self, rng: &mut impl Rng) {
+        let speed = (self.velocity.x.powi(2) + self.velocity.y.powi(2)).sqrt();
+        if speed == 0.0 {
+            return;
+        }
+
+        let magnitude = rng.random_range(0.1..0.3);
+        let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+
+        let x = self.velocity.x - self.velocity.y * magnitude * sign;
+        let y = self.velocity.y + self.velocity.x * magnitude * sign;
+        let length = (x.powi(2) + y.powi(2)).sqrt();
+
+        self.velocity.x = x / length * speed;
+        self.velocity.y = y / length * speed;
     }
 
-    pub fn is_goal(self) -> Option<PlayerPosition> {
-        if self.position.x - self.radius < 0.0 {
+    /// Where the ball will be after `dt`, the time passed in ticks of `BASE_TICK_RATE`
+    pub fn next_position(&self, dt: f32) -> Vec2 {
+        Vec2 {
+            x: self.position.x + self.velocity.x * dt,
+            y: self.position.y + self.velocity.y * dt,
+        }
+    }
+
+    pub fn update_position(&mut self, dt: f32) {
+        self.position = self.next_position(dt);
+    }
+
+    /// Returns the side the ball went through, counting only sides in `occupied`.
+    /// The whole ball has to be past the edge, a ball touching it is still in play
+    pub fn is_goal(&self, board: BoardSize, occupied: &[PlayerPosition]) -> Option<PlayerPosition> {
+        let goal = if self.position.x + self.radius < 0.0 {
             Some(PlayerPosition::Left)
-        } else if self.position.x + self.radius > 10.0 {
+        } else if self.position.x - self.radius > board.width {
             Some(PlayerPosition::Right)
-        } else if self.position.y - self.radius < 0.0 {
+        } else if self.position.y + self.radius < 0.0 {
             Some(PlayerPosition::Top)
-        } else if self.position.y + self.radius > 10.0 {
+        } else if self.position.y - self.radius > board.height {
             Some(PlayerPosition::Bottom)
         } else {
             None
-        }
+        };
+
+        goal.filter(|pos| occupied.contains(pos))
     }
 
-    pub fn calculate_wall_reflection(&mut self, pos: PlayerPosition) {
+    /// Whether the ball is level with the goal gap centered on `side`,
+    /// without a `goal_width` the whole side is the goal
+    pub fn in_goal_gap(
+        &self,
+        side: PlayerPosition,
+        board: BoardSize,
+        goal_width: Option<f32>,
+    ) -> bool {
+        let Some(goal_width) = goal_width else {
+            return true;
+        };
+        let along = match side {
+            PlayerPosition::Top | PlayerPosition::Bottom => self.position.x,
+            PlayerPosition::Left | PlayerPosition::Right => self.position.y,
+        };
+        (along - board.side_length(side) / 2.0).abs() <= goal_width / 2.0
+    }
+
+    /// Escaped the board further than any goal or bounce could take it, or lost its position to NaN
+    pub fn is_out_of_bounds(&self, board: BoardSize) -> bool {
+        let within = |value: f32, size: f32| {
+            (-OUT_OF_BOUNDS_MARGIN..=size + OUT_OF_BOUNDS_MARGIN).contains(&value)
+        };
+        !within(self.position.x, board.width) || !within(self.position.y, board.height)
+    }
+
+    pub fn calculate_wall_reflection(&mut self, pos: PlayerPosition, board: BoardSize) {
         match pos {
             PlayerPosition::Top => {
                 if self.position.y - self.radius < 0.0 {
@@ -94,8 +218,8 @@ impl Ball {
                 }
             }
             PlayerPosition::Bottom => {
-                if self.position.y + self.radius > GAME_SIZE {
-                    self.position.y = 10.0 - self.radius;
+                if self.position.y + self.radius > board.height {
+                    self.position.y = board.height - self.radius;
                     self.velocity.y *= -1.0;
                 }
             }
@@ -106,11 +230,265 @@ impl Ball {
                 }
             }
             PlayerPosition::Right => {
-                if self.position.x + self.radius > GAME_SIZE {
-                    self.position.x = 10.0 - self.radius;
+                if self.position.x + self.radius > board.width {
+                    self.position.x = board.width - self.radius;
                     self.velocity.x *= -1.0;
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_is_out_of_bounds() {
+        let board = BoardSize::square(10.0);
+        let ball_at = |x: f32, y: f32| Ball {
+            position: Vec2 { x, y },
+            ..Ball::centered(board)
+        };
+
+        assert!(!ball_at(5.0, 5.0).is_out_of_bounds(board));
+        // just scored, still close to the board
+        assert!(!ball_at(-1.0, 5.0).is_out_of_bounds(board));
+        assert!(ball_at(-50.0, 5.0).is_out_of_bounds(board));
+        assert!(ball_at(5.0, board.height + 50.0).is_out_of_bounds(board));
+        assert!(ball_at(f32::NAN, 5.0).is_out_of_bounds(board));
+    }
+
+    const ALL_SIDES: [PlayerPosition; 4] = [
+        PlayerPosition::Top,
+        PlayerPosition::Bottom,
+        PlayerPosition::Left,
+        PlayerPosition::Right,
+    ];
+
+    fn ball_at(x: f32, y: f32) -> Ball {
+        let mut ball = Ball::new();
+        ball.position = Vec2 { x, y };
+        ball
+    }
+
+    #[test]
+    fn test_is_goal_each_side() {
+        let board = BoardSize::default();
+        let past = Ball::new().radius + 0.01;
+
+        let exits = [
+            (ball_at(5.0, -past), PlayerPosition::Top),
+            (ball_at(5.0, board.height + past), PlayerPosition::Bottom),
+            (ball_at(-past, 5.0), PlayerPosition::Left),
+            (ball_at(board.width + past, 5.0), PlayerPosition::Right),
+        ];
+        for (ball, side) in exits {
+            assert_eq!(ball.is_goal(board, &ALL_SIDES), Some(side));
+        }
+    }
+
+    #[test]
+    fn test_is_goal_in_bounds() {
+        let board = BoardSize::default();
+        let radius = Ball::new().radius;
+
+        assert_eq!(ball_at(5.0, 5.0).is_goal(board, &ALL_SIDES), None);
+        // touching or half over the edge is not a goal yet
+        assert_eq!(ball_at(5.0, 0.0).is_goal(board, &ALL_SIDES), None);
+        assert_eq!(
+            ball_at(board.width + radius, 5.0).is_goal(board, &ALL_SIDES),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_goal_with_large_radius() {
+        let board = BoardSize::default();
+        let small = ball_at(-0.3, 5.0);
+        let mut large = ball_at(-0.3, 5.0);
+        large.radius = 0.5;
+
+        // the whole ball has to leave the board, a large one takes longer to
+        assert_eq!(small.is_goal(board, &ALL_SIDES), Some(PlayerPosition::Left));
+        assert_eq!(large.is_goal(board, &ALL_SIDES), None);
+        large.position.x = -0.51;
+        assert_eq!(large.is_goal(board, &ALL_SIDES), Some(PlayerPosition::Left));
+    }
+
+    #[test]
+    fn test_is_goal_scales_with_board_size() {
+        let ball = ball_at(10.6, 10.0);
+        assert_eq!(ball.is_goal(BoardSize::square(20.0), &ALL_SIDES), None);
+        assert_eq!(
+            ball.is_goal(BoardSize::default(), &ALL_SIDES),
+            Some(PlayerPosition::Right)
+        );
+
+        let ball = ball_at(10.0, 20.3);
+        assert_eq!(
+            ball.is_goal(BoardSize::square(20.0), &ALL_SIDES),
+            Some(PlayerPosition::Bottom)
+        );
+    }
+
+    #[test]
+    fn test_is_goal_ignores_empty_sides() {
+        let ball = ball_at(10.3, 5.0);
+        assert_eq!(
+            ball.is_goal(BoardSize::default(), &[PlayerPosition::Right]),
+            Some(PlayerPosition::Right)
+        );
+        assert_eq!(
+            ball.is_goal(BoardSize::default(), &[PlayerPosition::Top]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let board = BoardSize::square(20.0);
+        for occupied in [
+            vec![PlayerPosition::Top],
+            vec![PlayerPosition::Left, PlayerPosition::Bottom],
+            ALL_SIDES.to_vec(),
+            vec![],
+        ] {
+            for _ in 0..20 {
+                let mut ball = Ball::new();
+                ball.position = Vec2 { x: 1.0, y: 17.0 };
+                ball.velocity = Vec2 { x: 0.3, y: -0.2 };
+                ball.last_touched_by = Some(Uuid::new_v4());
+
+                ball.reset(occupied.clone(), board, &mut rand::rng());
+
+                assert_eq!(ball.position, Vec2 { x: 10.0, y: 10.0 });
+                assert!(ball.last_touched_by.is_none());
+                let speed = (ball.velocity.x.powi(2) + ball.velocity.y.powi(2)).sqrt();
+                assert!((speed - SERVE_SPEED).abs() < 1e-6);
+
+                if occupied.is_empty() {
+                    continue;
+                }
+                // nothing but walls would turn it back, it runs into an occupied side
+                while ball.is_goal(board, &ALL_SIDES).is_none() {
+                    ball.update_position(1.0);
+                }
+                let side = ball.is_goal(board, &ALL_SIDES).unwrap();
+                assert!(occupied.contains(&side), "{:?} not in {:?}", side, occupied);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serve_toward() {
+        let board = BoardSize::default();
+        for side in ALL_SIDES {
+            let mut ball = Ball::new();
+            ball.serve_toward(side);
+
+            // moving on, the served ball leaves through the side it was aimed at
+            while ball.is_goal(board, &ALL_SIDES).is_none() {
+                ball.update_position(1.0);
+            }
+            assert_eq!(ball.is_goal(board, &ALL_SIDES), Some(side));
+        }
+    }
+
+    #[test]
+    fn test_update_position_scales_with_time() {
+        let mut ball = Ball::new();
+        ball.velocity = Vec2 { x: 0.1, y: -0.2 };
+
+        // a 30Hz tick moves the ball as far as two 60Hz ticks
+        ball.update_position(ticks_elapsed(std::time::Duration::from_secs_f32(
+            1.0 / 30.0,
+        )));
+        assert!((ball.position.x - 5.2).abs() < 1e-4);
+        assert!((ball.position.y - 4.6).abs() < 1e-4);
+
+        ball.update_position(0.0);
+        assert!((ball.position.x - 5.2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_double_dt_doubles_displacement() {
+        let mut ball = Ball::new();
+        ball.velocity = Vec2 { x: 0.15, y: -0.05 };
+        let start = ball.position.clone();
+
+        let once = ball.next_position(1.0);
+        let twice = ball.next_position(2.0);
+        assert!(((twice.x - start.x) - 2.0 * (once.x - start.x)).abs() < 1e-5);
+        assert!(((twice.y - start.y) - 2.0 * (once.y - start.y)).abs() < 1e-5);
+
+        ball.update_position(2.0);
+        assert_eq!(ball.position, twice);
+    }
+
+    #[test]
+    fn test_nudge() {
+        let mut ball = Ball::new();
+        ball.velocity = Vec2 { x: 0.0, y: 0.125 };
+        ball.nudge(&mut StdRng::seed_from_u64(42));
+
+        assert_ne!(ball.velocity.x, 0.0);
+        let speed = (ball.velocity.x.powi(2) + ball.velocity.y.powi(2)).sqrt();
+        assert!((speed - 0.125).abs() < 1e-6);
+
+        let mut same_seed = Ball::new();
+        same_seed.velocity = Vec2 { x: 0.0, y: 0.125 };
+        same_seed.nudge(&mut StdRng::seed_from_u64(42));
+        assert_eq!(ball.velocity, same_seed.velocity);
+    }
+
+    #[test]
+    fn test_wall_reflection_scales_with_board_size() {
+        let mut ball = Ball::centered(BoardSize::square(20.0));
+        ball.position = Vec2 { x: 19.95, y: 10.0 };
+        ball.velocity = Vec2 { x: 0.125, y: 0.0 };
+        ball.calculate_wall_reflection(PlayerPosition::Right, BoardSize::square(20.0));
+        assert_eq!(ball.position.x, 20.0 - ball.radius);
+        assert_eq!(ball.velocity.x, -0.125);
+    }
+
+    #[test]
+    fn test_collisions_respect_width_and_height() {
+        let board = BoardSize {
+            width: 20.0,
+            height: 10.0,
+        };
+        let ball = Ball::centered(board);
+        assert_eq!(ball.position, Vec2 { x: 10.0, y: 5.0 });
+
+        // past the bottom of a wide board, but nowhere near its right side
+        let ball = ball_at(15.0, 10.3);
+        assert_eq!(
+            ball.is_goal(board, &ALL_SIDES),
+            Some(PlayerPosition::Bottom)
+        );
+        let ball = ball_at(15.0, 5.0);
+        assert_eq!(ball.is_goal(board, &ALL_SIDES), None);
+        let ball = ball_at(20.3, 5.0);
+        assert_eq!(ball.is_goal(board, &ALL_SIDES), Some(PlayerPosition::Right));
+
+        let mut ball = ball_at(19.95, 5.0);
+        ball.velocity = Vec2 { x: 0.125, y: 0.0 };
+        ball.calculate_wall_reflection(PlayerPosition::Right, board);
+        assert_eq!(ball.position.x, 20.0 - ball.radius);
+        let mut ball = ball_at(15.0, 9.95);
+        ball.velocity = Vec2 { x: 0.0, y: 0.125 };
+        ball.calculate_wall_reflection(PlayerPosition::Bottom, board);
+        assert_eq!(ball.position.y, 10.0 - ball.radius);
+        assert_eq!(ball.velocity.y, -0.125);
+
+        // the goal gaps are centered on each side on its own
+        assert!(ball_at(10.0, 0.0).in_goal_gap(PlayerPosition::Top, board, Some(2.0)));
+        assert!(!ball_at(5.0, 0.0).in_goal_gap(PlayerPosition::Top, board, Some(2.0)));
+        assert!(ball_at(0.0, 5.0).in_goal_gap(PlayerPosition::Left, board, Some(2.0)));
+
+        assert!(!ball_at(22.0, 5.0).is_out_of_bounds(board));
+        assert!(ball_at(5.0, 22.0).is_out_of_bounds(board));
+    }
+}
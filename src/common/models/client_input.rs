@@ -2,6 +2,10 @@ use std::net::SocketAddr;
 
 use serde::{Deserialize, Serialize};
 
+use super::{Game, GameSettings, Player, PlayerPosition, ServerInfo};
+
+/// Inputs only carry the player's intent, never positions,
+/// the paddles are moved and kept on the board by the server alone
 #[derive(Serialize, Debug, Deserialize, PartialEq, Clone)]
 pub enum ClientInputType {
     JoinGame,
@@ -11,6 +15,8 @@ pub enum ClientInputType {
     MovePaddle(Direction),
     Disconnect,
     Ping,
+    MovePaddleBy(Direction, f32), // held key, moves `paddle_delta` times the speed up to MAX_PADDLE_BOOST
+    ToggleBotsReady,              // host only, for games where bots don't ready on their own
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -19,11 +25,21 @@ pub enum Direction {
     Negative,
 }
 
+/// Version of the MessagePack messages, bumped whenever `ClientInput` or `GameDto` change
+/// in a way the other side can't read, messages from clients before versioning read as 0
+pub const PROTOCOL_VERSION: u16 = 1;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ClientInput {
     pub game_id: String,
     pub player_id: String,
     pub action: ClientInputType,
+    #[serde(default)]
+    pub reconnect_token: Option<String>, // proves who is joining, checked on `JoinGame`
+    #[serde(default)]
+    pub protocol_version: u16,
+    #[serde(default)]
+    pub seq: u64, // increases with every input the client sends, stale ones are dropped
 }
 
 impl ClientInput {
@@ -32,8 +48,16 @@ impl ClientInput {
             game_id,
             player_id,
             action,
+            reconnect_token: None,
+            protocol_version: PROTOCOL_VERSION,
+            seq: 0,
         }
     }
+
+    pub fn with_reconnect_token(mut self, reconnect_token: String) -> Self {
+        self.reconnect_token = Some(reconnect_token);
+        self
+    }
 }
 
 pub struct ClientInputWithAddr {
@@ -44,4 +68,44 @@ pub struct ClientInputWithAddr {
 #[derive(Serialize, Deserialize)]
 pub struct JoinGameRequest {
     pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_position: Option<PlayerPosition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>, // required by private games
+}
+
+/// Reply to joining a game, the player and the token only its owner gets to see
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JoinGameResponse {
+    #[serde(flatten)]
+    pub player: Player,
+    pub reconnect_token: String,
+    /// Where to send the game traffic, missing from servers that don't advertise it
+    #[serde(default)]
+    pub server_info: Option<ServerInfo>,
+}
+
+/// Body of the quick match request
+#[derive(Serialize, Deserialize, Default)]
+pub struct QuickMatchRequest {
+    pub username: Option<String>,
+}
+
+/// Reply to the quick match, the join reply along with the game the player ended up in
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuickMatchResponse {
+    pub game: Game,
+    #[serde(flatten)]
+    pub joined: JoinGameResponse,
+}
+
+/// Body of the create game request, the settings sit next to the optional password and name
+#[derive(Serialize, Deserialize, Default)]
+pub struct CreateGameRequest {
+    #[serde(flatten)]
+    pub settings: GameSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>, // of the lobby, cut when too long
 }
@@ -1,20 +1,30 @@
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::common::models::{ball::Vec2, Ball};
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct BallDto {
     pub position: Vec2,
     pub velocity: Vec2,
     pub radius: f32,
+    #[serde(default)]
+    pub last_touched_by: Option<Uuid>,
 }
 
-impl From<Ball> for BallDto {
-    fn from(ball: Ball) -> Self {
+impl From<&Ball> for BallDto {
+    fn from(ball: &Ball) -> Self {
         BallDto {
-            position: ball.position,
-            velocity: ball.velocity,
+            position: ball.position.clone(),
+            velocity: ball.velocity.clone(),
             radius: ball.radius,
+            last_touched_by: ball.last_touched_by,
         }
     }
 }
+
+impl From<Ball> for BallDto {
+    fn from(ball: Ball) -> Self {
+        BallDto::from(&ball)
+    }
+}
@@ -3,33 +3,172 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::common::{models::GameState, Game};
+use crate::common::{
+    models::{BoardSize, GameMode, GameState, PROTOCOL_VERSION},
+    Game,
+};
 
+use super::super::game_settings::DEFAULT_BOARD_SIZE;
 use super::{BallDto, PlayerDto};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct GameDto {
     pub id: Uuid,
     pub state: GameState,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub ball: Option<BallDto>,
+    #[serde(default)]
+    pub start_countdown_ms: Option<u64>, // remaining time, immune to clock differences
+    pub ball: Option<BallDto>, // the first ball, all a client without multiball support knows
     pub players: HashMap<Uuid, PlayerDto>,
+    #[serde(default)]
+    pub player_count: usize, // entries of `players`, bots included
+    #[serde(default = "default_board_size")]
+    pub board_width: f32,
+    #[serde(default = "default_board_size")]
+    pub board_height: f32,
+    #[serde(default)]
+    pub sequence: u64,
+    #[serde(default)]
+    pub extra_balls: Vec<BallDto>,
+    #[serde(default)]
+    pub forfeited_by: Option<String>, // name of the player who left the game early
+    #[serde(default)]
+    pub sudden_death: bool,
+    #[serde(default)]
+    pub serving: bool, // the balls wait in the center to be served
+    #[serde(default)]
+    pub protocol_version: u16, // 0 from servers before versioning
+    #[serde(default)]
+    pub last_goal_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub name: Option<String>, // of the lobby, unnamed ones are known by their id
+    #[serde(default)]
+    pub is_private: bool, // joining and playing again need the password
+    #[serde(default)]
+    pub mode: GameMode, // what players are ranked by
+    #[serde(default)]
+    pub goal_timeout_ms: Option<u64>, // pause after a goal, unknown to servers before it was sent
 }
 
-impl From<Game> for GameDto {
-    fn from(game: Game) -> Self {
+/// Borrows the game so broadcasting does not have to clone it first
+impl From<&Game> for GameDto {
+    fn from(game: &Game) -> Self {
+        let mut balls = game.balls.iter().map(BallDto::from);
         GameDto {
             id: game.id,
-            state: game.state,
+            state: game.state.clone(),
             created_at: game.created_at,
             started_at: game.started_at,
-            ball: game.ball.map(BallDto::from),
+            start_countdown_ms: game.start_countdown_until.map(|until| {
+                until
+                    .signed_duration_since(chrono::Utc::now())
+                    .num_milliseconds()
+                    .max(0) as u64
+            }),
+            ball: balls.next(),
             players: game
                 .players
-                .into_iter()
-                .map(|(id, player)| (id, PlayerDto::from(player)))
+                .iter()
+                .map(|(id, player)| (*id, PlayerDto::from(player)))
                 .collect(),
+            player_count: game.players.len(),
+            board_width: game.board().width,
+            board_height: game.board().height,
+            sequence: game.sequence,
+            extra_balls: balls.collect(),
+            forfeited_by: game.forfeited_by_name.clone(),
+            sudden_death: game.sudden_death,
+            serving: game.serving,
+            protocol_version: PROTOCOL_VERSION,
+            last_goal_at: game.last_goal_at,
+            name: game.name.clone(),
+            is_private: game.is_private,
+            mode: game.settings.mode,
+            goal_timeout_ms: game.settings.goal_timeout,
+        }
+    }
+}
+
+impl From<Game> for GameDto {
+    fn from(game: Game) -> Self {
+        GameDto::from(&game)
+    }
+}
+
+impl GameDto {
+    pub fn board(&self) -> BoardSize {
+        BoardSize {
+            width: self.board_width,
+            height: self.board_height,
         }
     }
+
+    pub fn balls(&self) -> impl Iterator<Item = &BallDto> {
+        self.ball.iter().chain(&self.extra_balls)
+    }
+
+    /// Player who last deflected the first ball
+    pub fn last_touched_by(&self) -> Option<Uuid> {
+        self.ball.as_ref().and_then(|ball| ball.last_touched_by)
+    }
+
+    /// Whether anything but the sequence number differs from the `previous` snapshot,
+    /// an unchanged game is not worth broadcasting again
+    pub fn changed_since(&self, previous: &GameDto) -> bool {
+        let unsequenced = GameDto {
+            sequence: previous.sequence,
+            ..self.clone()
+        };
+        unsequenced != *previous
+    }
+}
+
+fn default_board_size() -> f32 {
+    DEFAULT_BOARD_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Player;
+
+    #[test]
+    fn test_changed_since() {
+        let mut game = Game::new();
+        game.add_player(Player::new("Player 1".to_string(), false))
+            .unwrap();
+        let previous = GameDto::from(&game);
+
+        // a new sequence number alone is no change
+        game.sequence += 1;
+        assert!(!GameDto::from(&game).changed_since(&previous));
+
+        game.balls[0].position.x += 0.1;
+        assert!(GameDto::from(&game).changed_since(&previous));
+
+        game.balls[0].position.x -= 0.1;
+        game.players.values_mut().next().unwrap().is_ready = true;
+        assert!(GameDto::from(&game).changed_since(&previous));
+    }
+
+    #[test]
+    fn test_player_count() {
+        let mut game = Game::new();
+        assert_eq!(GameDto::from(&game).player_count, 0);
+
+        for (name, is_ai) in [("Player 1", false), ("Player 2", false), ("Bot", true)] {
+            game.add_player(Player::new(name.to_string(), is_ai))
+                .unwrap();
+        }
+        let dto = GameDto::from(&game);
+        assert_eq!(dto.player_count, 3);
+        assert_eq!(dto.player_count, dto.players.len());
+
+        let id = *game.players.keys().next().unwrap();
+        game.remove_player(id);
+        let dto = GameDto::from(&game);
+        assert_eq!(dto.player_count, 2);
+        assert_eq!(dto.player_count, dto.players.len());
+    }
 }
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::common::{models::score_tie_break, Game};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StandingDto {
+    pub player_id: Uuid,
+    pub name: String,
+    pub score: u32,
+    pub rank: usize, // equal scores share a rank unless one of them was reached first
+}
+
+/// Final standings of a finished game, best first
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GameResultsDto {
+    pub game_id: Uuid,
+    pub standings: Vec<StandingDto>,
+    pub winner: Option<Uuid>, // none when the game ended in a tie
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<&Game> for GameResultsDto {
+    fn from(game: &Game) -> Self {
+        let mut players: Vec<_> = game.players.values().collect();
+        players.sort_by(|a, b| {
+            game.standing(b)
+                .cmp(&game.standing(a))
+                .then_with(|| score_tie_break(a.last_score_at, b.last_score_at))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut standings: Vec<StandingDto> = Vec::with_capacity(players.len());
+        let mut previous_standing = None;
+        for (index, player) in players.into_iter().enumerate() {
+            let standing = (game.standing(player), player.last_score_at);
+            let rank = match standings.last() {
+                Some(previous) if previous_standing == Some(standing) => previous.rank,
+                _ => index + 1,
+            };
+            previous_standing = Some(standing);
+            standings.push(StandingDto {
+                player_id: player.id,
+                name: player.name.clone(),
+                score: player.score,
+                rank,
+            });
+        }
+
+        GameResultsDto {
+            game_id: game.id,
+            standings,
+            winner: game.unique_leader(),
+            finished_at: game.finished_at,
+        }
+    }
+}
+
+impl GameResultsDto {
+    /// The standings as `rank,player_id,name,score` rows under a header line
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rank,player_id,name,score\n");
+        for standing in &self.standings {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                standing.rank,
+                standing.player_id,
+                csv_field(&standing.name),
+                standing.score
+            ));
+        }
+        csv
+    }
+}
+
+/// Quotes names a spreadsheet would otherwise split or misread
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,7 +1,9 @@
 mod ball_dto;
 mod game_dto;
+mod game_results_dto;
 mod player_dto;
 
 pub use ball_dto::BallDto;
 pub use game_dto::GameDto;
+pub use game_results_dto::{GameResultsDto, StandingDto};
 pub use player_dto::PlayerDto;
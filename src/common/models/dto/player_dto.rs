@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::common::{models::player::PlayerPosition, Player};
+use crate::common::{
+    models::{player::PlayerPosition, GameMode},
+    Player,
+};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct PlayerDto {
     pub id: Uuid,
     pub name: String,
@@ -14,13 +17,19 @@ pub struct PlayerDto {
     pub paddle_delta: f32,
     pub paddle_width: f32,
     pub is_ready: bool,
+    #[serde(default)]
+    pub returns: u32,
+    #[serde(default)]
+    pub lives: u32,
+    #[serde(default)]
+    pub last_score_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-impl From<Player> for PlayerDto {
-    fn from(player: Player) -> Self {
+impl From<&Player> for PlayerDto {
+    fn from(player: &Player) -> Self {
         PlayerDto {
             id: player.id,
-            name: player.name,
+            name: player.name.clone(),
             joined_at: player.joined_at,
             score: player.score,
             position: player.position,
@@ -28,6 +37,25 @@ impl From<Player> for PlayerDto {
             paddle_delta: player.paddle_delta,
             paddle_width: player.paddle_width,
             is_ready: player.is_ready,
+            returns: player.returns,
+            lives: player.lives,
+            last_score_at: player.last_score_at,
+        }
+    }
+}
+
+impl From<Player> for PlayerDto {
+    fn from(player: Player) -> Self {
+        PlayerDto::from(&player)
+    }
+}
+
+impl PlayerDto {
+    /// What the player is ranked by, the score or the lives left in the lives mode
+    pub fn standing(&self, mode: GameMode) -> u32 {
+        match mode {
+            GameMode::Score => self.score,
+            GameMode::Lives => self.lives,
         }
     }
 }
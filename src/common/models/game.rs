@@ -1,31 +1,41 @@
 use chrono::{self, Utc};
-use log::info;
+use log::{info, warn};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::f32::consts::PI;
 use uuid::Uuid;
 
 use crate::common::game_error::GameError;
+use crate::common::lifecycle::{log_event, GameEvent};
 
-use super::ball::Ball;
+use super::ball::{Ball, BoardSize, Vec2};
 use super::dto::GameDto;
+use super::game_config::GameConfig;
+use super::game_settings::{GameMode, GameSettings, ServeTarget};
 use super::player::PlayerPosition;
-use super::Player;
+use super::{score_tie_break, Player};
 
 const MAX_ANGLE: f32 = PI / 3.0; // Maximum reflection angle (60 degrees in radians)
-const BALL_SPEED: f32 = 0.15; // Constant ball speed
 const PADDLE_PADDING: f32 = 0.25; // Padding around paddle to prevent collisions
 const SAFE_ZONE_MARGIN: f32 = 1.5; // Multiplier for padding to define safe zone
-const GAME_SIZE: f32 = 10.0;
-const MAX_PLAYERS: usize = 4;
-const PING_TIMEOUT: u64 = 2000;
-const MAX_SCORE: u32 = 10;
-const GOAL_TIMEOUT: u64 = 750;
-const GAME_DELETE_TIMEOUT: u64 = 60000; // 1 minute
+const MAX_TICK_DT: f32 = 4.0; // a stalled loop is not caught up in one step, the ball would skip paddles
+const ALL_POSITIONS: &[PlayerPosition] = &[
+    PlayerPosition::Top,
+    PlayerPosition::Bottom,
+    PlayerPosition::Right,
+    PlayerPosition::Left,
+];
+pub const MAX_LOBBY_NAME_LENGTH: usize = 32; // in characters, longer names are cut
+pub const START_COUNTDOWN: i64 = 3000; // ms between everyone being ready and the game starting
 
 #[derive(Debug, Serialize, Clone, PartialEq, Deserialize)]
 pub enum GameState {
     WaitingForPlayers,
+    Starting,
     Active,
     Paused,
     Finished,
@@ -38,9 +48,61 @@ pub struct Game {
     pub state: GameState,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub ball: Option<Ball>,
+    #[serde(default)]
+    pub start_countdown_until: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub balls: Vec<Ball>, // a single ball unless the multiball mode is set in the settings
     pub last_goal_at: Option<chrono::DateTime<chrono::Utc>>,
     pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub settings: GameSettings,
+    #[serde(skip)]
+    pub result_recorded: bool,
+    pub last_anti_stall_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub anti_stall_count: u64,
+    #[serde(default)]
+    pub sequence: u64, // bumped on every broadcast so clients can drop stale snapshots
+    #[serde(default)]
+    pub forfeited_by: Option<Uuid>, // the last human who left the game while it was played
+    #[serde(default)]
+    pub forfeited_by_name: Option<String>, // kept as the player is gone from `players`
+    #[serde(skip)]
+    pub password_hash: Option<[u8; 32]>, // private games only, never sent to clients
+    #[serde(default)]
+    pub is_private: bool, // joining needs the password, the hash itself stays on the server
+    #[serde(default)]
+    pub sudden_death: bool, // the time limit ran out with a tie, the next goal decides
+    #[serde(default)]
+    pub serving: bool, // the ball waits in the center after a goal
+    #[serde(default)]
+    pub name: Option<String>, // shown in lobby lists, set when the game is created
+    #[serde(default)]
+    pub seed: u64, // of `rng`, the settings' seed or a random one, a match replays with it
+    #[serde(skip)]
+    rng: GameRng, // every random choice of the game, reseeded when a match starts
+}
+
+/// Random source of a game, its position in the stream is not part of the game's state
+#[derive(Clone, Debug)]
+struct GameRng(StdRng);
+
+impl GameRng {
+    fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::seeded(0)
+    }
+}
+
+impl PartialEq for GameRng {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 impl Default for Game {
@@ -51,31 +113,186 @@ impl Default for Game {
 
 impl Game {
     pub fn new() -> Self {
+        let seed = rand::random();
         Self {
             id: Uuid::new_v4(),
             players: HashMap::new(),
             state: GameState::WaitingForPlayers,
             created_at: chrono::Utc::now(),
             started_at: None,
-            ball: Some(Ball::new()),
+            start_countdown_until: None,
+            balls: vec![Ball::new()],
             last_goal_at: None,
             finished_at: None,
+            settings: GameSettings::default(),
+            result_recorded: false,
+            last_anti_stall_at: None,
+            anti_stall_count: 0,
+            sequence: 0,
+            forfeited_by: None,
+            forfeited_by_name: None,
+            password_hash: None,
+            is_private: false,
+            sudden_death: false,
+            serving: false,
+            name: None,
+            seed,
+            rng: GameRng::seeded(seed),
+        }
+    }
+
+    /// Creates a game with already validated `settings`
+    pub fn with_settings(settings: GameSettings) -> Self {
+        let mut game = Self {
+            settings,
+            ..Self::new()
+        };
+        if let Some(seed) = game.settings.seed {
+            game.seed = seed;
+            game.rng = GameRng::seeded(seed);
+        }
+        game.balls = game.new_balls();
+        game
+    }
+
+    /// Creates a ball centered on the board using the game's settings
+    pub fn new_ball(&self) -> Ball {
+        let mut ball = Ball::centered(self.board());
+        ball.radius = self.settings.ball_radius;
+        ball
+    }
+
+    /// Creates `ball_count` balls, the first served toward an occupied side picked by the rng
+    /// like after a goal, the others turned evenly from it so they split up
+    pub fn new_balls(&mut self) -> Vec<Ball> {
+        let count = self.settings.ball_count.max(1);
+        let serve_to = self.occupied_sides().choose(&mut self.rng.0).copied();
+        (0..count)
+            .map(|i| {
+                let mut ball = self.new_ball();
+                if let Some(side) = serve_to {
+                    ball.serve_toward(side);
+                }
+                let Vec2 { x, y } = ball.velocity;
+                let angle = i as f32 * 2.0 * PI / count as f32;
+                ball.velocity = Vec2 {
+                    x: x * angle.cos() + y * angle.sin(),
+                    y: y * angle.cos() - x * angle.sin(),
+                };
+                ball
+            })
+            .collect()
+    }
+
+    /// The ball closest to the given side, the one an AI player should chase
+    pub fn nearest_ball(&self, position: PlayerPosition) -> Option<&Ball> {
+        let board = self.board();
+        let distance = |ball: &Ball| match position {
+            PlayerPosition::Top => ball.position.y,
+            PlayerPosition::Bottom => board.height - ball.position.y,
+            PlayerPosition::Left => ball.position.x,
+            PlayerPosition::Right => board.width - ball.position.x,
+        };
+        self.balls
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    /// Salted with the game id so equal passwords of different games differ
+    fn hash_password(&self, password: &str) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(self.id.as_bytes())
+            .chain_update(password.as_bytes())
+            .finalize()
+            .into()
+    }
+
+    /// Names the lobby, dropping control characters and cutting it to `MAX_LOBBY_NAME_LENGTH`,
+    /// a blank name leaves it unnamed
+    pub fn set_name(&mut self, name: &str) {
+        let name: String = name
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect::<String>()
+            .trim()
+            .chars()
+            .take(MAX_LOBBY_NAME_LENGTH)
+            .collect();
+        let name = name.trim_end();
+        self.name = (!name.is_empty()).then(|| name.to_string());
+    }
+
+    /// Makes the game private, an empty password keeps it open to everyone
+    pub fn set_password(&mut self, password: &str) {
+        self.password_hash = (!password.is_empty()).then(|| self.hash_password(password));
+        self.is_private = self.password_hash.is_some();
+    }
+
+    pub fn check_password(&self, password: Option<&str>) -> bool {
+        match self.password_hash {
+            Some(hash) => password.is_some_and(|password| self.hash_password(password) == hash),
+            None => true,
         }
     }
 
     pub fn to_network_bytes(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
-        let dto = GameDto::from(self.clone());
-        rmp_serde::to_vec(&dto)
+        rmp_serde::to_vec(&GameDto::from(self))
+    }
+
+    /// Width and height of the board the game is played on
+    pub fn board(&self) -> BoardSize {
+        self.settings.board()
+    }
+
+    /// Puts the paddle of `player` where paddles start along their side,
+    /// the middle of the side unless the settings say otherwise
+    fn place_at_start(player: &mut Player, settings: &GameSettings) {
+        let board = settings.board();
+        let start = settings
+            .starting_paddle_position
+            .unwrap_or(player.side_length(board) / 2.0);
+        player.place_paddle(start, board);
     }
 
-    pub fn add_player(&mut self, player: Player) -> Result<(), GameError> {
+    pub fn add_player(&mut self, mut player: Player) -> Result<(), GameError> {
         if self.is_full() {
             return Err(GameError::GameFull);
         }
+        player.paddle_width = self.settings.paddle_width;
+        Game::place_at_start(&mut player, &self.settings);
+        player.paddle_delta = self.settings.paddle_speed;
+        player.lives = self.settings.lives;
+        if player.is_ai {
+            player.is_ready = self.settings.bots_auto_ready;
+        }
+        let detail = if player.is_ai {
+            format!("{} (bot)", player.name)
+        } else {
+            player.name.clone()
+        };
+        log_event(GameEvent::PlayerJoined, self.id, Some(player.id), &detail);
         self.players.insert(player.id, player);
         Ok(())
     }
 
+    /// Sides handed out to players, a 2-player game is classic pong between Left and Right
+    pub fn playable_positions(&self) -> &'static [PlayerPosition] {
+        match self.settings.max_players {
+            0..=2 => &[PlayerPosition::Left, PlayerPosition::Right],
+            3 => &[
+                PlayerPosition::Left,
+                PlayerPosition::Right,
+                PlayerPosition::Top,
+            ],
+            _ => &[
+                PlayerPosition::Top,
+                PlayerPosition::Bottom,
+                PlayerPosition::Right,
+                PlayerPosition::Left,
+            ],
+        }
+    }
+
     pub fn assign_position(&self) -> Option<PlayerPosition> {
         let existing_positions: Vec<PlayerPosition> = self
             .players
@@ -83,36 +300,105 @@ impl Game {
             .filter_map(|player| player.position)
             .collect();
 
-        let all_positions = [
-            PlayerPosition::Top,
-            PlayerPosition::Bottom,
-            PlayerPosition::Right,
-            PlayerPosition::Left,
-        ];
-
-        all_positions
+        self.playable_positions()
             .iter()
             .find(|&&pos| !existing_positions.contains(&pos))
             .copied()
     }
 
+    /// Returns `preferred` if that side is still free, otherwise falls back to `assign_position`
+    pub fn assign_preferred_position(
+        &self,
+        preferred: Option<PlayerPosition>,
+    ) -> Option<PlayerPosition> {
+        match preferred {
+            Some(position)
+                if self.playable_positions().contains(&position)
+                    && self.get_player_by_side(position).is_none() =>
+            {
+                Some(position)
+            }
+            _ => self.assign_position(),
+        }
+    }
+
     pub fn remove_player(&mut self, id: Uuid) {
-        self.players.remove(&id);
+        if let Some(player) = self.players.remove(&id) {
+            log_event(GameEvent::PlayerLeft, self.id, Some(id), &player.name);
+        }
         if self.players.values().filter(|player| !player.is_ai).count() < 2 {
             self.set_game_state(GameState::Finished);
         }
     }
 
+    /// Voluntary leave, counted as a forfeit when it ends a game that is being played
+    pub fn forfeit(&mut self, id: Uuid) {
+        let in_progress = matches!(self.state, GameState::Active | GameState::Paused);
+        let leaving = self
+            .players
+            .get(&id)
+            .filter(|p| in_progress && !p.is_ai)
+            .map(|p| p.name.clone());
+        self.remove_player(id);
+        // the others play on when enough humans are left
+        if let Some(name) = leaving.filter(|_| self.state == GameState::Finished) {
+            info!("game {}: {} ({}) forfeited", self.id, name, id);
+            self.forfeited_by = Some(id);
+            self.forfeited_by_name = Some(name);
+        }
+    }
+
     pub fn set_game_state(&mut self, state: GameState) {
         if state == GameState::Finished {
             self.finished_at = Some(chrono::Utc::now());
+            if self.state != GameState::Finished {
+                log_event(
+                    GameEvent::Finished,
+                    self.id,
+                    self.unique_leader(),
+                    &self.final_scores(),
+                );
+            }
         }
 
         self.state = state;
     }
 
     pub fn is_full(&self) -> bool {
-        self.players.len() >= MAX_PLAYERS
+        self.players.len() >= self.settings.max_players
+    }
+
+    /// `name`, or with the first free numeric suffix when a player already uses it
+    pub fn unique_name(&self, name: &str) -> String {
+        let taken = |candidate: &str| self.players.values().any(|p| p.name == candidate);
+        if !taken(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|suffix| format!("{}_{}", name, suffix))
+            .find(|candidate| !taken(candidate))
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// The human who joined first, they decide for the bots
+    pub fn host(&self) -> Option<Uuid> {
+        self.players
+            .values()
+            .filter(|player| !player.is_ai)
+            .min_by_key(|player| player.joined_at)
+            .map(|player| player.id)
+    }
+
+    /// Readies all bots, or unreadies them when they all are ready already
+    pub fn toggle_bots_ready(&mut self) {
+        let all_ready = self
+            .players
+            .values()
+            .filter(|player| player.is_ai)
+            .all(|player| player.is_ready);
+        for bot in self.players.values_mut().filter(|player| player.is_ai) {
+            bot.is_ready = !all_ready;
+        }
     }
 
     pub fn get_player(&self, id: &Uuid) -> Option<&Player> {
@@ -132,7 +418,7 @@ impl Game {
             return Err(GameError::InvalidStateTransition);
         }
 
-        if self.players.values().count() < 2 {
+        if self.players.values().count() < self.settings.min_players {
             return Err(GameError::InvalidStateTransition);
         }
 
@@ -140,11 +426,158 @@ impl Game {
             return Err(GameError::PlayersNotReady);
         }
 
-        self.started_at = Some(chrono::Utc::now());
-        self.state = GameState::Active;
+        self.start_countdown_until =
+            Some(Utc::now() + chrono::Duration::milliseconds(START_COUNTDOWN));
+        self.state = GameState::Starting;
+        Ok(())
+    }
+
+    /// Goes back to waiting for players when the countdown is interrupted
+    pub fn cancel_start(&mut self) -> Result<(), GameError> {
+        if self.state != GameState::Starting {
+            return Err(GameError::InvalidStateTransition);
+        }
+
+        self.start_countdown_until = None;
+        self.state = GameState::WaitingForPlayers;
+        Ok(())
+    }
+
+    /// Starts the countdown without anyone asking once the lobby is full and everyone is ready,
+    /// the regular ready flow already started it in most cases
+    fn check_auto_start(&mut self) {
+        if self.settings.auto_start_when_full && self.is_full() && self.start_game().is_ok() {
+            info!("game {}: full and ready, starting", self.id);
+        }
+    }
+
+    /// Starts the game once the countdown elapses, cancels it if a player left or un-readied
+    fn tick_countdown(&mut self) {
+        let everyone_ready = self.players.len() >= self.settings.min_players
+            && self.players.values().all(|player| player.is_ready);
+        if !everyone_ready {
+            if self.cancel_start().is_ok() {
+                info!("game {}: countdown cancelled", self.id);
+            }
+            return;
+        }
+
+        if self
+            .start_countdown_until
+            .is_some_and(|until| Utc::now() >= until)
+        {
+            self.start_countdown_until = None;
+            self.started_at = Some(Utc::now());
+            // every match of a seed plays out the same, whatever happened in the lobby before
+            self.rng = GameRng::seeded(self.seed);
+            self.balls = self.new_balls();
+            if self.settings.warmup {
+                // the game starts from the same spots as without the warmup
+                for player in self.players.values_mut() {
+                    player.returns = 0;
+                    Game::place_at_start(player, &self.settings);
+                }
+            }
+            self.state = GameState::Active;
+            log_event(GameEvent::Started, self.id, None, "");
+        }
+    }
+
+    /// Resets a finished game back to the lobby while keeping its roster
+    pub fn rematch(&mut self) -> Result<(), GameError> {
+        if self.state != GameState::Finished {
+            return Err(GameError::InvalidStateTransition);
+        }
+
+        for player in self.players.values_mut() {
+            player.score = 0;
+            player.last_score_at = None;
+            player.returns = 0;
+            player.lives = self.settings.lives;
+            player.is_ready = player.is_ai && self.settings.bots_auto_ready;
+        }
+
+        // players eliminated in the lives mode get a side again
+        let eliminated: Vec<Uuid> = self
+            .players
+            .values()
+            .filter(|player| player.position.is_none())
+            .map(|player| player.id)
+            .collect();
+        for id in eliminated {
+            let position = self.assign_position();
+            if let Some(player) = self.players.get_mut(&id) {
+                player.position = position;
+            }
+        }
+        if self.settings.rotate_seats_on_rematch {
+            self.rotate_positions();
+        }
+        // placed once the sides are settled, they may differ in length
+        for player in self.players.values_mut() {
+            Game::place_at_start(player, &self.settings);
+        }
+
+        self.reset_to_lobby();
+        Ok(())
+    }
+
+    /// Reopens a finished game as an empty lobby, for whoever joins to play again
+    pub fn play_again(&mut self) -> Result<(), GameError> {
+        if self.state != GameState::Finished {
+            return Err(GameError::InvalidStateTransition);
+        }
+
+        self.players.clear();
+        self.reset_to_lobby();
         Ok(())
     }
 
+    /// Clears what the last match left behind, the roster is up to the caller
+    fn reset_to_lobby(&mut self) {
+        for player in self.players.values_mut() {
+            // nobody pings from the end screen, the lobby starts the timeout again
+            player.ping_timestamp = None;
+        }
+
+        self.balls = self.new_balls();
+        self.started_at = None;
+        self.start_countdown_until = None;
+        self.forfeited_by = None;
+        self.forfeited_by_name = None;
+        self.sudden_death = false;
+        self.serving = false;
+        self.finished_at = None;
+        self.last_goal_at = None;
+        self.last_anti_stall_at = None;
+        self.result_recorded = false;
+        self.state = GameState::WaitingForPlayers;
+    }
+
+    /// Moves every player to the next occupied side clockwise (Top, Right, Bottom, Left),
+    /// so no one keeps a side that may play better over a series of rematches
+    pub fn rotate_positions(&mut self) {
+        const CLOCKWISE: [PlayerPosition; 4] = [
+            PlayerPosition::Top,
+            PlayerPosition::Right,
+            PlayerPosition::Bottom,
+            PlayerPosition::Left,
+        ];
+        let occupied: Vec<PlayerPosition> = CLOCKWISE
+            .into_iter()
+            .filter(|&side| self.players.values().any(|p| p.position == Some(side)))
+            .collect();
+
+        for player in self.players.values_mut() {
+            let index = player
+                .position
+                .and_then(|position| occupied.iter().position(|&side| side == position));
+            if let Some(index) = index {
+                player.position = Some(occupied[(index + 1) % occupied.len()]);
+            }
+        }
+    }
+
     pub fn pause_game(&mut self) -> Result<(), GameError> {
         if self.state != GameState::Active {
             return Err(GameError::InvalidStateTransition);
@@ -154,42 +587,150 @@ impl Game {
         Ok(())
     }
 
+    /// Sides with a player on them, always in the same order unlike the players
+    pub fn occupied_sides(&self) -> Vec<PlayerPosition> {
+        ALL_POSITIONS
+            .iter()
+            .filter(|&&side| self.get_player_by_side(side).is_some())
+            .copied()
+            .collect()
+    }
+
     pub fn get_player_by_side(&self, side: PlayerPosition) -> Option<&Player> {
         self.players
             .values()
             .find(|player| player.position == Some(side))
     }
 
-    pub fn goal_action(&mut self, goal_pos: PlayerPosition) {
+    /// Scores the goal of the ball at `ball_index` and resets only that ball
+    pub fn goal_action(&mut self, ball_index: usize, goal_pos: PlayerPosition) {
         if self.state != GameState::Active {
             return;
         }
 
+        // a goal can only be scored on a side defended by a player
+        let Some(conceded_by) = self.get_player_by_side(goal_pos).map(|player| player.id) else {
+            return;
+        };
+
+        if self.settings.mode == GameMode::Lives {
+            self.lose_life(conceded_by);
+        }
+
         let mut last_touched: Option<Uuid> = None;
 
-        if let Some(ref mut ball) = self.ball {
+        let positions = self.occupied_sides();
+        let serve_to = match self.settings.serve {
+            ServeTarget::ScoredOn => positions.contains(&goal_pos).then_some(goal_pos),
+            ServeTarget::Random => positions.choose(&mut self.rng.0).copied(),
+        };
+
+        let board = self.board();
+        if let Some(ball) = self.balls.get_mut(ball_index) {
             last_touched = ball.last_touched_by;
             self.last_goal_at = Some(Utc::now());
-            ball.reset(
-                self.players
-                    .values()
-                    .map(|p| p.position.unwrap_or(PlayerPosition::Top))
-                    .collect(),
-            );
+            self.serving = true;
+            ball.reset(positions, board, &mut self.rng.0);
+            if let Some(side) = serve_to {
+                ball.serve_toward(side);
+            }
         }
 
         if let Some(id) = last_touched {
             let player = self.get_player_mut(&id);
             if let Some(player) = player {
-                if player.position != Some(goal_pos) {
+                if player.id != conceded_by {
                     player.increment_score();
-                    info!("game {}: player {} scored", self.id, id);
+                    let detail = format!("scored on {:?}, score {}", goal_pos, player.score);
+                    log_event(GameEvent::Goal, self.id, Some(id), &detail);
                 }
             }
         }
     }
 
-    pub fn check_players_health(&mut self) {
+    /// Takes a life of the player, at zero their paddle is removed and the side becomes a wall
+    fn lose_life(&mut self, id: Uuid) {
+        let game_id = self.id;
+        if let Some(player) = self.players.get_mut(&id) {
+            player.lives = player.lives.saturating_sub(1);
+            if player.lives == 0 {
+                player.position = None;
+                info!("game {}: player {} eliminated", game_id, id);
+            }
+        }
+    }
+
+    /// What players are ranked by, the score or the lives left in the lives mode
+    pub fn standing(&self, player: &Player) -> u32 {
+        match self.settings.mode {
+            GameMode::Score => player.score,
+            GameMode::Lives => player.lives,
+        }
+    }
+
+    /// Whether a player reached the max score, or only one is left standing in the lives mode
+    fn is_decided(&self) -> bool {
+        match self.settings.mode {
+            GameMode::Score => self
+                .players
+                .values()
+                .any(|p| p.score >= self.settings.max_score),
+            GameMode::Lives => self.players.values().filter(|p| p.lives > 0).count() <= 1,
+        }
+    }
+
+    /// `name=standing` of every player, the best first
+    fn final_scores(&self) -> String {
+        let mut players: Vec<&Player> = self.players.values().collect();
+        players.sort_by(|a, b| {
+            self.standing(b)
+                .cmp(&self.standing(a))
+                .then_with(|| score_tie_break(a.last_score_at, b.last_score_at))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        players
+            .iter()
+            .map(|player| format!("{}={}", player.name, self.standing(player)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The player with the best standing, `None` while several share it
+    pub fn unique_leader(&self) -> Option<Uuid> {
+        let top = self.players.values().map(|p| self.standing(p)).max()?;
+        let mut leaders = self.players.values().filter(|p| self.standing(p) == top);
+        match (leaders.next(), leaders.next()) {
+            (Some(leader), None) => Some(leader.id),
+            _ => None,
+        }
+    }
+
+    fn time_limit_reached(&self) -> bool {
+        self.settings.time_limit > 0
+            && self.started_at.is_some_and(|started_at| {
+                Utc::now()
+                    .signed_duration_since(started_at)
+                    .num_milliseconds() as u64
+                    >= self.settings.time_limit
+            })
+    }
+
+    /// Ends a timed game with a unique leader, a tie goes to sudden death instead
+    fn check_time_limit(&mut self) {
+        if self.sudden_death || !self.time_limit_reached() {
+            return;
+        }
+
+        if self.unique_leader().is_some() {
+            self.set_game_state(GameState::Finished);
+            info!("game {}: finished at the time limit", self.id);
+        } else {
+            self.sudden_death = true;
+            info!("game {}: tied at the time limit, sudden death", self.id);
+        }
+    }
+
+    pub fn check_players_health(&mut self, config: &GameConfig) {
         let current_time = Utc::now();
 
         let players_to_remove: Vec<_> = self
@@ -198,7 +739,7 @@ impl Game {
             .filter_map(|player| {
                 player.ping_timestamp.and_then(|timestamp| {
                     let elapsed = current_time.signed_duration_since(timestamp);
-                    (elapsed.num_milliseconds() as u64 > PING_TIMEOUT).then_some(player.id)
+                    (elapsed.num_milliseconds() as u64 > config.ping_timeout).then_some(player.id)
                 })
             })
             .collect();
@@ -209,207 +750,400 @@ impl Game {
         }
     }
 
-    pub fn should_delete_game(&self) -> bool {
+    /// Finished games are kept for a while to show their results,
+    /// unless every human left and only bots would be looking at them.
+    /// Lobbies no human is waiting in are deleted once they outlive the idle timeout,
+    /// a human leaving a lobby finishes it so they can only be the ones nobody joined
+    pub fn should_delete_game(&self, config: &GameConfig) -> bool {
+        if self.state == GameState::Finished && self.players.values().all(|player| player.is_ai) {
+            return true;
+        }
         if let Some(finished_at) = self.finished_at {
             let elapsed_since_finished = Utc::now().signed_duration_since(finished_at);
-            (elapsed_since_finished.num_milliseconds() as u64) > GAME_DELETE_TIMEOUT
+            (elapsed_since_finished.num_milliseconds() as u64) > config.game_delete_timeout
+        } else if self.state == GameState::WaitingForPlayers {
+            let idle = Utc::now().signed_duration_since(self.created_at);
+            self.players.values().all(|player| player.is_ai)
+                && (idle.num_milliseconds() as u64) > config.idle_lobby_timeout
         } else {
             false
         }
     }
 
-    pub fn game_tick(&mut self) {
+    /// Advances the game by `dt`, the time since the last tick in ticks of `BASE_TICK_RATE`
+    pub fn game_tick(&mut self, config: &GameConfig, dt: f32) {
+        let dt = dt.clamp(0.0, MAX_TICK_DT);
+
         if self.state == GameState::Finished {
             return;
         }
 
-        self.check_players_health();
+        self.check_players_health(config);
+
+        if self.state == GameState::WaitingForPlayers {
+            self.check_auto_start();
+        }
+
+        if self.state == GameState::WaitingForPlayers && self.settings.warmup {
+            self.warmup_tick(dt);
+            return;
+        }
+
+        if self.state == GameState::Starting {
+            self.tick_countdown();
+            return;
+        }
+
+        if self.state != GameState::Active {
+            return;
+        }
 
+        self.check_time_limit();
         if self.state != GameState::Active {
             return;
         }
 
+        let board = self.board();
+
+        self.players
+            .values_mut()
+            .for_each(|player| player.apply_pending_move(board));
+
         // create an artificial pause after the goal was scored
         if let Some(last_goal_at) = self.last_goal_at {
             let elapsed_since_goal = Utc::now().signed_duration_since(last_goal_at);
-            if (elapsed_since_goal.num_milliseconds() as u64) < GOAL_TIMEOUT {
+            let goal_timeout = self.settings.goal_timeout.unwrap_or(config.goal_timeout);
+            if (elapsed_since_goal.num_milliseconds() as u64) < goal_timeout {
                 return;
             }
         }
+        self.serving = false;
+
+        self.check_stall();
+
+        self.move_ai_players(dt);
+
+        for index in 0..self.balls.len() {
+            let occupied = self.occupied_sides();
+            let ball = &mut self.balls[index];
+            let velocity = ball.velocity.clone();
+            ball.update_position(dt);
+
+            // a physics bug must not leave the game without a ball to play
+            if ball.is_out_of_bounds(board) {
+                warn!(
+                    "game {}: ball escaped the board at {:?} with velocity {:?}, resetting it",
+                    self.id, ball.position, velocity
+                );
+                ball.reset(occupied, board, &mut self.rng.0);
+                continue;
+            }
 
-        if let Some(ball) = &mut self.ball {
-            ball.update_position();
-
-            self.players.values_mut().for_each(|player| {
-                if player.is_ai {
-                    player.ai(ball.clone());
+            // empty sides are walls, only the goal gaps of occupied sides can be scored on
+            let goal_width = self.settings.goal_width;
+            for pos in ALL_POSITIONS {
+                if !occupied.contains(pos) || !ball.in_goal_gap(*pos, board, goal_width) {
+                    ball.calculate_wall_reflection(*pos, board);
                 }
-            });
-
-            const ALL_POSITIONS: &[PlayerPosition] = &[
-                PlayerPosition::Top,
-                PlayerPosition::Bottom,
-                PlayerPosition::Right,
-                PlayerPosition::Left,
-            ];
-
-            for empty_pos in ALL_POSITIONS.iter().filter(|pos| {
-                self.players
-                    .values()
-                    .all(|player| player.position != Some(**pos))
-            }) {
-                ball.calculate_wall_reflection(*empty_pos);
             }
 
-            if let Some(goal_pos) = ball.clone().is_goal() {
-                self.goal_action(goal_pos);
+            if let Some(goal_pos) = ball.is_goal(board, &occupied) {
+                self.goal_action(index, goal_pos);
 
-                if self.players.values().any(|p| p.score >= MAX_SCORE) {
+                if self.is_decided() || (self.sudden_death && self.unique_leader().is_some()) {
                     self.set_game_state(GameState::Finished);
-                    info!("game {}: finished", self.id);
                     return;
                 }
             }
         }
 
-        self.check_collision();
+        self.check_collision(dt);
+    }
+
+    /// Free play in the lobby, paddles and balls move but a ball in a goal is only put back
+    fn warmup_tick(&mut self, dt: f32) {
+        let board = self.board();
+
+        self.players
+            .values_mut()
+            .for_each(|player| player.apply_pending_move(board));
+        self.move_ai_players(dt);
+
+        let occupied = self.occupied_sides();
+        let goal_width = self.settings.goal_width;
+        for ball in self.balls.iter_mut() {
+            ball.update_position(dt);
+            if ball.is_out_of_bounds(board) {
+                ball.reset(occupied.clone(), board, &mut self.rng.0);
+                continue;
+            }
+            for pos in ALL_POSITIONS {
+                if !occupied.contains(pos) || !ball.in_goal_gap(*pos, board, goal_width) {
+                    ball.calculate_wall_reflection(*pos, board);
+                }
+            }
+            if ball.is_goal(board, &occupied).is_some() {
+                ball.reset(occupied.clone(), board, &mut self.rng.0);
+            }
+        }
+
+        self.check_collision(dt);
+    }
+
+    /// Moves every AI player towards the ball closest to its side, as far as it gets in `dt` ticks
+    pub fn move_ai_players(&mut self, dt: f32) {
+        let board = self.board();
+        let reaction_ticks = self.settings.ai_reaction_ticks;
+        let occupied = self.occupied_sides();
+        // side by side, so the bots draw from the game's rng in the same order every time
+        let targets: Vec<(Uuid, Ball)> = occupied
+            .iter()
+            .filter_map(|&side| {
+                let bot = self
+                    .get_player_by_side(side)
+                    .filter(|player| player.is_ai)?;
+                let ball = self.nearest_ball(side)?;
+                Some((bot.id, ball.clone()))
+            })
+            .collect();
+
+        for (id, ball) in targets {
+            if let Some(player) = self.players.get_mut(&id) {
+                player.ai(ball, board, &occupied, reaction_ticks, dt, &mut self.rng.0);
+            }
+        }
+    }
+
+    /// Nudges the ball when no goal was scored for `stall_timeout` ms,
+    /// breaking up rallies where the ball bounces in a near-perpetual loop
+    pub fn check_stall(&mut self) {
+        if self.settings.stall_timeout == 0 {
+            return;
+        }
+
+        let last_event = [self.started_at, self.last_goal_at, self.last_anti_stall_at]
+            .into_iter()
+            .flatten()
+            .max();
+
+        let Some(last_event) = last_event else {
+            return;
+        };
+
+        let elapsed = Utc::now().signed_duration_since(last_event);
+        if (elapsed.num_milliseconds() as u64) < self.settings.stall_timeout {
+            return;
+        }
+
+        if !self.balls.is_empty() {
+            // the game's rng keeps the nudges as reproducible as the rest of a seeded match
+            for ball in self.balls.iter_mut() {
+                ball.nudge(&mut self.rng.0);
+            }
+            self.anti_stall_count += 1;
+            self.last_anti_stall_at = Some(Utc::now());
+            info!("game {}: anti-stall nudge applied", self.id);
+        }
     }
 
-    pub fn is_ball_in_safe_zone(ball: &Ball, paddle_padding: f32) -> bool {
-        let safe_distance = paddle_padding * SAFE_ZONE_MARGIN;
+    pub fn is_ball_in_safe_zone(ball: &Ball, paddle_padding: f32, board: BoardSize) -> bool {
+        Game::is_in_safe_zone(&ball.position, ball.radius, paddle_padding, board)
+    }
 
-        ball.position.x > safe_distance
-            && ball.position.x < (GAME_SIZE - safe_distance)
-            && ball.position.y > safe_distance
-            && ball.position.y < (GAME_SIZE - safe_distance)
+    /// Whether a ball of `radius` centered at `position` is too far from every paddle to hit one
+    fn is_in_safe_zone(
+        position: &Vec2,
+        radius: f32,
+        paddle_padding: f32,
+        board: BoardSize,
+    ) -> bool {
+        let safe_distance = paddle_padding * SAFE_ZONE_MARGIN + radius;
+
+        position.x > safe_distance
+            && position.x < (board.width - safe_distance)
+            && position.y > safe_distance
+            && position.y < (board.height - safe_distance)
     }
 
-    pub fn check_collision(&mut self) {
-        if let Some(ball) = &mut self.ball {
+    /// Bounces the balls that would pass a paddle within the next `dt`.
+    /// The whole path to the next position is checked, so a fast ball can't skip a paddle
+    pub fn check_collision(&mut self, dt: f32) {
+        let board = self.board();
+        let ball_speed = self.settings.ball_speed;
+        let bounce_jitter = self.settings.bounce_jitter;
+
+        for ball in self.balls.iter_mut() {
             // check if we need to check collision
-            if Game::is_ball_in_safe_zone(ball, PADDLE_PADDING) {
-                return;
+            let next = ball.next_position(dt);
+            if Game::is_ball_in_safe_zone(ball, PADDLE_PADDING, board)
+                && Game::is_in_safe_zone(&next, ball.radius, PADDLE_PADDING, board)
+            {
+                continue;
             }
             for player in self.players.values_mut() {
+                let next = ball.next_position(dt);
+                let paddle_start = player.paddle_position - player.paddle_width / 2.0;
+                let paddle_end = player.paddle_position + player.paddle_width / 2.0;
+                let spans_paddle = |along: f32, radius: f32| {
+                    along + radius >= paddle_start && along - radius <= paddle_end
+                };
+
                 match player.position {
                     Some(PlayerPosition::Top) => {
-                        let paddle_start = player.paddle_position - player.paddle_width / 2.0;
-                        let paddle_end = player.paddle_position + player.paddle_width / 2.0;
                         let paddle_y = PADDLE_PADDING;
+                        let Some(t) =
+                            paddle_crossing(ball.position.y, next.y, paddle_y, ball.radius, -1.0)
+                        else {
+                            continue;
+                        };
+                        let hit_x = lerp(ball.position.x, next.x, t);
+                        if !spans_paddle(hit_x, ball.radius) {
+                            continue;
+                        }
 
-                        let next_ball_y = ball.position.y + ball.velocity.y;
-
-                        // Check if the ball will collide with the paddle
-                        if next_ball_y < paddle_y
-                            && (ball.position.x + ball.radius) >= paddle_start
-                            && (ball.position.x - ball.radius) <= paddle_end
-                        {
-                            let hit_offset = ((ball.position.x - player.paddle_position)
-                                / (player.paddle_width / 2.0))
-                                .clamp(-1.0, 1.0);
-
-                            let angle = (3.0 * PI / 2.0) + hit_offset * MAX_ANGLE;
+                        let hit_offset = ((hit_x - player.paddle_position)
+                            / (player.paddle_width / 2.0))
+                            .clamp(-1.0, 1.0);
 
-                            // Update the ball's velocity based on the reflection angle
-                            ball.velocity.x = BALL_SPEED * angle.cos();
-                            ball.velocity.y = -BALL_SPEED * angle.sin();
+                        let angle = (3.0 * PI / 2.0)
+                            + deflection(hit_offset, bounce_jitter, &mut self.rng.0);
 
-                            ball.position.y = paddle_y + ball.radius;
+                        // Update the ball's velocity based on the reflection angle
+                        ball.velocity.x = ball_speed * angle.cos();
+                        ball.velocity.y = -ball_speed * angle.sin();
 
-                            ball.last_touched_by = Some(player.id);
-                        }
+                        ball.position = Vec2 {
+                            x: hit_x,
+                            y: paddle_y + ball.radius,
+                        };
                     }
                     Some(PlayerPosition::Bottom) => {
-                        let paddle_start = player.paddle_position - player.paddle_width / 2.0;
-                        let paddle_end = player.paddle_position + player.paddle_width / 2.0;
-                        let paddle_y = GAME_SIZE - PADDLE_PADDING;
-
-                        let next_ball_y = ball.position.y + ball.velocity.y;
-
-                        // Check if the ball will collide with the paddle
-                        if next_ball_y > paddle_y
-                            && (ball.position.x + ball.radius) >= paddle_start
-                            && (ball.position.x - ball.radius) <= paddle_end
-                        {
-                            let hit_offset = -((ball.position.x - player.paddle_position)
-                                / (player.paddle_width / 2.0))
-                                .clamp(-1.0, 1.0);
+                        let paddle_y = board.height - PADDLE_PADDING;
+                        let Some(t) =
+                            paddle_crossing(ball.position.y, next.y, paddle_y, ball.radius, 1.0)
+                        else {
+                            continue;
+                        };
+                        let hit_x = lerp(ball.position.x, next.x, t);
+                        if !spans_paddle(hit_x, ball.radius) {
+                            continue;
+                        }
 
-                            let angle = (PI / 2.0) + hit_offset * MAX_ANGLE;
+                        let hit_offset = -((hit_x - player.paddle_position)
+                            / (player.paddle_width / 2.0))
+                            .clamp(-1.0, 1.0);
 
-                            ball.velocity.x = BALL_SPEED * angle.cos();
-                            ball.velocity.y = -BALL_SPEED * angle.sin();
+                        let angle =
+                            (PI / 2.0) + deflection(hit_offset, bounce_jitter, &mut self.rng.0);
 
-                            ball.position.y = paddle_y - ball.radius;
+                        ball.velocity.x = ball_speed * angle.cos();
+                        ball.velocity.y = -ball_speed * angle.sin();
 
-                            ball.last_touched_by = Some(player.id);
-                        }
+                        ball.position = Vec2 {
+                            x: hit_x,
+                            y: paddle_y - ball.radius,
+                        };
                     }
                     Some(PlayerPosition::Left) => {
-                        let paddle_start = player.paddle_position - player.paddle_width / 2.0;
-                        let paddle_end = player.paddle_position + player.paddle_width / 2.0;
                         let paddle_x = PADDLE_PADDING;
+                        let Some(t) =
+                            paddle_crossing(ball.position.x, next.x, paddle_x, ball.radius, -1.0)
+                        else {
+                            continue;
+                        };
+                        let hit_y = lerp(ball.position.y, next.y, t);
+                        if !spans_paddle(hit_y, ball.radius) {
+                            continue;
+                        }
 
-                        let next_ball_x = ball.position.x + ball.velocity.x;
-
-                        // Check if the ball will collide with the paddle
-                        if next_ball_x < paddle_x
-                            && (ball.position.y + ball.radius) >= paddle_start
-                            && (ball.position.y - ball.radius) <= paddle_end
-                        {
-                            let hit_offset = -((ball.position.y - player.paddle_position)
-                                / (player.paddle_width / 2.0))
-                                .clamp(-1.0, 1.0);
-
-                            let angle = (PI) + hit_offset * MAX_ANGLE;
+                        let hit_offset = -((hit_y - player.paddle_position)
+                            / (player.paddle_width / 2.0))
+                            .clamp(-1.0, 1.0);
 
-                            ball.velocity.x = -BALL_SPEED * angle.cos();
-                            ball.velocity.y = BALL_SPEED * angle.sin();
+                        // angle off the paddle's normal, which points right, into the board
+                        let angle = deflection(hit_offset, bounce_jitter, &mut self.rng.0);
 
-                            ball.position.x = paddle_x + ball.radius;
+                        ball.velocity.x = ball_speed * angle.cos();
+                        ball.velocity.y = -ball_speed * angle.sin();
 
-                            ball.last_touched_by = Some(player.id);
-                        }
+                        ball.position = Vec2 {
+                            x: paddle_x + ball.radius,
+                            y: hit_y,
+                        };
                     }
                     Some(PlayerPosition::Right) => {
-                        let paddle_start = player.paddle_position - player.paddle_width / 2.0;
-                        let paddle_end = player.paddle_position + player.paddle_width / 2.0;
-                        let paddle_x = GAME_SIZE - PADDLE_PADDING;
-
-                        let next_ball_x = ball.position.x + ball.velocity.x;
-
-                        // Check if the ball will collide with the paddle
-                        if next_ball_x > paddle_x
-                            && (ball.position.y + ball.radius) >= paddle_start
-                            && (ball.position.y - ball.radius) <= paddle_end
-                        {
-                            let hit_offset = ((ball.position.y - player.paddle_position)
-                                / (player.paddle_width / 2.0))
-                                .clamp(-1.0, 1.0);
+                        let paddle_x = board.width - PADDLE_PADDING;
+                        let Some(t) =
+                            paddle_crossing(ball.position.x, next.x, paddle_x, ball.radius, 1.0)
+                        else {
+                            continue;
+                        };
+                        let hit_y = lerp(ball.position.y, next.y, t);
+                        if !spans_paddle(hit_y, ball.radius) {
+                            continue;
+                        }
 
-                            let angle = (2.0 * PI) + hit_offset * MAX_ANGLE;
+                        let hit_offset = ((hit_y - player.paddle_position)
+                            / (player.paddle_width / 2.0))
+                            .clamp(-1.0, 1.0);
 
-                            ball.velocity.x = -BALL_SPEED * angle.cos();
-                            ball.velocity.y = BALL_SPEED * angle.sin();
+                        // angle off the paddle's normal, which points left, into the board
+                        let angle = deflection(hit_offset, bounce_jitter, &mut self.rng.0);
 
-                            ball.position.x = paddle_x - ball.radius;
+                        ball.velocity.x = -ball_speed * angle.cos();
+                        ball.velocity.y = ball_speed * angle.sin();
 
-                            ball.last_touched_by = Some(player.id);
-                        }
+                        ball.position = Vec2 {
+                            x: paddle_x - ball.radius,
+                            y: hit_y,
+                        };
                     }
-                    None => {}
+                    None => continue,
                 }
+
+                ball.last_touched_by = Some(player.id);
+                player.returns += 1;
             }
         }
     }
 }
 
+/// Angle off the paddle's normal for a hit at `hit_offset` from its center,
+/// turned at random by up to `jitter` either way but never past `MAX_ANGLE`
+fn deflection(hit_offset: f32, jitter: f32, rng: &mut StdRng) -> f32 {
+    let jitter = if jitter > 0.0 {
+        rng.random_range(-jitter..=jitter)
+    } else {
+        0.0
+    };
+    (hit_offset * MAX_ANGLE + jitter).clamp(-MAX_ANGLE, MAX_ANGLE)
+}
+
+/// Fraction of the move from `from` to `to` at which a ball of `radius` touches the paddle `line`
+/// on its way out of the board, `outward` is the sign of the direction leaving the board.
+/// A ball already touching the paddle, its center not past the line yet, hits it right away
+fn paddle_crossing(from: f32, to: f32, line: f32, radius: f32, outward: f32) -> Option<f32> {
+    // distances past the point of contact
+    let from = (from - line) * outward + radius;
+    let to = (to - line) * outward + radius;
+    (from <= radius && to > 0.0 && to > from).then(|| (-from / (to - from)).max(0.0))
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::lifecycle::{capture, format_event};
+    use crate::common::models::game_config::DEFAULT_PING_TIMEOUT;
+    use crate::common::models::game_settings::{
+        GameMode, DEFAULT_BALL_RADIUS, DEFAULT_BOARD_SIZE, DEFAULT_MAX_PLAYERS,
+    };
     use crate::common::models::player::Player;
     use crate::common::models::player::PlayerPosition;
-    use crate::common::models::Vec2;
+    use crate::common::models::{Direction, Vec2};
 
     #[test]
     fn test_new() {
@@ -417,7 +1151,7 @@ mod tests {
         assert_eq!(game.players.len(), 0);
         assert_eq!(game.state, GameState::WaitingForPlayers);
         assert_eq!(game.started_at, None);
-        assert!(game.ball.is_some());
+        assert_eq!(game.balls.len(), 1);
         assert_eq!(game.last_goal_at, None);
     }
 
@@ -433,7 +1167,7 @@ mod tests {
     #[test]
     fn test_add_player_full() {
         let mut game = Game::new();
-        for _ in 0..MAX_PLAYERS {
+        for _ in 0..DEFAULT_MAX_PLAYERS {
             let player = Player::new("Player".to_string(), false);
             game.add_player(player).unwrap();
         }
@@ -453,14 +1187,164 @@ mod tests {
     }
 
     #[test]
-    fn test_remove_player() {
-        let mut game = Game::new();
-        let player = Player::new("Player 1".to_string(), false);
+    fn test_two_player_game() {
+        let mut game = Game::with_settings(GameSettings {
+            max_players: 2,
+            ..Default::default()
+        });
+        // the top side is not part of a 2-player game
+        assert_eq!(
+            game.assign_preferred_position(Some(PlayerPosition::Top)),
+            Some(PlayerPosition::Left)
+        );
+
+        for name in ["Player 1", "Player 2"] {
+            let mut player = Player::new(name.to_string(), false);
+            player.position = game.assign_position();
+            game.add_player(player).unwrap();
+        }
+
+        let mut positions: Vec<_> = game.players.values().map(|p| p.position).collect();
+        positions.sort_by_key(|position| position.map(|p| p.to_string()));
+        assert_eq!(
+            positions,
+            vec![Some(PlayerPosition::Left), Some(PlayerPosition::Right)]
+        );
+        assert!(game.is_full());
+        assert_eq!(game.assign_position(), None);
+        let player = Player::new("Player 3".to_string(), false);
+        assert!(matches!(game.add_player(player), Err(GameError::GameFull)));
+    }
+
+    #[test]
+    fn test_three_player_game_positions() {
+        let mut game = Game::with_settings(GameSettings {
+            max_players: 3,
+            ..Default::default()
+        });
+        for _ in 0..3 {
+            let mut player = Player::new("Player".to_string(), false);
+            player.position = game.assign_position();
+            game.add_player(player).unwrap();
+        }
+
+        assert!(game.get_player_by_side(PlayerPosition::Bottom).is_none());
+        assert!(game.players.values().all(|p| p.position.is_some()));
+    }
+
+    #[test]
+    fn test_assign_preferred_position() {
+        let mut game = Game::new();
+        assert_eq!(
+            game.assign_preferred_position(Some(PlayerPosition::Left)),
+            Some(PlayerPosition::Left)
+        );
+
+        let mut player = Player::new("Player 1".to_string(), false);
+        player.position = Some(PlayerPosition::Left);
+        game.add_player(player).unwrap();
+        assert_eq!(
+            game.assign_preferred_position(Some(PlayerPosition::Left)),
+            game.assign_position()
+        );
+        assert_eq!(game.assign_preferred_position(None), game.assign_position());
+    }
+
+    #[test]
+    fn test_remove_player() {
+        let mut game = Game::new();
+        let player = Player::new("Player 1".to_string(), false);
         game.add_player(player.clone()).unwrap();
         game.remove_player(player.id);
         assert_eq!(game.players.len(), 0);
     }
 
+    #[test]
+    fn test_forfeit() {
+        let mut game = ready_game();
+        let ids: Vec<Uuid> = game.players.keys().copied().collect();
+        game.players.get_mut(&ids[1]).unwrap().score = 3;
+        game.state = GameState::Active;
+        let name = game.players[&ids[0]].name.clone();
+
+        game.forfeit(ids[0]);
+
+        assert_eq!(game.forfeited_by, Some(ids[0]));
+        assert_eq!(game.forfeited_by_name, Some(name));
+        assert_eq!(game.state, GameState::Finished);
+        assert!(game.finished_at.is_some());
+        assert_eq!(game.players.len(), 1);
+        assert!(game.players.contains_key(&ids[1]));
+    }
+
+    #[test]
+    fn test_leave_before_start_is_no_forfeit() {
+        let mut game = ready_game();
+        let id = *game.players.keys().next().unwrap();
+
+        game.forfeit(id);
+
+        assert_eq!(game.forfeited_by, None);
+        assert_eq!(game.players.len(), 1);
+    }
+
+    #[test]
+    fn test_leave_with_others_playing_on_is_no_forfeit() {
+        let mut game = Game::new();
+        for name in ["alice", "bob", "carol"] {
+            game.add_player(Player::new(name.to_string(), false))
+                .unwrap();
+        }
+        game.state = GameState::Active;
+        let id = *game.players.keys().next().unwrap();
+
+        game.forfeit(id);
+
+        assert_eq!(game.state, GameState::Active);
+        assert_eq!(game.forfeited_by, None);
+        assert_eq!(game.forfeited_by_name, None);
+        assert_eq!(game.players.len(), 2);
+    }
+
+    #[test]
+    fn test_set_name() {
+        let mut game = Game::new();
+        assert_eq!(game.name, None);
+
+        game.set_name("  Friday\n night\u{7} pong ");
+        assert_eq!(game.name.as_deref(), Some("Friday night pong"));
+
+        game.set_name(&"é".repeat(MAX_LOBBY_NAME_LENGTH + 10));
+        assert_eq!(
+            game.name.as_ref().map(|name| name.chars().count()),
+            Some(MAX_LOBBY_NAME_LENGTH)
+        );
+
+        game.set_name(" \t ");
+        assert_eq!(game.name, None);
+    }
+
+    #[test]
+    fn test_password() {
+        let mut game = Game::new();
+        assert!(game.check_password(None));
+        assert!(game.check_password(Some("anything")));
+
+        game.set_password("secret");
+        assert!(game.check_password(Some("secret")));
+        assert!(!game.check_password(Some("Secret")));
+        assert!(!game.check_password(None));
+        assert!(game.is_private);
+
+        // the hash depends on the game
+        let mut other = Game::new();
+        other.set_password("secret");
+        assert_ne!(game.password_hash, other.password_hash);
+
+        game.set_password("");
+        assert!(game.check_password(None));
+    }
+
     #[test]
     fn test_set_game_state() {
         let mut game = Game::new();
@@ -468,16 +1352,53 @@ mod tests {
         assert_eq!(game.state, GameState::Active);
     }
 
+    #[test]
+    fn test_starting_paddle_position_clamped() {
+        let start_at = |starting_paddle_position: Option<f32>| {
+            let mut game = Game::with_settings(GameSettings {
+                starting_paddle_position,
+                paddle_width: 2.0,
+                ..Default::default()
+            });
+            let player = Player::new("Player".to_string(), false);
+            let player_id = player.id;
+            game.add_player(player).unwrap();
+            game.players[&player_id].paddle_position
+        };
+
+        assert_eq!(start_at(None), DEFAULT_BOARD_SIZE / 2.0);
+        assert_eq!(start_at(Some(3.0)), 3.0);
+        assert_eq!(start_at(Some(-4.0)), 1.0);
+        assert_eq!(start_at(Some(100.0)), DEFAULT_BOARD_SIZE - 1.0);
+    }
+
     #[test]
     fn test_is_full() {
         let mut game = Game::new();
-        for _ in 0..MAX_PLAYERS {
+        for _ in 0..DEFAULT_MAX_PLAYERS {
             let player = Player::new("Player".to_string(), false);
             game.add_player(player).unwrap();
         }
         assert!(game.is_full());
     }
 
+    #[test]
+    fn test_pending_move_applied_once_per_tick() {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        let player = Player::new("Player 1".to_string(), false);
+        game.add_player(player.clone()).unwrap();
+        let expected = game.players[&player.id].paddle_position + player.paddle_delta;
+
+        game.players.get_mut(&player.id).unwrap().pending_direction = Some(Direction::Positive);
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.players[&player.id].paddle_position, expected);
+
+        // nothing queued, nothing moves
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.players[&player.id].paddle_position, expected);
+    }
+
     #[test]
     fn test_get_player() {
         let mut game = Game::new();
@@ -506,8 +1427,356 @@ mod tests {
         game.get_player_mut(&player_1.id).unwrap().is_ready = true;
         game.get_player_mut(&player_2.id).unwrap().is_ready = true;
         assert!(game.start_game().is_ok());
+        assert_eq!(game.state, GameState::Starting);
+        assert!(game.start_countdown_until.is_some());
+        assert!(game.started_at.is_none());
+    }
+
+    fn ready_game() -> Game {
+        let mut game = Game::new();
+        for name in ["Player 1", "Player 2"] {
+            let mut player = Player::new(name.to_string(), false);
+            player.is_ready = true;
+            game.add_player(player).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_start_countdown_elapses() {
+        let mut game = ready_game();
+        game.start_game().unwrap();
+
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::Starting);
+
+        game.start_countdown_until = Some(Utc::now() - chrono::Duration::milliseconds(1));
+        game.game_tick(&GameConfig::default(), 1.0);
         assert_eq!(game.state, GameState::Active);
         assert!(game.started_at.is_some());
+        assert!(game.start_countdown_until.is_none());
+    }
+
+    #[test]
+    fn test_opening_serve_toward_a_paddle() {
+        for ball_count in [1, 2] {
+            let mut game = Game::with_settings(GameSettings {
+                ball_count,
+                ..Default::default()
+            });
+            for position in [PlayerPosition::Left, PlayerPosition::Right] {
+                let mut player = Player::new(position.to_string(), false);
+                player.position = Some(position);
+                player.is_ready = true;
+                game.add_player(player).unwrap();
+            }
+            game.start_game().unwrap();
+            game.game_tick(&GameConfig::default(), 1.0);
+            game.start_countdown_until = Some(Utc::now() - chrono::Duration::milliseconds(1));
+            game.game_tick(&GameConfig::default(), 1.0);
+            assert_eq!(game.state, GameState::Active);
+
+            assert_eq!(game.balls.len(), ball_count);
+            // only the left and right sides have paddles, none of the balls goes up or down
+            for ball in &game.balls {
+                assert!(ball.velocity.x.abs() > 0.1, "{:?}", ball.velocity);
+                assert!(ball.velocity.y.abs() < 1e-6, "{:?}", ball.velocity);
+            }
+        }
+    }
+
+    fn full_ready_game(auto_start_when_full: bool) -> Game {
+        let mut game = Game::new();
+        game.settings.auto_start_when_full = auto_start_when_full;
+        while !game.is_full() {
+            let mut player = Player::new("Player".to_string(), false);
+            player.is_ready = true;
+            game.add_player(player).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_auto_start_when_full() {
+        let mut game = full_ready_game(true);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::Starting);
+
+        game.start_countdown_until = Some(Utc::now() - chrono::Duration::milliseconds(1));
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::Active);
+    }
+
+    #[test]
+    fn test_no_auto_start_without_flag_or_ready_players() {
+        let mut game = full_ready_game(false);
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+
+        let mut game = full_ready_game(true);
+        game.players.values_mut().next().unwrap().is_ready = false;
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+
+        // a seat still free
+        let mut game = ready_game();
+        game.settings.auto_start_when_full = true;
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+    }
+
+    #[test]
+    fn test_lifecycle_events() {
+        let mut game = ready_game();
+        let events = capture::events(|| {
+            game.start_game().unwrap();
+            game.game_tick(&GameConfig::default(), 1.0);
+        });
+        // only the countdown started so far
+        assert!(events.is_empty());
+
+        game.start_countdown_until = Some(Utc::now() - chrono::Duration::milliseconds(1));
+        let events = capture::events(|| game.game_tick(&GameConfig::default(), 1.0));
+        assert_eq!(
+            events,
+            vec![format_event(GameEvent::Started, game.id, None, "")]
+        );
+
+        let leaver = *game.players.keys().next().unwrap();
+        let events = capture::events(|| game.forfeit(leaver));
+        assert_eq!(events.len(), 2);
+        assert!(events[0].starts_with("event=player_left "));
+        assert!(events[1].starts_with("event=game_finished "));
+
+        // finishing again is not a second event
+        assert!(capture::events(|| game.set_game_state(GameState::Finished)).is_empty());
+    }
+
+    #[test]
+    fn test_start_countdown_cancelled_by_unready() {
+        let mut game = ready_game();
+        game.start_game().unwrap();
+
+        let player_id = *game.players.keys().next().unwrap();
+        game.get_player_mut(&player_id).unwrap().is_ready = false;
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+        assert!(game.start_countdown_until.is_none());
+        assert!(game.started_at.is_none());
+        assert!(matches!(
+            game.cancel_start(),
+            Err(GameError::InvalidStateTransition)
+        ));
+    }
+
+    #[test]
+    fn test_rematch() {
+        let mut game = Game::new();
+        let mut player = Player::new("Player 1".to_string(), false);
+        player.score = 7;
+        player.is_ready = true;
+        let bot = Player::new("bot".to_string(), true);
+        game.add_player(player.clone()).unwrap();
+        game.add_player(bot.clone()).unwrap();
+
+        assert!(matches!(
+            game.rematch(),
+            Err(GameError::InvalidStateTransition)
+        ));
+
+        game.started_at = Some(Utc::now());
+        game.last_goal_at = Some(Utc::now());
+        game.set_game_state(GameState::Finished);
+        assert!(game.rematch().is_ok());
+
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(game.players[&player.id].score, 0);
+        assert!(!game.players[&player.id].is_ready);
+        assert!(game.players[&bot.id].is_ready);
+        assert_eq!(game.started_at, None);
+        assert_eq!(game.finished_at, None);
+        assert_eq!(game.last_goal_at, None);
+        // back in the center, served at one of the players
+        assert!(game.occupied_sides().into_iter().any(|side| {
+            let mut ball = Ball::new();
+            ball.serve_toward(side);
+            game.balls == vec![ball]
+        }));
+    }
+
+    #[test]
+    fn test_rematch_after_ping_timeout() {
+        let config = GameConfig::default();
+        let mut game = ready_game();
+        let last_ping =
+            Utc::now() - chrono::Duration::milliseconds(config.ping_timeout as i64 + 1000);
+        for player in game.players.values_mut() {
+            player.ping_timestamp = Some(last_ping);
+        }
+        game.set_game_state(GameState::Finished);
+
+        // the players sat on the end screen for longer than the ping timeout
+        game.rematch().unwrap();
+        game.game_tick(&config, 1.0);
+
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+    }
+
+    #[test]
+    fn test_play_again() {
+        let mut game = ready_game();
+        assert!(matches!(
+            game.play_again(),
+            Err(GameError::InvalidStateTransition)
+        ));
+
+        game.started_at = Some(Utc::now());
+        game.start_countdown_until = Some(Utc::now());
+        game.last_goal_at = Some(Utc::now());
+        game.last_anti_stall_at = Some(Utc::now());
+        game.serving = true;
+        game.sudden_death = true;
+        game.result_recorded = true;
+        game.set_game_state(GameState::Finished);
+        game.play_again().unwrap();
+
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+        assert!(game.players.is_empty());
+        assert_eq!(game.started_at, None);
+        assert_eq!(game.start_countdown_until, None);
+        assert_eq!(game.finished_at, None);
+        assert_eq!(game.last_goal_at, None);
+        assert_eq!(game.last_anti_stall_at, None);
+        assert!(!game.serving);
+        assert!(!game.sudden_death);
+        assert!(!game.result_recorded);
+    }
+
+    fn positions(game: &Game, players: &[Uuid]) -> Vec<Option<PlayerPosition>> {
+        players.iter().map(|id| game.players[id].position).collect()
+    }
+
+    #[test]
+    fn test_rotate_positions() {
+        let mut game = Game::new();
+        let mut ids = Vec::new();
+        for position in [
+            PlayerPosition::Top,
+            PlayerPosition::Right,
+            PlayerPosition::Bottom,
+            PlayerPosition::Left,
+        ] {
+            let mut player = Player::new(position.to_string(), false);
+            player.position = Some(position);
+            ids.push(player.id);
+            game.players.insert(player.id, player);
+        }
+
+        game.rotate_positions();
+        assert_eq!(
+            positions(&game, &ids),
+            vec![
+                Some(PlayerPosition::Right),
+                Some(PlayerPosition::Bottom),
+                Some(PlayerPosition::Left),
+                Some(PlayerPosition::Top),
+            ]
+        );
+
+        for _ in 0..3 {
+            game.rotate_positions();
+        }
+        assert_eq!(game.players[&ids[0]].position, Some(PlayerPosition::Top));
+    }
+
+    /// Adds a player on the side the server would hand out, returns their id
+    fn seat(game: &mut Game, name: &str, is_ai: bool) -> Uuid {
+        let mut player = Player::new(name.to_string(), is_ai);
+        player.position = game.assign_position();
+        let id = player.id;
+        game.add_player(player).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_rotate_positions_between_occupied_sides() {
+        // Top and Bottom out of the four sides
+        let mut game = Game::new();
+        let ids = [
+            seat(&mut game, "first", false),
+            seat(&mut game, "second", false),
+        ];
+        let before = positions(&game, &ids);
+        assert_eq!(
+            before,
+            vec![Some(PlayerPosition::Top), Some(PlayerPosition::Bottom)]
+        );
+
+        // the two of them swap sides, nobody ends up facing an empty one
+        game.rotate_positions();
+        assert_eq!(positions(&game, &ids), vec![before[1], before[0]]);
+        game.rotate_positions();
+        assert_eq!(positions(&game, &ids), before);
+    }
+
+    #[test]
+    fn test_rematch_rotates_seats() {
+        let mut game = Game::with_settings(GameSettings {
+            max_players: 2,
+            rotate_seats_on_rematch: true,
+            ..Default::default()
+        });
+        let player_id = seat(&mut game, "player", false);
+        seat(&mut game, "bot", true);
+        assert_eq!(
+            game.players[&player_id].position,
+            Some(PlayerPosition::Left)
+        );
+
+        game.set_game_state(GameState::Finished);
+        game.rematch().unwrap();
+        assert_eq!(
+            game.players[&player_id].position,
+            Some(PlayerPosition::Right)
+        );
+    }
+
+    /// A ready human and a bot, added to a game with `bots_auto_ready`
+    fn game_with_bot(bots_auto_ready: bool) -> Game {
+        let mut game = Game::with_settings(GameSettings {
+            bots_auto_ready,
+            ..Default::default()
+        });
+        let mut human = Player::new("human".to_string(), false);
+        human.is_ready = true;
+        game.add_player(human).unwrap();
+        game.add_player(Player::new("bot".to_string(), true))
+            .unwrap();
+        game
+    }
+
+    #[test]
+    fn test_bots_auto_ready() {
+        let mut game = game_with_bot(true);
+        assert!(game.start_game().is_ok());
+    }
+
+    #[test]
+    fn test_bots_wait_for_host() {
+        let mut game = game_with_bot(false);
+        assert!(matches!(game.start_game(), Err(GameError::PlayersNotReady)));
+
+        game.toggle_bots_ready();
+        assert!(game.start_game().is_ok());
+
+        // a rematch keeps them waiting again
+        game.set_game_state(GameState::Finished);
+        game.rematch().unwrap();
+        assert!(game.players.values().all(|p| !p.is_ai || !p.is_ready));
     }
 
     #[test]
@@ -540,46 +1809,1097 @@ mod tests {
         let position = PlayerPosition::Top;
         player.position = Some(position);
         game.add_player(player.clone()).unwrap();
+        let mut opponent = Player::new("Player 2".to_string(), false);
+        opponent.position = Some(PlayerPosition::Bottom);
+        game.add_player(opponent).unwrap();
 
-        if let Some(mut ball) = game.ball.clone() {
+        if let Some(mut ball) = game.balls.first().cloned() {
             ball.last_touched_by = Some(player.id);
-            game.ball = Some(ball);
+            game.balls = vec![ball];
         } else {
             panic!("Ball is None");
         }
-        game.goal_action(position); // same side
+        game.goal_action(0, position); // same side
         assert_eq!(game.players.get(&player.id).unwrap().score, 0); // cant score on yourself
 
-        if let Some(mut ball) = game.ball.clone() {
+        if let Some(mut ball) = game.balls.first().cloned() {
             ball.last_touched_by = Some(player.id);
-            game.ball = Some(ball);
+            game.balls = vec![ball];
         } else {
             panic!("Ball is None");
         }
-        game.goal_action(PlayerPosition::Bottom); // opposite side
+        game.goal_action(0, PlayerPosition::Bottom); // opposite side
         assert_eq!(game.players.get(&player.id).unwrap().score, 1);
     }
 
+    #[test]
+    fn test_unique_name() {
+        let mut game = Game::new();
+        assert_eq!(game.unique_name("test"), "test");
+
+        game.add_player(Player::new("test".to_string(), false))
+            .unwrap();
+        assert_eq!(game.unique_name("test"), "test_2");
+
+        game.add_player(Player::new("test_2".to_string(), false))
+            .unwrap();
+        assert_eq!(game.unique_name("test"), "test_3");
+        assert_eq!(game.unique_name("other"), "other");
+    }
+
+    #[test]
+    fn test_escaped_ball_reset_to_center() {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        let mut left = Player::new("Left".to_string(), false);
+        left.position = Some(PlayerPosition::Left);
+        let mut right = Player::new("Right".to_string(), false);
+        right.position = Some(PlayerPosition::Right);
+        game.add_player(left).unwrap();
+        game.add_player(right).unwrap();
+        let center = game.settings.board_size / 2.0;
+
+        for position in [
+            Vec2 { x: -50.0, y: 3.0 },
+            Vec2 {
+                x: center,
+                y: f32::NAN,
+            },
+        ] {
+            game.balls[0].position = position;
+            game.game_tick(&GameConfig::default(), 1.0);
+
+            let ball = &game.balls[0];
+            assert_eq!(
+                ball.position,
+                Vec2 {
+                    x: center,
+                    y: center
+                }
+            );
+            // no goal was given for it
+            assert!(game.players.values().all(|player| player.score == 0));
+        }
+    }
+
+    #[test]
+    fn test_goal_serves_to_scored_on_player() {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        let mut top = Player::new("Top".to_string(), false);
+        top.position = Some(PlayerPosition::Top);
+        let mut left = Player::new("Left".to_string(), false);
+        left.position = Some(PlayerPosition::Left);
+        game.add_player(top).unwrap();
+        game.add_player(left).unwrap();
+
+        game.goal_action(0, PlayerPosition::Left);
+
+        let ball = &game.balls[0];
+        let center = game.settings.board_size / 2.0;
+        assert_eq!(
+            ball.position,
+            Vec2 {
+                x: center,
+                y: center
+            }
+        );
+        assert!(ball.velocity.x < 0.0);
+        assert_eq!(ball.velocity.y, 0.0);
+        assert!(game.serving);
+        assert!(GameDto::from(game.clone()).serving);
+
+        // the ball waits in the center until the goal timeout passed
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert!(game.serving);
+        assert_eq!(
+            game.balls[0].position,
+            Vec2 {
+                x: center,
+                y: center
+            }
+        );
+
+        let no_pause = GameConfig {
+            goal_timeout: 0,
+            ..Default::default()
+        };
+        game.game_tick(&no_pause, 1.0);
+        assert!(!game.serving);
+        assert!(game.balls[0].position.x < center);
+    }
+
+    #[test]
+    fn test_goal_on_occupied_side() {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        let mut scorer = Player::new("Scorer".to_string(), false);
+        scorer.position = Some(PlayerPosition::Top);
+        let mut defender = Player::new("Defender".to_string(), false);
+        defender.position = Some(PlayerPosition::Bottom);
+        game.add_player(scorer.clone()).unwrap();
+        game.add_player(defender.clone()).unwrap();
+        game.players.get_mut(&defender.id).unwrap().paddle_position = 1.0;
+
+        // ball flies past the defender's paddle
+        let ball = &mut game.balls[0];
+        ball.position = Vec2 { x: 8.0, y: 10.2 };
+        ball.velocity = Vec2 { x: 0.0, y: 0.15 };
+        ball.last_touched_by = Some(scorer.id);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.players[&scorer.id].score, 1);
+        assert!(game.last_goal_at.is_some());
+    }
+
+    /// Positions of the balls over `ticks` of a bot match, which keeps scoring on itself
+    fn bot_match_trajectory(seed: u64, ticks: usize) -> Vec<Vec<Vec2>> {
+        let mut game = Game::with_settings(GameSettings {
+            seed: Some(seed),
+            serve: ServeTarget::Random,
+            max_score: 100,
+            stall_timeout: 20000,
+            ..Default::default()
+        });
+        for name in ["a", "b", "c", "d"] {
+            let mut bot = Player::new(name.to_string(), true);
+            bot.position = game.assign_position();
+            game.add_player(bot).unwrap();
+        }
+        game.state = GameState::Active;
+        // long stalled already, the anti-stall nudge comes right away
+        game.started_at = Some(Utc::now() - chrono::Duration::hours(1));
+        // no pause after goals, it would depend on the wall clock
+        let config = GameConfig {
+            goal_timeout: 0,
+            ..Default::default()
+        };
+
+        let trajectory = (0..ticks)
+            .map(|_| {
+                game.game_tick(&config, 1.0);
+                game.balls
+                    .iter()
+                    .map(|ball| ball.position.clone())
+                    .collect()
+            })
+            .collect();
+        assert!(game.anti_stall_count > 0);
+        trajectory
+    }
+
+    #[test]
+    fn test_same_seed_same_trajectory() {
+        let trajectory = bot_match_trajectory(7, 3000);
+        assert_eq!(trajectory, bot_match_trajectory(7, 3000));
+        assert_ne!(trajectory, bot_match_trajectory(8, 3000));
+    }
+
+    #[test]
+    fn test_seed_from_settings() {
+        let game = Game::with_settings(GameSettings {
+            seed: Some(1234),
+            ..Default::default()
+        });
+        assert_eq!(game.seed, 1234);
+    }
+
+    /// Lobby with warmup, a scorer on the top and a defender on the bottom side
+    fn warmup_game() -> (Game, Uuid, Uuid) {
+        let mut game = Game::with_settings(GameSettings {
+            warmup: true,
+            ..Default::default()
+        });
+        let mut scorer = Player::new("Scorer".to_string(), false);
+        scorer.position = Some(PlayerPosition::Top);
+        let mut defender = Player::new("Defender".to_string(), false);
+        defender.position = Some(PlayerPosition::Bottom);
+        game.add_player(scorer.clone()).unwrap();
+        game.add_player(defender.clone()).unwrap();
+        (game, scorer.id, defender.id)
+    }
+
+    #[test]
+    fn test_warmup_moves_paddles_and_ball() {
+        let (mut game, scorer_id, _) = warmup_game();
+        let paddle = game.players[&scorer_id].paddle_position;
+        let ball = game.balls[0].position.clone();
+        game.players.get_mut(&scorer_id).unwrap().pending_direction = Some(Direction::Positive);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+        assert!(game.players[&scorer_id].paddle_position > paddle);
+        assert_ne!(game.balls[0].position, ball);
+
+        // without the warmup the lobby stands still
+        let (mut game, scorer_id, _) = warmup_game();
+        game.settings.warmup = false;
+        game.players.get_mut(&scorer_id).unwrap().pending_direction = Some(Direction::Positive);
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.players[&scorer_id].paddle_position, paddle);
+        assert_eq!(game.balls[0].position, ball);
+    }
+
+    #[test]
+    fn test_warmup_goal_not_scored() {
+        let (mut game, scorer_id, defender_id) = warmup_game();
+        game.players.get_mut(&defender_id).unwrap().paddle_position = 1.0;
+        let ball = &mut game.balls[0];
+        ball.position = Vec2 { x: 8.0, y: 10.2 };
+        ball.velocity = Vec2 { x: 0.0, y: 0.15 };
+        ball.last_touched_by = Some(scorer_id);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        // the ball is only put back into play
+        assert!(game.players.values().all(|player| player.score == 0));
+        assert!(game.last_goal_at.is_none());
+        assert!(game.balls[0].position.y < 10.0);
+    }
+
+    /// Active game with a goal gap of 2 centered on the bottom side, the defender's paddle out of the way
+    fn goal_gap_game() -> (Game, Uuid) {
+        let mut game = Game::with_settings(GameSettings {
+            goal_width: Some(2.0),
+            ..Default::default()
+        });
+        game.state = GameState::Active;
+        let mut scorer = Player::new("Scorer".to_string(), false);
+        scorer.position = Some(PlayerPosition::Top);
+        let mut defender = Player::new("Defender".to_string(), false);
+        defender.position = Some(PlayerPosition::Bottom);
+        game.add_player(scorer.clone()).unwrap();
+        game.add_player(defender.clone()).unwrap();
+        game.players.get_mut(&defender.id).unwrap().paddle_position = 9.0;
+        game.balls[0].velocity = Vec2 { x: 0.0, y: 0.15 };
+        game.balls[0].last_touched_by = Some(scorer.id);
+        (game, scorer.id)
+    }
+
+    #[test]
+    fn test_wall_outside_goal_gap_reflects() {
+        let (mut game, scorer_id) = goal_gap_game();
+        game.balls[0].position = Vec2 { x: 3.0, y: 10.2 };
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert!(game.balls[0].velocity.y < 0.0);
+        assert!(game.balls[0].position.y < 10.0);
+        assert_eq!(game.players[&scorer_id].score, 0);
+        assert!(game.last_goal_at.is_none());
+    }
+
+    #[test]
+    fn test_goal_inside_goal_gap() {
+        let (mut game, scorer_id) = goal_gap_game();
+        game.balls[0].position = Vec2 { x: 5.5, y: 10.2 };
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.players[&scorer_id].score, 1);
+        assert!(game.last_goal_at.is_some());
+    }
+
+    #[test]
+    fn test_no_goal_on_empty_side() {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        let mut player = Player::new("Player".to_string(), false);
+        player.position = Some(PlayerPosition::Top);
+        game.add_player(player.clone()).unwrap();
+
+        // nobody defends the right side, so the ball bounces off the wall
+        let ball = &mut game.balls[0];
+        ball.position = Vec2 { x: 9.9, y: 5.0 };
+        ball.velocity = Vec2 { x: 0.15, y: 0.0 };
+        ball.last_touched_by = Some(player.id);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        let ball = &game.balls[0];
+        assert!(ball.velocity.x < 0.0);
+        assert_eq!(game.players[&player.id].score, 0);
+        assert!(game.last_goal_at.is_none());
+
+        // scoring directly on an empty side is ignored as well
+        game.goal_action(0, PlayerPosition::Right);
+        assert_eq!(game.players[&player.id].score, 0);
+    }
+
+    /// Active lives mode game with a player on each of the `positions`
+    fn lives_game(lives: u32, positions: &[PlayerPosition]) -> (Game, Vec<Uuid>) {
+        let mut game = Game::with_settings(GameSettings {
+            mode: GameMode::Lives,
+            lives,
+            ..Default::default()
+        });
+        let mut ids = Vec::new();
+        for (index, position) in positions.iter().enumerate() {
+            let mut player = Player::new(format!("Player {}", index + 1), false);
+            player.position = Some(*position);
+            ids.push(player.id);
+            game.add_player(player).unwrap();
+        }
+        game.state = GameState::Active;
+        game.started_at = Some(Utc::now());
+        (game, ids)
+    }
+
+    #[test]
+    fn test_player_eliminated_at_zero_lives() {
+        let (mut game, ids) = lives_game(
+            2,
+            &[
+                PlayerPosition::Top,
+                PlayerPosition::Bottom,
+                PlayerPosition::Left,
+            ],
+        );
+        assert!(game.players.values().all(|player| player.lives == 2));
+
+        game.balls[0].last_touched_by = Some(ids[0]);
+        game.goal_action(0, PlayerPosition::Bottom);
+        assert_eq!(game.players[&ids[1]].lives, 1);
+        assert_eq!(game.players[&ids[1]].position, Some(PlayerPosition::Bottom));
+
+        game.balls[0].last_touched_by = Some(ids[1]);
+        game.goal_action(0, PlayerPosition::Bottom); // an own goal costs a life as well
+        assert_eq!(game.players[&ids[1]].lives, 0);
+        assert_eq!(game.players[&ids[1]].position, None);
+        assert_eq!(game.players[&ids[1]].score, 0);
+        assert_eq!(game.players[&ids[0]].score, 1);
+        assert!(game.get_player_by_side(PlayerPosition::Bottom).is_none());
+
+        // the empty side is a wall now, two players are still standing
+        game.last_goal_at = None;
+        game.balls[0].position = Vec2 { x: 5.0, y: 9.9 };
+        game.balls[0].velocity = Vec2 { x: 0.0, y: 0.15 };
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert!(game.balls[0].velocity.y < 0.0);
+        assert_eq!(game.state, GameState::Active);
+
+        // a rematch brings the eliminated player back
+        game.set_game_state(GameState::Finished);
+        game.rematch().unwrap();
+        assert_eq!(game.players[&ids[1]].lives, 2);
+        assert_eq!(game.players[&ids[1]].position, Some(PlayerPosition::Bottom));
+    }
+
+    #[test]
+    fn test_lives_game_ends_with_survivor() {
+        let (mut game, ids) = lives_game(1, &[PlayerPosition::Top, PlayerPosition::Bottom]);
+
+        let board_size = game.settings.board_size;
+        game.players.get_mut(&ids[1]).unwrap().paddle_position = 1.0; // far away from the goal
+        game.balls[0].position = Vec2 {
+            x: board_size - 1.0,
+            y: board_size + 0.2,
+        };
+        game.balls[0].velocity = Vec2 { x: 0.0, y: 0.15 };
+        game.balls[0].last_touched_by = Some(ids[0]);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.state, GameState::Finished);
+        assert_eq!(game.players[&ids[1]].lives, 0);
+        assert_eq!(game.players[&ids[0]].lives, 1);
+        assert_eq!(game.unique_leader(), Some(ids[0]));
+        // the survivor comes first whatever the scores
+        let survivor = format!("{}=1", game.players[&ids[0]].name);
+        assert!(game.final_scores().starts_with(&survivor));
+    }
+
     #[test]
     fn test_check_players_health() {
         let mut game = Game::new();
         let mut player = Player::new("Player 1".to_string(), false);
         player.ping_timestamp = Some(Utc::now());
         game.add_player(player.clone()).unwrap();
-        game.check_players_health();
+        game.check_players_health(&GameConfig::default());
         assert_eq!(game.players.len(), 1);
         game.players.get_mut(&player.id).unwrap().ping_timestamp =
-            Some(Utc::now() - chrono::Duration::milliseconds((PING_TIMEOUT * 10) as i64));
-        game.check_players_health();
+            Some(Utc::now() - chrono::Duration::milliseconds((DEFAULT_PING_TIMEOUT * 10) as i64));
+        game.check_players_health(&GameConfig::default());
         assert_eq!(game.players.len(), 0);
     }
 
+    #[test]
+    fn test_custom_ping_timeout() {
+        let config = GameConfig {
+            ping_timeout: 100,
+            ..Default::default()
+        };
+        let mut game = Game::new();
+        let player = Player::new("Player 1".to_string(), false);
+        game.add_player(player.clone()).unwrap();
+
+        // well within the default timeout, but past the custom one
+        game.players.get_mut(&player.id).unwrap().ping_timestamp =
+            Some(Utc::now() - chrono::Duration::milliseconds(500));
+        game.check_players_health(&GameConfig::default());
+        assert_eq!(game.players.len(), 1);
+
+        game.check_players_health(&config);
+        assert_eq!(game.players.len(), 0);
+    }
+
+    #[test]
+    fn test_custom_game_delete_timeout() {
+        let config = GameConfig {
+            game_delete_timeout: 100,
+            ..Default::default()
+        };
+        let mut game = Game::new();
+        game.finished_at = Some(Utc::now() - chrono::Duration::milliseconds(500));
+
+        assert!(!game.should_delete_game(&GameConfig::default()));
+        assert!(game.should_delete_game(&config));
+    }
+
+    #[test]
+    fn test_idle_lobby_deleted_after_timeout() {
+        let config = GameConfig {
+            idle_lobby_timeout: 100,
+            ..Default::default()
+        };
+        let mut game = Game::new();
+        assert!(!game.should_delete_game(&config));
+
+        game.created_at = Utc::now() - chrono::Duration::milliseconds(500);
+        assert!(!game.should_delete_game(&GameConfig::default()));
+        assert!(game.should_delete_game(&config));
+
+        // bots alone do not keep it around
+        game.add_player(Player::new("bot".to_string(), true))
+            .unwrap();
+        assert!(game.should_delete_game(&config));
+
+        // a human waiting in it does
+        game.add_player(Player::new("human".to_string(), false))
+            .unwrap();
+        assert!(!game.should_delete_game(&config));
+    }
+
+    #[test]
+    fn test_bot_only_game_deleted_right_away() {
+        let mut game = Game::new();
+        let human = Player::new("human".to_string(), false);
+        let human_id = human.id;
+        game.add_player(human).unwrap();
+        game.add_player(Player::new("bot".to_string(), true))
+            .unwrap();
+        game.add_player(Player::new("bot".to_string(), true))
+            .unwrap();
+        game.state = GameState::Active;
+
+        game.remove_player(human_id);
+
+        assert_eq!(game.state, GameState::Finished);
+        assert!(game.should_delete_game(&GameConfig::default()));
+    }
+
+    #[test]
+    fn test_finished_game_with_humans_kept() {
+        let mut game = Game::new();
+        let leaving = Player::new("leaving".to_string(), false);
+        let leaving_id = leaving.id;
+        game.add_player(leaving).unwrap();
+        game.add_player(Player::new("staying".to_string(), false))
+            .unwrap();
+        game.add_player(Player::new("bot".to_string(), true))
+            .unwrap();
+        game.state = GameState::Active;
+
+        game.remove_player(leaving_id);
+
+        // the remaining human still gets to see the results
+        assert_eq!(game.state, GameState::Finished);
+        assert!(!game.should_delete_game(&GameConfig::default()));
+    }
+
+    #[test]
+    fn test_check_stall() {
+        let mut game = Game::new();
+        game.settings.stall_timeout = 20000;
+        game.state = GameState::Active;
+        game.started_at = Some(Utc::now());
+        game.balls[0].velocity = Vec2 { x: 0.0, y: 0.125 };
+
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.balls[0].velocity.x, 0.0);
+        assert_eq!(game.anti_stall_count, 0);
+
+        game.started_at = Some(Utc::now() - chrono::Duration::milliseconds(20001));
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        let velocity = &game.balls[0].velocity;
+        assert_ne!(velocity.x, 0.0);
+        assert_eq!(game.anti_stall_count, 1);
+        assert!(game.last_anti_stall_at.is_some());
+
+        // the window restarts after a nudge
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.anti_stall_count, 1);
+    }
+
+    #[test]
+    fn test_check_stall_disabled() {
+        // off unless the lobby asks for it
+        let mut game = Game::new();
+        assert_eq!(game.settings.stall_timeout, 0);
+        game.started_at = Some(Utc::now() - chrono::Duration::hours(1));
+        game.check_stall();
+        assert_eq!(game.anti_stall_count, 0);
+    }
+
     #[test]
     fn test_is_ball_in_safe_zone() {
         let ball = Ball::new();
-        assert!(Game::is_ball_in_safe_zone(&ball, PADDLE_PADDING));
+        assert!(Game::is_ball_in_safe_zone(
+            &ball,
+            PADDLE_PADDING,
+            BoardSize::default()
+        ));
         let mut ball = Ball::new();
         ball.position = Vec2 { x: 0.0, y: 0.0 };
-        assert!(!Game::is_ball_in_safe_zone(&ball, PADDLE_PADDING));
+        assert!(!Game::is_ball_in_safe_zone(
+            &ball,
+            PADDLE_PADDING,
+            BoardSize::default()
+        ));
+    }
+
+    #[test]
+    fn test_with_settings() {
+        let game = Game::with_settings(GameSettings {
+            board_size: 20.0,
+            ball_radius: 0.5,
+            ..Default::default()
+        });
+        assert_eq!(game.settings.board_size, 20.0);
+        let ball = &game.balls[0];
+        assert_eq!(ball.position, Vec2 { x: 10.0, y: 10.0 });
+        assert_eq!(ball.radius, 0.5);
+    }
+
+    #[test]
+    fn test_collision_scales_with_board_size() {
+        let mut game = Game::with_settings(GameSettings {
+            board_size: 20.0,
+            ..Default::default()
+        });
+        let mut player = Player::new("Player 1".to_string(), false);
+        player.position = Some(PlayerPosition::Bottom);
+        game.add_player(player.clone()).unwrap();
+        assert_eq!(game.players[&player.id].paddle_position, 10.0);
+
+        // a ball near the default board's bottom edge is still in the safe zone
+        let mut ball = Ball::centered(BoardSize::square(20.0));
+        ball.position = Vec2 { x: 10.0, y: 9.9 };
+        assert!(Game::is_ball_in_safe_zone(
+            &ball,
+            PADDLE_PADDING,
+            BoardSize::square(20.0)
+        ));
+
+        ball.position = Vec2 { x: 10.0, y: 19.7 };
+        ball.velocity = Vec2 { x: 0.0, y: 0.15 };
+        game.balls = vec![ball];
+        game.check_collision(1.0);
+
+        let ball = &game.balls[0];
+        assert!(ball.velocity.y < 0.0);
+        assert_eq!(ball.last_touched_by, Some(player.id));
+    }
+
+    #[test]
+    fn test_collision_respects_board_width_and_height() {
+        let mut game = Game::with_settings(GameSettings {
+            board_width: Some(20.0),
+            board_height: Some(10.0),
+            ..Default::default()
+        });
+        let board = game.board();
+        let mut bottom = Player::new("Bottom".to_string(), false);
+        bottom.position = Some(PlayerPosition::Bottom);
+        game.add_player(bottom.clone()).unwrap();
+        let mut right = Player::new("Right".to_string(), false);
+        right.position = Some(PlayerPosition::Right);
+        game.add_player(right.clone()).unwrap();
+
+        // every paddle starts in the middle of its own side
+        assert_eq!(game.players[&bottom.id].paddle_position, 10.0);
+        assert_eq!(game.players[&right.id].paddle_position, 5.0);
+        assert_eq!(game.balls[0].position, Vec2 { x: 10.0, y: 5.0 });
+
+        // far from the short sides, but not from the long ones
+        let mut ball = Ball::centered(board);
+        ball.position = Vec2 { x: 15.0, y: 5.0 };
+        assert!(Game::is_ball_in_safe_zone(&ball, PADDLE_PADDING, board));
+        assert!(!Game::is_ball_in_safe_zone(
+            &ball,
+            PADDLE_PADDING,
+            BoardSize::square(10.0)
+        ));
+        ball.position = Vec2 { x: 10.0, y: 9.9 };
+        assert!(!Game::is_ball_in_safe_zone(&ball, PADDLE_PADDING, board));
+
+        ball.position = Vec2 { x: 10.0, y: 9.7 };
+        ball.velocity = Vec2 { x: 0.0, y: 0.15 };
+        game.balls = vec![ball.clone()];
+        game.check_collision(1.0);
+        assert!(game.balls[0].velocity.y < 0.0);
+        assert_eq!(game.balls[0].last_touched_by, Some(bottom.id));
+
+        ball.position = Vec2 { x: 19.7, y: 5.0 };
+        ball.velocity = Vec2 { x: 0.15, y: 0.0 };
+        game.balls = vec![ball];
+        game.check_collision(1.0);
+        assert!(game.balls[0].velocity.x < 0.0);
+        assert_eq!(game.balls[0].last_touched_by, Some(right.id));
+    }
+
+    /// Hits the center of a bottom paddle `hits` times, returns each bounce's angle off the normal
+    fn bounce_angles(settings: GameSettings, hits: usize) -> Vec<f32> {
+        let mut game = Game::with_settings(settings);
+        let mut player = Player::new("Player 1".to_string(), false);
+        player.position = Some(PlayerPosition::Bottom);
+        game.add_player(player).unwrap();
+
+        (0..hits)
+            .map(|_| {
+                let mut ball = Ball::centered(game.board());
+                ball.position = Vec2 { x: 5.0, y: 9.7 };
+                ball.velocity = Vec2 { x: 0.0, y: 0.15 };
+                game.balls = vec![ball];
+                game.check_collision(1.0);
+                let velocity = &game.balls[0].velocity;
+                velocity.x.atan2(-velocity.y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bounce_without_jitter_is_deterministic() {
+        for seed in [1, 2] {
+            let angles = bounce_angles(
+                GameSettings {
+                    seed: Some(seed),
+                    ..Default::default()
+                },
+                20,
+            );
+            assert!(angles.iter().all(|angle| angle.abs() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_bounce_jitter_stays_within_bound() {
+        let settings = GameSettings {
+            bounce_jitter: 0.2,
+            seed: Some(7),
+            ..Default::default()
+        };
+        let angles = bounce_angles(settings.clone(), 50);
+
+        assert!(angles.iter().all(|angle| angle.abs() <= 0.2 + 1e-6));
+        assert!(angles.iter().any(|angle| angle.abs() > 1e-3));
+        assert!(angles.windows(2).any(|pair| pair[0] != pair[1]));
+        // the same seed bounces the same way
+        assert_eq!(bounce_angles(settings, 50), angles);
+    }
+
+    /// Hits the paddle on `position` at every tenth of its half width, returns the velocities after
+    fn side_paddle_hits(position: PlayerPosition) -> Vec<(f32, Vec2)> {
+        let mut velocities = Vec::new();
+        for step in -10..=10 {
+            let hit_offset = step as f32 / 10.0;
+            let mut game = Game::new();
+            let mut player = Player::new("Player".to_string(), false);
+            player.position = Some(position);
+            game.add_player(player.clone()).unwrap();
+
+            let board_size = game.settings.board_size;
+            let paddle = &game.players[&player.id];
+            let (x, velocity_x) = match position {
+                PlayerPosition::Left => (PADDLE_PADDING + 0.1, -0.15),
+                _ => (board_size - PADDLE_PADDING - 0.1, 0.15),
+            };
+            let mut ball = Ball::centered(BoardSize::square(board_size));
+            ball.position = Vec2 {
+                x,
+                y: paddle.paddle_position + hit_offset * paddle.paddle_width / 2.0,
+            };
+            ball.velocity = Vec2 {
+                x: velocity_x,
+                y: 0.0,
+            };
+            game.balls = vec![ball];
+            game.check_collision(1.0);
+
+            assert_eq!(game.balls[0].last_touched_by, Some(player.id));
+            velocities.push((hit_offset, game.balls[0].velocity.clone()));
+        }
+        velocities
+    }
+
+    #[test]
+    fn test_left_paddle_sends_ball_right() {
+        let ball_speed = Game::new().settings.ball_speed;
+        for (hit_offset, velocity) in side_paddle_hits(PlayerPosition::Left) {
+            assert!(velocity.x > 0.0, "offset {}: {:?}", hit_offset, velocity);
+            // steepest at the edges, never more than the max angle off the normal
+            assert!(velocity.x >= ball_speed * MAX_ANGLE.cos() - 1e-5);
+            assert!((velocity.x.hypot(velocity.y) - ball_speed).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_right_paddle_sends_ball_left() {
+        let ball_speed = Game::new().settings.ball_speed;
+        for (hit_offset, velocity) in side_paddle_hits(PlayerPosition::Right) {
+            assert!(velocity.x < 0.0, "offset {}: {:?}", hit_offset, velocity);
+            assert!(-velocity.x >= ball_speed * MAX_ANGLE.cos() - 1e-5);
+            assert!((velocity.x.hypot(velocity.y) - ball_speed).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_deflection_counts_return() {
+        let mut game = Game::new();
+        let mut left = Player::new("Left".to_string(), false);
+        left.position = Some(PlayerPosition::Left);
+        let mut right = Player::new("Right".to_string(), false);
+        right.position = Some(PlayerPosition::Right);
+        game.add_player(left.clone()).unwrap();
+        game.add_player(right.clone()).unwrap();
+
+        let board_size = game.settings.board_size;
+        let mut ball = Ball::centered(BoardSize::square(board_size));
+        ball.position = Vec2 {
+            x: PADDLE_PADDING + 0.1,
+            y: board_size / 2.0,
+        };
+        ball.velocity = Vec2 { x: -0.15, y: 0.0 };
+        game.balls = vec![ball];
+        game.check_collision(1.0);
+
+        assert_eq!(game.balls[0].last_touched_by, Some(left.id));
+        assert_eq!(game.players[&left.id].returns, 1);
+        assert_eq!(game.players[&right.id].returns, 0);
+
+        // a ball the paddle misses is no return
+        let mut ball = Ball::centered(BoardSize::square(board_size));
+        ball.position = Vec2 {
+            x: board_size - PADDLE_PADDING - 0.1,
+            y: 1.0,
+        };
+        ball.velocity = Vec2 { x: 0.15, y: 0.0 };
+        game.balls = vec![ball];
+        game.check_collision(1.0);
+
+        assert_eq!(game.players[&right.id].returns, 0);
+    }
+
+    #[test]
+    fn test_large_ball_hits_paddle_earlier() {
+        let hits_top = |radius: f32| {
+            let mut game = Game::with_settings(GameSettings {
+                ball_radius: radius,
+                ..Default::default()
+            });
+            let mut top = Player::new("Top".to_string(), false);
+            top.position = Some(PlayerPosition::Top);
+            game.add_player(top.clone()).unwrap();
+
+            let ball = &mut game.balls[0];
+            ball.position = Vec2 {
+                x: game.players[&top.id].paddle_position,
+                y: PADDLE_PADDING + 0.6,
+            };
+            ball.velocity = Vec2 { x: 0.0, y: -0.2 };
+            game.check_collision(1.0);
+            (game.balls[0].last_touched_by == Some(top.id)).then(|| game.balls[0].position.y)
+        };
+
+        // the small ball's edge stays short of the paddle, the large one's reaches it
+        assert_eq!(hits_top(DEFAULT_BALL_RADIUS), None);
+        assert_eq!(hits_top(0.5), Some(PADDLE_PADDING + 0.5));
+    }
+
+    #[test]
+    fn test_collision_scales_with_dt() {
+        let mut game = Game::new();
+        let mut player = Player::new("Bottom".to_string(), false);
+        player.position = Some(PlayerPosition::Bottom);
+        game.add_player(player.clone()).unwrap();
+
+        let board_size = game.settings.board_size;
+        let mut ball = Ball::centered(BoardSize::square(board_size));
+        ball.position = Vec2 {
+            x: board_size / 2.0,
+            y: board_size - 0.36 - ball.radius, // just outside of the safe zone
+        };
+        ball.velocity = Vec2 { x: 0.0, y: 0.1 };
+        game.balls = vec![ball];
+
+        // one tick stays in front of the paddle, a twice as long one would pass it
+        game.check_collision(1.0);
+        assert_eq!(game.balls[0].last_touched_by, None);
+        game.check_collision(2.0);
+        assert_eq!(game.balls[0].last_touched_by, Some(player.id));
+        assert!(game.balls[0].velocity.y < 0.0);
+    }
+
+    #[test]
+    fn test_fast_ball_does_not_tunnel_through_paddle() {
+        let mut game = Game::new();
+        game.state = GameState::Active;
+        let mut left = Player::new("Left".to_string(), false);
+        left.position = Some(PlayerPosition::Left);
+        let mut right = Player::new("Right".to_string(), false);
+        right.position = Some(PlayerPosition::Right);
+        game.add_player(left.clone()).unwrap();
+        game.add_player(right).unwrap();
+
+        let board_size = game.settings.board_size;
+        let mut ball = Ball::centered(BoardSize::square(board_size));
+        // in the safe zone after the first move and past the goal after the second
+        ball.position = Vec2 {
+            x: 5.0,
+            y: board_size / 2.0,
+        };
+        ball.velocity = Vec2 { x: -3.0, y: 0.0 };
+        game.balls = vec![ball];
+
+        for _ in 0..3 {
+            game.game_tick(&GameConfig::default(), 1.0);
+        }
+
+        assert!(game.players.values().all(|player| player.score == 0));
+        assert_eq!(game.balls[0].last_touched_by, Some(left.id));
+        assert!(game.balls[0].velocity.x > 0.0);
+    }
+
+    #[test]
+    fn test_swept_collision_uses_crossing_point() {
+        let mut game = Game::new();
+        let mut top = Player::new("Top".to_string(), false);
+        top.position = Some(PlayerPosition::Top);
+        game.add_player(top.clone()).unwrap();
+
+        let board_size = game.settings.board_size;
+        let paddle = &game.players[&top.id];
+        let (paddle_position, paddle_width) = (paddle.paddle_position, paddle.paddle_width);
+
+        // beside the paddle now, touching it right in the middle of it
+        let mut ball = Ball::centered(BoardSize::square(board_size));
+        ball.position = Vec2 {
+            x: paddle_position + paddle_width * 2.0,
+            y: PADDLE_PADDING + ball.radius + 1.0,
+        };
+        ball.velocity = Vec2 {
+            x: -paddle_width * 4.0,
+            y: -2.0,
+        };
+        game.balls = vec![ball];
+        game.check_collision(1.0);
+
+        let ball = &game.balls[0];
+        assert_eq!(ball.last_touched_by, Some(top.id));
+        assert!(ball.velocity.y > 0.0);
+        assert!((ball.position.x - paddle_position).abs() < 1e-4);
+        assert_eq!(ball.position.y, PADDLE_PADDING + ball.radius);
+
+        // over the paddle now, but crossing its line far beside it
+        let mut ball = Ball::centered(BoardSize::square(board_size));
+        ball.position = Vec2 {
+            x: paddle_position,
+            y: PADDLE_PADDING + 1.0,
+        };
+        ball.velocity = Vec2 {
+            x: paddle_width * 4.0,
+            y: -2.0,
+        };
+        game.balls = vec![ball];
+        game.check_collision(1.0);
+
+        assert_eq!(game.balls[0].last_touched_by, None);
+    }
+
+    #[test]
+    fn test_paddle_hit_does_not_score() {
+        let mut game = Game::new();
+        let board_size = game.settings.board_size;
+        // ball positions just before each paddle, moving towards it
+        let cases = [
+            (
+                PlayerPosition::Top,
+                Vec2 {
+                    x: board_size / 2.0,
+                    y: 0.3,
+                },
+                Vec2 { x: 0.0, y: -0.15 },
+            ),
+            (
+                PlayerPosition::Bottom,
+                Vec2 {
+                    x: board_size / 2.0,
+                    y: board_size - 0.3,
+                },
+                Vec2 { x: 0.0, y: 0.15 },
+            ),
+            (
+                PlayerPosition::Left,
+                Vec2 {
+                    x: 0.3,
+                    y: board_size / 2.0,
+                },
+                Vec2 { x: -0.15, y: 0.0 },
+            ),
+            (
+                PlayerPosition::Right,
+                Vec2 {
+                    x: board_size - 0.3,
+                    y: board_size / 2.0,
+                },
+                Vec2 { x: 0.15, y: 0.0 },
+            ),
+        ];
+        for (position, _, _) in &cases {
+            let mut player = Player::new(format!("{:?}", position), false);
+            player.position = Some(*position);
+            game.add_player(player).unwrap();
+        }
+
+        for (position, ball_position, velocity) in cases {
+            let mut ball = Ball::centered(BoardSize::square(board_size));
+            ball.position = ball_position;
+            ball.velocity = velocity;
+            game.balls = vec![ball];
+            game.check_collision(1.0);
+
+            let player = game.get_player_by_side(position).unwrap();
+            assert_eq!(game.balls[0].last_touched_by, Some(player.id));
+            assert_eq!(player.score, 0);
+        }
+    }
+
+    #[test]
+    fn test_multiball_one_ball_scores() {
+        let mut game = Game::with_settings(GameSettings {
+            ball_count: 2,
+            ..Default::default()
+        });
+        assert_eq!(game.balls.len(), 2);
+        assert_ne!(game.balls[0].velocity, game.balls[1].velocity);
+
+        let mut scorer = Player::new("Scorer".to_string(), false);
+        scorer.position = Some(PlayerPosition::Top);
+        let mut defender = Player::new("Defender".to_string(), false);
+        defender.position = Some(PlayerPosition::Bottom);
+        defender.paddle_position = 1.0; // far away from the goal below
+        game.add_player(scorer.clone()).unwrap();
+        game.add_player(defender).unwrap();
+        game.state = GameState::Active;
+        game.started_at = Some(Utc::now());
+
+        let board_size = game.settings.board_size;
+        // the first ball is about to pass the bottom side, the second one is in the middle
+        game.balls[0].position = Vec2 {
+            x: board_size - 1.0,
+            y: board_size + 0.2,
+        };
+        game.balls[0].velocity = Vec2 { x: 0.0, y: 0.15 };
+        game.balls[0].last_touched_by = Some(scorer.id);
+        game.balls[1].position = Vec2 { x: 3.0, y: 5.0 };
+        game.balls[1].velocity = Vec2 { x: 0.1, y: 0.0 };
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.players[&scorer.id].score, 1);
+        // only the scoring ball is reset
+        assert_eq!(game.balls[0].position, Vec2 { x: 5.0, y: 5.0 });
+        assert_eq!(game.balls[0].last_touched_by, None);
+        assert_eq!(game.balls[1].position, Vec2 { x: 3.1, y: 5.0 });
+        assert_eq!(game.balls[1].velocity, Vec2 { x: 0.1, y: 0.0 });
+
+        // once the goal pause is over the other ball keeps moving
+        game.last_goal_at = Some(Utc::now() - chrono::Duration::seconds(10));
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert!((game.balls[1].position.x - 3.2).abs() < 1e-5);
+    }
+
+    /// Active timed game with two players, `scores` being the top and the bottom one
+    fn timed_game(scores: (u32, u32)) -> (Game, Uuid, Uuid) {
+        let mut game = Game::with_settings(GameSettings {
+            time_limit: 60000,
+            ..Default::default()
+        });
+        let mut top = Player::new("Top".to_string(), false);
+        top.position = Some(PlayerPosition::Top);
+        top.score = scores.0;
+        let mut bottom = Player::new("Bottom".to_string(), false);
+        bottom.position = Some(PlayerPosition::Bottom);
+        bottom.score = scores.1;
+        bottom.paddle_position = 1.0; // far away from the goals below
+        let (top_id, bottom_id) = (top.id, bottom.id);
+        game.add_player(top).unwrap();
+        game.add_player(bottom).unwrap();
+        game.state = GameState::Active;
+        game.started_at = Some(Utc::now() - chrono::Duration::seconds(61));
+        (game, top_id, bottom_id)
+    }
+
+    #[test]
+    fn test_time_limit_with_leader_finishes() {
+        let (mut game, _, _) = timed_game((3, 2));
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.state, GameState::Finished);
+        assert!(!game.sudden_death);
+    }
+
+    #[test]
+    fn test_time_limit_tie_starts_sudden_death() {
+        let (mut game, _, _) = timed_game((2, 2));
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.state, GameState::Active);
+        assert!(game.sudden_death);
+        assert!(GameDto::from(game.clone()).sudden_death);
+
+        // the game goes on until somebody scores
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert_eq!(game.state, GameState::Active);
+    }
+
+    #[test]
+    fn test_sudden_death_next_goal_wins() {
+        let (mut game, top_id, _) = timed_game((2, 2));
+        game.game_tick(&GameConfig::default(), 1.0);
+        assert!(game.sudden_death);
+
+        let board_size = game.settings.board_size;
+        let ball = &mut game.balls[0];
+        ball.position = Vec2 {
+            x: board_size - 1.0,
+            y: board_size + 0.2,
+        };
+        ball.velocity = Vec2 { x: 0.0, y: 0.15 };
+        ball.last_touched_by = Some(top_id);
+
+        game.game_tick(&GameConfig::default(), 1.0);
+
+        assert_eq!(game.players[&top_id].score, 3);
+        assert_eq!(game.unique_leader(), Some(top_id));
+        assert_eq!(game.state, GameState::Finished);
+
+        game.rematch().unwrap();
+        assert!(!game.sudden_death);
     }
 }
@@ -0,0 +1,38 @@
+pub const DEFAULT_PING_TIMEOUT: u64 = 2000;
+pub const DEFAULT_GOAL_TIMEOUT: u64 = 750;
+pub const DEFAULT_GAME_DELETE_TIMEOUT: u64 = 60000; // 1 minute
+pub const DEFAULT_CLEANUP_INTERVAL: u64 = 60000;
+pub const DEFAULT_IDLE_LOBBY_TIMEOUT: u64 = 300000; // 5 minutes
+pub const DEFAULT_TICK_RATE: u64 = 60;
+pub const DEFAULT_MAX_LOBBIES: u64 = 1000;
+pub const DEFAULT_MAX_USERNAME_LENGTH: usize = 20;
+
+/// Server-wide timing shared by all games, in milliseconds unless noted otherwise
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameConfig {
+    pub ping_timeout: u64,        // time without a ping before a player is removed
+    pub goal_timeout: u64,        // pause after a goal, unless the game settings override it
+    pub game_delete_timeout: u64, // time a finished game is kept around
+    pub cleanup_interval: u64,    // time between the runs deleting finished games
+    pub idle_lobby_timeout: u64,  // time a lobby nobody joined waits before it is deleted
+    pub tick_rate: u64,           // simulation steps per second
+    pub broadcast_rate: u64,      // game states sent to the players per second, at most tick_rate
+    pub max_lobbies: u64,         // games kept at once, creating more is refused
+    pub max_username_length: usize, // characters of a player name, longer ones are cut
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            ping_timeout: DEFAULT_PING_TIMEOUT,
+            goal_timeout: DEFAULT_GOAL_TIMEOUT,
+            game_delete_timeout: DEFAULT_GAME_DELETE_TIMEOUT,
+            cleanup_interval: DEFAULT_CLEANUP_INTERVAL,
+            idle_lobby_timeout: DEFAULT_IDLE_LOBBY_TIMEOUT,
+            tick_rate: DEFAULT_TICK_RATE,
+            broadcast_rate: DEFAULT_TICK_RATE,
+            max_lobbies: DEFAULT_MAX_LOBBIES,
+            max_username_length: DEFAULT_MAX_USERNAME_LENGTH,
+        }
+    }
+}
@@ -1,11 +1,37 @@
-use log::info;
+use log::{error, info};
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use super::Game;
+use super::{Game, GameConfig, GameDto, GameSettings, GameState, Leaderboard, ServerInfo};
+use crate::common::lifecycle::{log_event, GameEvent};
 
 pub struct GameRooms {
     pub lobbies: HashMap<Uuid, Game>,
+    pub leaderboard: Leaderboard,
+    pub config: GameConfig,
+    pub rejected_inputs: u64, // client inputs dropped for unparseable ids
+    pub server_info: Option<ServerInfo>, // handed to joining players, set by the server binary
+    last_broadcasts: HashMap<Uuid, LastBroadcast>,
+}
+
+/// Unchanged games are still sent this often, a lost packet doesn't leave a client behind for good
+const UNCHANGED_BROADCAST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the last broadcast of a game showed and to whom
+struct LastBroadcast {
+    dto: GameDto,
+    recipients: Vec<(Uuid, Option<SocketAddr>)>,
+    sent_at: Instant,
+}
+
+/// A game serialized for the broadcast, along with the players it goes to
+pub struct BroadcastSnapshot {
+    pub game_id: Uuid,
+    pub sequence: u64,
+    pub bytes: Vec<u8>,
+    pub recipients: Vec<(Uuid, Option<SocketAddr>)>,
 }
 
 impl Default for GameRooms {
@@ -16,14 +42,32 @@ impl Default for GameRooms {
 
 impl GameRooms {
     pub fn new() -> Self {
+        Self::with_config(GameConfig::default())
+    }
+
+    pub fn with_config(config: GameConfig) -> Self {
         Self {
             lobbies: HashMap::new(),
+            leaderboard: Leaderboard::new(),
+            config,
+            rejected_inputs: 0,
+            server_info: None,
+            last_broadcasts: HashMap::new(),
         }
     }
 
     pub fn create_game(&mut self) -> Uuid {
-        let game = Game::new();
+        self.create_game_with_settings(GameSettings::default())
+    }
+
+    pub fn create_game_with_settings(&mut self, settings: GameSettings) -> Uuid {
+        let game = Game::with_settings(settings);
         let game_id = game.id;
+        let detail = format!(
+            "{:?} mode, {} to {} players",
+            game.settings.mode, game.settings.min_players, game.settings.max_players
+        );
+        log_event(GameEvent::Created, game_id, None, &detail);
         self.lobbies.insert(game_id, game);
 
         game_id
@@ -37,11 +81,110 @@ impl GameRooms {
         self.lobbies.get(&id)
     }
 
+    /// Lobbies still waiting for players with a free seat, the oldest first
+    pub fn find_joinable(&self) -> Vec<&Game> {
+        let mut joinable: Vec<&Game> = self
+            .lobbies
+            .values()
+            .filter(|game| game.state == GameState::WaitingForPlayers && !game.is_full())
+            .collect();
+        joinable.sort_by_key(|game| game.created_at);
+        joinable
+    }
+
+    pub fn tick_games(&mut self, dt: f32) {
+        for game in self.lobbies.values_mut() {
+            game.game_tick(&self.config, dt);
+        }
+    }
+
+    /// Snapshots of the games that changed since their last broadcast, each stamped with
+    /// its next sequence number. Games are serialized in place, so the lock is released
+    /// without cloning them
+    pub fn broadcast_snapshots(&mut self) -> Vec<BroadcastSnapshot> {
+        let now = Instant::now();
+        let lobbies = &self.lobbies;
+        self.last_broadcasts
+            .retain(|game_id, _| lobbies.contains_key(game_id));
+
+        let last_broadcasts = &mut self.last_broadcasts;
+        let config = &self.config;
+        self.lobbies
+            .values_mut()
+            .filter_map(|game| {
+                let mut dto = GameDto::from(&*game);
+                // the server default applies when the game does not override it
+                dto.goal_timeout_ms =
+                    Some(game.settings.goal_timeout.unwrap_or(config.goal_timeout));
+                let recipients: Vec<(Uuid, Option<SocketAddr>)> = game
+                    .players
+                    .values()
+                    .map(|player| (player.id, player.addr))
+                    .collect();
+
+                // a player whose address just became known still needs the state
+                let unchanged = last_broadcasts.get(&game.id).is_some_and(|last| {
+                    now.duration_since(last.sent_at) < UNCHANGED_BROADCAST_INTERVAL
+                        && last.recipients == recipients
+                        && !dto.changed_since(&last.dto)
+                });
+                if unchanged {
+                    return None;
+                }
+
+                game.sequence += 1;
+                dto.sequence = game.sequence;
+                let bytes = rmp_serde::to_vec(&dto)
+                    .inspect_err(|e| error!("game {}: failed to serialize state: {}", game.id, e))
+                    .ok()?;
+                last_broadcasts.insert(
+                    game.id,
+                    LastBroadcast {
+                        dto,
+                        recipients: recipients.clone(),
+                        sent_at: now,
+                    },
+                );
+                Some(BroadcastSnapshot {
+                    game_id: game.id,
+                    sequence: game.sequence,
+                    bytes,
+                    recipients,
+                })
+            })
+            .collect()
+    }
+
+    /// Records every newly finished game that was actually played into the leaderboard
+    pub fn record_results(&mut self) {
+        for game in self.lobbies.values_mut() {
+            if game.state == GameState::Finished
+                && game.started_at.is_some()
+                && !game.result_recorded
+            {
+                self.leaderboard.record_result(game);
+                game.result_recorded = true;
+                info!("game {}: result recorded", game.id);
+            }
+        }
+    }
+
+    /// Removes a game right away regardless of its state, recording its result first
+    pub fn purge_game(&mut self, id: Uuid) -> Option<Game> {
+        self.record_results();
+
+        let game = self.lobbies.remove(&id)?;
+        info!("game {}: purged", id);
+        Some(game)
+    }
+
     pub fn delete_games(&mut self) {
+        self.record_results();
+
         let to_delete: Vec<Uuid> = self
             .lobbies
             .iter()
-            .filter(|(_, game)| game.should_delete_game())
+            .filter(|(_, game)| game.should_delete_game(&self.config))
             .map(|(id, _)| *id)
             .collect();
 
@@ -55,6 +198,8 @@ impl GameRooms {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::models::GameDto;
+    use crate::common::Player;
 
     #[test]
     fn test_create_game() {
@@ -65,6 +210,205 @@ mod tests {
         assert!(game_rooms.lobbies.contains_key(&game_id));
     }
 
+    #[test]
+    fn test_create_game_with_settings() {
+        let mut game_rooms = GameRooms::new();
+
+        let game_id = game_rooms.create_game_with_settings(GameSettings {
+            board_size: 15.0,
+            ..Default::default()
+        });
+
+        assert_eq!(game_rooms.lobbies[&game_id].settings.board_size, 15.0);
+    }
+
+    #[test]
+    fn test_record_results() {
+        let mut game_rooms = GameRooms::new();
+
+        let game_id = game_rooms.create_game();
+        let game = game_rooms.find_lobby_mut(game_id).unwrap();
+        let mut winner = Player::new("winner".to_string(), false);
+        winner.score = 10;
+        let loser = Player::new("loser".to_string(), false);
+        game.add_player(winner).unwrap();
+        game.add_player(loser).unwrap();
+        game.started_at = Some(chrono::Utc::now());
+        game.set_game_state(GameState::Finished);
+
+        game_rooms.record_results();
+        game_rooms.record_results(); // must not be counted twice
+
+        let winner = game_rooms.leaderboard.get("winner").unwrap();
+        assert_eq!(winner.wins, 1);
+        assert_eq!(winner.goals, 10);
+        let loser = game_rooms.leaderboard.get("loser").unwrap();
+        assert_eq!(loser.losses, 1);
+        assert_eq!(loser.games_played, 1);
+    }
+
+    #[test]
+    fn test_delete_games_removes_bot_only_game() {
+        let mut game_rooms = GameRooms::new();
+
+        let game_id = game_rooms.create_game();
+        let game = game_rooms.find_lobby_mut(game_id).unwrap();
+        let human = Player::new("human".to_string(), false);
+        let human_id = human.id;
+        game.add_player(human).unwrap();
+        game.add_player(Player::new("bot".to_string(), true))
+            .unwrap();
+        game.state = GameState::Active;
+        game.forfeit(human_id);
+
+        // gone without waiting for the delete timeout
+        game_rooms.delete_games();
+        assert!(!game_rooms.lobbies.contains_key(&game_id));
+    }
+
+    #[test]
+    fn test_delete_games_removes_idle_lobby() {
+        let mut game_rooms = GameRooms::with_config(GameConfig {
+            idle_lobby_timeout: 100,
+            ..Default::default()
+        });
+
+        let idle_id = game_rooms.create_game();
+        game_rooms.find_lobby_mut(idle_id).unwrap().created_at =
+            chrono::Utc::now() - chrono::Duration::milliseconds(500);
+        let fresh_id = game_rooms.create_game();
+
+        game_rooms.delete_games();
+        assert!(!game_rooms.lobbies.contains_key(&idle_id));
+        assert!(game_rooms.lobbies.contains_key(&fresh_id));
+    }
+
+    #[test]
+    fn test_broadcast_snapshots_sequence() {
+        let mut game_rooms = GameRooms::new();
+        let game_id = game_rooms.create_game();
+
+        let first = game_rooms.broadcast_snapshots();
+        game_rooms
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(Player::new("Player 1".to_string(), false))
+            .unwrap();
+        let second = game_rooms.broadcast_snapshots();
+
+        assert_eq!(first[0].sequence, 1);
+        assert_eq!(second[0].sequence, 2);
+        assert_eq!(game_rooms.lobbies[&game_id].sequence, 2);
+    }
+
+    #[test]
+    fn test_unchanged_games_not_broadcast() {
+        let mut game_rooms = GameRooms::new();
+        let game_id = game_rooms.create_game();
+        let player = Player::new("Player 1".to_string(), false);
+        let player_id = player.id;
+        game_rooms
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(player)
+            .unwrap();
+
+        assert_eq!(game_rooms.broadcast_snapshots().len(), 1);
+        assert!(game_rooms.broadcast_snapshots().is_empty());
+        assert_eq!(game_rooms.lobbies[&game_id].sequence, 1);
+
+        // the player's address arrives with its first UDP message
+        game_rooms
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .get_player_mut(&player_id)
+            .unwrap()
+            .addr = Some(SocketAddr::from(([127, 0, 0, 1], 4000)));
+        assert_eq!(game_rooms.broadcast_snapshots().len(), 1);
+
+        // resent every so often even when nothing changed
+        game_rooms
+            .last_broadcasts
+            .get_mut(&game_id)
+            .unwrap()
+            .sent_at -= UNCHANGED_BROADCAST_INTERVAL;
+        assert_eq!(game_rooms.broadcast_snapshots().len(), 1);
+        assert_eq!(game_rooms.lobbies[&game_id].sequence, 3);
+    }
+
+    #[test]
+    fn test_broadcast_snapshots_goal_timeout() {
+        let mut game_rooms = GameRooms::with_config(GameConfig {
+            goal_timeout: 1500,
+            ..Default::default()
+        });
+        let default_id = game_rooms.create_game();
+        let custom_id = game_rooms.create_game_with_settings(GameSettings {
+            goal_timeout: Some(3000),
+            ..Default::default()
+        });
+
+        let snapshots = game_rooms.broadcast_snapshots();
+        let goal_timeout = |game_id| {
+            let snapshot = snapshots.iter().find(|s| s.game_id == game_id).unwrap();
+            rmp_serde::from_slice::<GameDto>(&snapshot.bytes)
+                .unwrap()
+                .goal_timeout_ms
+        };
+        assert_eq!(goal_timeout(default_id), Some(1500));
+        assert_eq!(goal_timeout(custom_id), Some(3000));
+    }
+
+    fn decoded(bytes: &[u8]) -> serde_json::Value {
+        serde_json::to_value(rmp_serde::from_slice::<GameDto>(bytes).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_broadcast_snapshots_match_cloned_games() {
+        let mut game_rooms = GameRooms::new();
+        for index in 0..200 {
+            let game_id = game_rooms.create_game();
+            let game = game_rooms.find_lobby_mut(game_id).unwrap();
+            for player in 0..index % 4 {
+                let mut player = Player::new(format!("player_{}", player), player % 2 == 0);
+                player.addr = Some(SocketAddr::from(([127, 0, 0, 1], 4000 + index as u16)));
+                game.add_player(player).unwrap();
+            }
+        }
+
+        let snapshots = game_rooms.broadcast_snapshots();
+        assert_eq!(snapshots.len(), 200);
+
+        for snapshot in snapshots {
+            let game = &game_rooms.lobbies[&snapshot.game_id];
+            // what the broadcast loop used to send after cloning the game out of the lock
+            let mut cloned = GameDto::from(game.clone());
+            cloned.goal_timeout_ms = Some(game_rooms.config.goal_timeout);
+            let cloned = rmp_serde::to_vec(&cloned).unwrap();
+            if game.players.len() <= 1 {
+                assert_eq!(snapshot.bytes, cloned);
+            } else {
+                // the players map is serialized in its random iteration order
+                assert_eq!(decoded(&snapshot.bytes), decoded(&cloned));
+            }
+            assert_eq!(snapshot.sequence, game.sequence);
+            assert_eq!(snapshot.recipients.len(), game.players.len());
+            for (player_id, addr) in snapshot.recipients {
+                assert_eq!(game.players[&player_id].addr, addr);
+            }
+        }
+    }
+
+    #[test]
+    fn test_purge_game() {
+        let mut game_rooms = GameRooms::new();
+        let game_id = game_rooms.create_game();
+
+        assert!(game_rooms.purge_game(game_id).is_some());
+        assert!(!game_rooms.lobbies.contains_key(&game_id));
+        assert!(game_rooms.purge_game(game_id).is_none());
+    }
+
     #[test]
     fn test_find_lobby_mut() {
         let mut game_rooms = GameRooms::new();
@@ -86,4 +430,36 @@ mod tests {
 
         assert!(game.is_some());
     }
+
+    #[test]
+    fn test_find_joinable() {
+        let mut game_rooms = GameRooms::new();
+
+        let older = game_rooms.create_game();
+        let newer = game_rooms.create_game();
+        game_rooms.lobbies.get_mut(&newer).unwrap().created_at += chrono::Duration::seconds(1);
+
+        let full = game_rooms.create_game();
+        let game = game_rooms.lobbies.get_mut(&full).unwrap();
+        while !game.is_full() {
+            game.add_player(Player::new("bot".to_string(), true))
+                .unwrap();
+        }
+
+        for state in [GameState::Starting, GameState::Active, GameState::Finished] {
+            let game_id = game_rooms.create_game();
+            game_rooms
+                .lobbies
+                .get_mut(&game_id)
+                .unwrap()
+                .set_game_state(state);
+        }
+
+        let joinable: Vec<Uuid> = game_rooms
+            .find_joinable()
+            .iter()
+            .map(|game| game.id)
+            .collect();
+        assert_eq!(joinable, vec![older, newer]);
+    }
 }
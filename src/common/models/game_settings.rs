@@ -0,0 +1,564 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use super::BoardSize;
+use crate::common::SettingsError;
+
+pub const DEFAULT_BOARD_SIZE: f32 = 10.0;
+pub const DEFAULT_MAX_SCORE: u32 = 10;
+pub const DEFAULT_BALL_SPEED: f32 = 0.15;
+pub const DEFAULT_BALL_RADIUS: f32 = 0.125;
+pub const DEFAULT_MIN_PLAYERS: usize = 2;
+pub const DEFAULT_MAX_PLAYERS: usize = 4;
+pub const DEFAULT_STALL_TIMEOUT: u64 = 0; // off, lobbies opt in
+pub const DEFAULT_BALL_COUNT: usize = 1;
+pub const DEFAULT_TIME_LIMIT: u64 = 0; // play until max_score
+pub const DEFAULT_PADDLE_WIDTH: f32 = 1.0;
+pub const DEFAULT_PADDLE_SPEED: f32 = 0.3;
+pub const DEFAULT_LIVES: u32 = 3;
+pub const DEFAULT_AI_REACTION_TICKS: u32 = 1; // bots see every move of the ball
+
+const BOARD_SIZE_RANGE: (f32, f32) = (5.0, 50.0);
+const MAX_SCORE_LIMIT: u32 = 100;
+const BALL_SPEED_RANGE: (f32, f32) = (0.05, 0.5);
+const BALL_RADIUS_RANGE: (f32, f32) = (0.05, 1.0);
+const GOAL_TIMEOUT_LIMIT: u64 = 5000;
+const STALL_TIMEOUT_RANGE: (u64, u64) = (1000, 600000);
+const MAX_BALL_COUNT: usize = 4;
+const TIME_LIMIT_RANGE: (u64, u64) = (30000, 3600000);
+const PADDLE_SPEED_RANGE: (f32, f32) = (0.05, 2.0);
+const MAX_LIVES: u32 = 20;
+const AI_REACTION_TICKS_RANGE: (u32, u32) = (1, 60);
+const MAX_BOUNCE_JITTER: f32 = PI / 12.0; // 15 degrees
+
+/// Side the ball is served towards after a goal
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ServeTarget {
+    #[default]
+    ScoredOn, // the player who conceded gets the next ball
+    Random,
+}
+
+/// How a game is won
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum GameMode {
+    #[default]
+    Score, // first to max_score wins
+    Lives, // conceding costs a life, the last player standing wins
+}
+
+/// Per-game settings, accepted as the body of the create game request.
+/// Missing fields fall back to their defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct GameSettings {
+    pub board_size: f32,
+    pub board_width: Option<f32>, // makes the board rectangular, `board_size` when unset
+    pub board_height: Option<f32>, // makes the board rectangular, `board_size` when unset
+    pub max_score: u32,
+    pub ball_speed: f32, // speed of the ball after bouncing off a paddle
+    pub ball_radius: f32,
+    pub goal_timeout: Option<u64>, // ms pause after a goal, the server default when unset
+    pub min_players: usize,
+    pub max_players: usize,
+    pub stall_timeout: u64, // ms without a goal before the ball is nudged, 0 disables it
+    pub ball_count: usize,  // balls in play at once, more than one is the multiball mode
+    pub time_limit: u64,    // ms of play after which the leader wins, 0 disables it
+    pub paddle_width: f32,  // starting width of every player's paddle
+    pub paddle_speed: f32,  // distance a paddle moves per input
+    pub serve: ServeTarget,
+    pub bots_auto_ready: bool, // bots join ready, otherwise they wait for the host to ready them
+    pub mode: GameMode,
+    pub lives: u32,                 // lives every player starts with in the lives mode
+    pub auto_start_when_full: bool, // the countdown starts once every seat is taken and ready
+    pub goal_width: Option<f32>, // width of the goal gap centered on each side, the whole side when unset
+    pub starting_paddle_position: Option<f32>, // where paddles start along their side, the center when unset
+    pub ai_reaction_ticks: u32, // ticks between a bot's looks at the ball, higher makes bots easier
+    pub rotate_seats_on_rematch: bool, // players move one side clockwise for every rematch
+    pub warmup: bool, // paddles and the ball move while waiting for players, nothing is scored
+    pub seed: Option<u64>, // makes ball serves and bot jitter reproducible, random when unset
+    pub bounce_jitter: f32, // radians a paddle bounce may turn at random either way, 0 disables it
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            board_size: DEFAULT_BOARD_SIZE,
+            board_width: None,
+            board_height: None,
+            max_score: DEFAULT_MAX_SCORE,
+            ball_speed: DEFAULT_BALL_SPEED,
+            ball_radius: DEFAULT_BALL_RADIUS,
+            goal_timeout: None,
+            min_players: DEFAULT_MIN_PLAYERS,
+            max_players: DEFAULT_MAX_PLAYERS,
+            stall_timeout: DEFAULT_STALL_TIMEOUT,
+            ball_count: DEFAULT_BALL_COUNT,
+            time_limit: DEFAULT_TIME_LIMIT,
+            paddle_width: DEFAULT_PADDLE_WIDTH,
+            paddle_speed: DEFAULT_PADDLE_SPEED,
+            serve: ServeTarget::default(),
+            bots_auto_ready: true,
+            mode: GameMode::default(),
+            lives: DEFAULT_LIVES,
+            auto_start_when_full: false,
+            goal_width: None,
+            starting_paddle_position: None,
+            ai_reaction_ticks: DEFAULT_AI_REACTION_TICKS,
+            rotate_seats_on_rematch: false,
+            warmup: false,
+            seed: None,
+            bounce_jitter: 0.0,
+        }
+    }
+}
+
+fn invalid(field: &'static str, reason: &str) -> SettingsError {
+    SettingsError {
+        field,
+        reason: reason.to_string(),
+    }
+}
+
+fn positive(field: &'static str, value: f32) -> Result<f32, SettingsError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(invalid(field, "must be a positive number"))
+    }
+}
+
+impl GameSettings {
+    /// Rejects values that make no sense (non-positive sizes, zero scores, inconsistent
+    /// player counts) and clamps the remaining ones into their supported ranges
+    pub fn validate_and_clamp(self) -> Result<GameSettings, SettingsError> {
+        let board_size =
+            positive("board_size", self.board_size)?.clamp(BOARD_SIZE_RANGE.0, BOARD_SIZE_RANGE.1);
+        let board_width = self
+            .board_width
+            .map(|width| {
+                positive("board_width", width)
+                    .map(|width| width.clamp(BOARD_SIZE_RANGE.0, BOARD_SIZE_RANGE.1))
+            })
+            .transpose()?;
+        let board_height = self
+            .board_height
+            .map(|height| {
+                positive("board_height", height)
+                    .map(|height| height.clamp(BOARD_SIZE_RANGE.0, BOARD_SIZE_RANGE.1))
+            })
+            .transpose()?;
+        let board = BoardSize {
+            width: board_width.unwrap_or(board_size),
+            height: board_height.unwrap_or(board_size),
+        };
+        // the ball and the paddles have to fit the shorter side
+        let shorter_side = board.width.min(board.height);
+        let longer_side = board.width.max(board.height);
+
+        if self.max_score == 0 {
+            return Err(invalid("max_score", "must be at least 1"));
+        }
+        let max_score = self.max_score.min(MAX_SCORE_LIMIT);
+
+        let ball_speed =
+            positive("ball_speed", self.ball_speed)?.clamp(BALL_SPEED_RANGE.0, BALL_SPEED_RANGE.1);
+
+        let ball_radius = positive("ball_radius", self.ball_radius)?
+            .clamp(BALL_RADIUS_RANGE.0, BALL_RADIUS_RANGE.1);
+        if ball_radius * 10.0 > shorter_side {
+            return Err(invalid(
+                "ball_radius",
+                "must be at most a tenth of the board size",
+            ));
+        }
+
+        let goal_timeout = self
+            .goal_timeout
+            .map(|timeout| timeout.min(GOAL_TIMEOUT_LIMIT));
+
+        if self.min_players < DEFAULT_MIN_PLAYERS {
+            return Err(invalid("min_players", "must be at least 2"));
+        }
+        if self.max_players < DEFAULT_MIN_PLAYERS {
+            return Err(invalid("max_players", "must be at least 2"));
+        }
+        let max_players = self.max_players.min(DEFAULT_MAX_PLAYERS);
+        if self.min_players > max_players {
+            return Err(invalid("min_players", "must not exceed max_players"));
+        }
+
+        let stall_timeout = match self.stall_timeout {
+            0 => 0,
+            timeout => timeout.clamp(STALL_TIMEOUT_RANGE.0, STALL_TIMEOUT_RANGE.1),
+        };
+
+        if self.ball_count == 0 {
+            return Err(invalid("ball_count", "must be at least 1"));
+        }
+        let ball_count = self.ball_count.min(MAX_BALL_COUNT);
+
+        let time_limit = match self.time_limit {
+            0 => 0,
+            limit => limit.clamp(TIME_LIMIT_RANGE.0, TIME_LIMIT_RANGE.1),
+        };
+
+        let paddle_width = positive("paddle_width", self.paddle_width)?;
+        if paddle_width * 2.0 > shorter_side {
+            return Err(invalid(
+                "paddle_width",
+                "must be at most half of the board size",
+            ));
+        }
+
+        let paddle_speed = positive("paddle_speed", self.paddle_speed)?
+            .clamp(PADDLE_SPEED_RANGE.0, PADDLE_SPEED_RANGE.1);
+
+        if self.lives == 0 {
+            return Err(invalid("lives", "must be at least 1"));
+        }
+        let lives = self.lives.min(MAX_LIVES);
+
+        let goal_width = self
+            .goal_width
+            .map(|width| positive("goal_width", width).map(|width| width.min(longer_side)))
+            .transpose()?;
+
+        // paddles are kept on their side when placed, only nonsense is rejected here
+        if self
+            .starting_paddle_position
+            .is_some_and(|position| !position.is_finite())
+        {
+            return Err(invalid("starting_paddle_position", "must be a number"));
+        }
+
+        let ai_reaction_ticks = self
+            .ai_reaction_ticks
+            .clamp(AI_REACTION_TICKS_RANGE.0, AI_REACTION_TICKS_RANGE.1);
+
+        if !self.bounce_jitter.is_finite() || self.bounce_jitter < 0.0 {
+            return Err(invalid(
+                "bounce_jitter",
+                "must be zero or a positive number",
+            ));
+        }
+        let bounce_jitter = self.bounce_jitter.min(MAX_BOUNCE_JITTER);
+
+        Ok(GameSettings {
+            board_size,
+            board_width,
+            board_height,
+            max_score,
+            ball_speed,
+            ball_radius,
+            goal_timeout,
+            min_players: self.min_players,
+            max_players,
+            stall_timeout,
+            ball_count,
+            time_limit,
+            paddle_width,
+            paddle_speed,
+            serve: self.serve,
+            bots_auto_ready: self.bots_auto_ready,
+            mode: self.mode,
+            lives,
+            auto_start_when_full: self.auto_start_when_full,
+            goal_width,
+            starting_paddle_position: self.starting_paddle_position,
+            ai_reaction_ticks,
+            rotate_seats_on_rematch: self.rotate_seats_on_rematch,
+            warmup: self.warmup,
+            seed: self.seed,
+            bounce_jitter,
+        })
+    }
+
+    /// Width and height of the board, `board_size` for any of them not set on its own
+    pub fn board(&self) -> BoardSize {
+        BoardSize {
+            width: self.board_width.unwrap_or(self.board_size),
+            height: self.board_height.unwrap_or(self.board_size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_field(settings: GameSettings) -> &'static str {
+        match settings.validate_and_clamp() {
+            Err(SettingsError { field, .. }) => field,
+            other => panic!("Expected SettingsError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_valid_settings() {
+        let settings = GameSettings {
+            board_size: 20.0,
+            board_width: Some(30.0),
+            board_height: None,
+            max_score: 5,
+            ball_speed: 0.2,
+            ball_radius: 0.25,
+            goal_timeout: Some(1000),
+            min_players: 3,
+            max_players: 3,
+            stall_timeout: 0,
+            ball_count: 2,
+            time_limit: 120000,
+            paddle_width: 2.0,
+            paddle_speed: 0.5,
+            serve: ServeTarget::Random,
+            bots_auto_ready: false,
+            mode: GameMode::Lives,
+            lives: 5,
+            auto_start_when_full: true,
+            goal_width: Some(4.0),
+            starting_paddle_position: Some(3.0),
+            ai_reaction_ticks: 10,
+            rotate_seats_on_rematch: true,
+            warmup: true,
+            seed: Some(42),
+            bounce_jitter: 0.1,
+        };
+
+        assert_eq!(settings.clone().validate_and_clamp().unwrap(), settings);
+        assert_eq!(
+            GameSettings::default().validate_and_clamp().unwrap(),
+            GameSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_clamped_settings() {
+        let settings = GameSettings {
+            board_size: 1000.0,
+            board_width: Some(1000.0),
+            board_height: Some(1.0),
+            max_score: 1000,
+            ball_speed: 10.0,
+            ball_radius: 0.01,
+            goal_timeout: Some(60000),
+            min_players: 2,
+            max_players: 10,
+            stall_timeout: 1,
+            ball_count: 10,
+            time_limit: 1,
+            paddle_width: 1.0,
+            paddle_speed: 10.0,
+            serve: ServeTarget::ScoredOn,
+            bots_auto_ready: true,
+            mode: GameMode::Score,
+            lives: 1000,
+            auto_start_when_full: false,
+            goal_width: Some(5000.0),
+            starting_paddle_position: None,
+            ai_reaction_ticks: 1000,
+            rotate_seats_on_rematch: false,
+            warmup: false,
+            seed: None,
+            bounce_jitter: 1.0,
+        }
+        .validate_and_clamp()
+        .unwrap();
+
+        assert_eq!(settings.board_size, BOARD_SIZE_RANGE.1);
+        assert_eq!(settings.board_width, Some(BOARD_SIZE_RANGE.1));
+        assert_eq!(settings.board_height, Some(BOARD_SIZE_RANGE.0));
+        assert_eq!(settings.max_score, MAX_SCORE_LIMIT);
+        assert_eq!(settings.ball_speed, BALL_SPEED_RANGE.1);
+        assert_eq!(settings.ball_radius, BALL_RADIUS_RANGE.0);
+        assert_eq!(settings.goal_timeout, Some(GOAL_TIMEOUT_LIMIT));
+        assert_eq!(settings.max_players, DEFAULT_MAX_PLAYERS);
+        assert_eq!(settings.stall_timeout, STALL_TIMEOUT_RANGE.0);
+        assert_eq!(settings.ball_count, MAX_BALL_COUNT);
+        assert_eq!(settings.time_limit, TIME_LIMIT_RANGE.0);
+        assert_eq!(settings.paddle_speed, PADDLE_SPEED_RANGE.1);
+        assert_eq!(settings.lives, MAX_LIVES);
+        assert_eq!(settings.goal_width, Some(BOARD_SIZE_RANGE.1));
+        assert_eq!(settings.ai_reaction_ticks, AI_REACTION_TICKS_RANGE.1);
+        assert_eq!(settings.bounce_jitter, MAX_BOUNCE_JITTER);
+    }
+
+    #[test]
+    fn test_invalid_starting_paddle_position() {
+        let settings = GameSettings {
+            starting_paddle_position: Some(f32::NAN),
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "starting_paddle_position");
+    }
+
+    #[test]
+    fn test_invalid_bounce_jitter() {
+        for bounce_jitter in [-0.1, f32::NAN] {
+            let settings = GameSettings {
+                bounce_jitter,
+                ..Default::default()
+            };
+            assert_eq!(invalid_field(settings), "bounce_jitter");
+        }
+    }
+
+    #[test]
+    fn test_invalid_goal_width() {
+        let settings = GameSettings {
+            goal_width: Some(0.0),
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "goal_width");
+    }
+
+    #[test]
+    fn test_invalid_lives() {
+        let settings = GameSettings {
+            lives: 0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "lives");
+    }
+
+    #[test]
+    fn test_invalid_ball_count() {
+        let settings = GameSettings {
+            ball_count: 0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "ball_count");
+    }
+
+    #[test]
+    fn test_invalid_board_size() {
+        for board_size in [0.0, -5.0, f32::NAN, f32::INFINITY] {
+            let settings = GameSettings {
+                board_size,
+                ..Default::default()
+            };
+            assert_eq!(invalid_field(settings), "board_size");
+        }
+    }
+
+    #[test]
+    fn test_invalid_board_dimensions() {
+        let settings = GameSettings {
+            board_width: Some(0.0),
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "board_width");
+
+        let settings = GameSettings {
+            board_height: Some(f32::NAN),
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "board_height");
+
+        // a paddle has to fit the shorter side twice
+        let settings = GameSettings {
+            board_width: Some(20.0),
+            board_height: Some(5.0),
+            paddle_width: 3.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "paddle_width");
+    }
+
+    #[test]
+    fn test_board() {
+        assert_eq!(GameSettings::default().board(), BoardSize::square(10.0));
+
+        let settings = GameSettings {
+            board_size: 12.0,
+            board_width: Some(24.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.board(),
+            BoardSize {
+                width: 24.0,
+                height: 12.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_max_score() {
+        let settings = GameSettings {
+            max_score: 0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "max_score");
+    }
+
+    #[test]
+    fn test_invalid_ball_speed() {
+        let settings = GameSettings {
+            ball_speed: -0.1,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "ball_speed");
+    }
+
+    #[test]
+    fn test_invalid_ball_radius() {
+        let settings = GameSettings {
+            ball_radius: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "ball_radius");
+
+        let settings = GameSettings {
+            board_size: 5.0,
+            ball_radius: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "ball_radius");
+    }
+
+    #[test]
+    fn test_invalid_paddle() {
+        let settings = GameSettings {
+            paddle_width: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "paddle_width");
+
+        let settings = GameSettings {
+            board_size: 10.0,
+            paddle_width: 6.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "paddle_width");
+
+        let settings = GameSettings {
+            paddle_speed: -1.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "paddle_speed");
+    }
+
+    #[test]
+    fn test_invalid_player_counts() {
+        let settings = GameSettings {
+            min_players: 1,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "min_players");
+
+        let settings = GameSettings {
+            max_players: 1,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "max_players");
+
+        let settings = GameSettings {
+            min_players: 4,
+            max_players: 3,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(settings), "min_players");
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::Game;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub goals: u32,
+    pub games_played: u32,
+}
+
+/// Cumulative per-player statistics across completed games.
+///
+/// Entries are keyed by player name, so the stats are best-effort: names are not
+/// authenticated and anyone joining under the same name shares the entry.
+/// The leaderboard lives only in memory and resets when the server restarts.
+#[derive(Default)]
+pub struct Leaderboard {
+    entries: HashMap<String, LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_result(&mut self, game: &Game) {
+        let humans: Vec<_> = game.players.values().filter(|p| !p.is_ai).collect();
+
+        let Some(best) = game.players.values().map(|p| game.standing(p)).max() else {
+            return;
+        };
+
+        for player in humans {
+            let entry =
+                self.entries
+                    .entry(player.name.clone())
+                    .or_insert_with(|| LeaderboardEntry {
+                        name: player.name.clone(),
+                        ..Default::default()
+                    });
+
+            entry.games_played += 1;
+            entry.goals += player.score;
+            if game.standing(player) == best {
+                entry.wins += 1;
+            } else {
+                entry.losses += 1;
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LeaderboardEntry> {
+        self.entries.get(name)
+    }
+
+    /// Returns up to `n` entries ordered by wins, then goals, then name
+    pub fn top(&self, n: usize) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<_> = self.entries.values().cloned().collect();
+        entries.sort_by(|a, b| {
+            b.wins
+                .cmp(&a.wins)
+                .then(b.goals.cmp(&a.goals))
+                .then(a.name.cmp(&b.name))
+        });
+        entries.truncate(n);
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Player;
+
+    fn finished_game(scores: &[(&str, u32, bool)]) -> Game {
+        let mut game = Game::new();
+        for (name, score, is_ai) in scores {
+            let mut player = Player::new(name.to_string(), *is_ai);
+            player.score = *score;
+            game.add_player(player).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_record_result() {
+        let mut leaderboard = Leaderboard::new();
+
+        leaderboard.record_result(&finished_game(&[("alice", 10, false), ("bob", 4, false)]));
+        leaderboard.record_result(&finished_game(&[("alice", 3, false), ("bob", 10, false)]));
+        leaderboard.record_result(&finished_game(&[("alice", 10, false), ("bot_2", 7, true)]));
+
+        let alice = leaderboard.get("alice").unwrap();
+        assert_eq!(alice.wins, 2);
+        assert_eq!(alice.losses, 1);
+        assert_eq!(alice.goals, 23);
+        assert_eq!(alice.games_played, 3);
+
+        let bob = leaderboard.get("bob").unwrap();
+        assert_eq!(bob.wins, 1);
+        assert_eq!(bob.losses, 1);
+        assert_eq!(bob.goals, 14);
+
+        // bots are not tracked
+        assert!(leaderboard.get("bot_2").is_none());
+    }
+
+    #[test]
+    fn test_top() {
+        let mut leaderboard = Leaderboard::new();
+
+        leaderboard.record_result(&finished_game(&[("alice", 10, false), ("bob", 4, false)]));
+        leaderboard.record_result(&finished_game(&[("carol", 10, false), ("bob", 9, false)]));
+        leaderboard.record_result(&finished_game(&[("alice", 10, false), ("carol", 2, false)]));
+
+        let top = leaderboard.top(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].name, "alice");
+        assert_eq!(top[1].name, "carol");
+    }
+}
@@ -2,15 +2,24 @@ mod ball;
 mod client_input;
 mod dto;
 mod game;
+mod game_config;
 mod game_rooms;
+mod game_settings;
+mod leaderboard;
 mod player;
+mod server_info;
 
-pub use ball::{Ball, Vec2};
+pub use ball::{ticks_elapsed, Ball, BoardSize, Vec2, BASE_TICK_RATE};
 pub use client_input::{
-    ClientInput, ClientInputType, ClientInputWithAddr, Direction, JoinGameRequest,
+    ClientInput, ClientInputType, ClientInputWithAddr, CreateGameRequest, Direction,
+    JoinGameRequest, JoinGameResponse, QuickMatchRequest, QuickMatchResponse, PROTOCOL_VERSION,
 };
-pub use dto::{BallDto, GameDto, PlayerDto};
-pub use game::{Game, GameState};
-pub use game_rooms::GameRooms;
-pub use player::Player;
+pub use dto::{BallDto, GameDto, GameResultsDto, PlayerDto, StandingDto};
+pub use game::{Game, GameState, MAX_LOBBY_NAME_LENGTH};
+pub use game_config::{GameConfig, DEFAULT_GOAL_TIMEOUT};
+pub use game_rooms::{BroadcastSnapshot, GameRooms};
+pub use game_settings::{GameMode, GameSettings, ServeTarget, DEFAULT_MAX_PLAYERS};
+pub use leaderboard::{Leaderboard, LeaderboardEntry};
 pub use player::PlayerPosition;
+pub use player::{clamp_boost, sanitize_username, score_tie_break, Player, MAX_PADDLE_BOOST};
+pub use server_info::ServerInfo;
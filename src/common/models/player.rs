@@ -1,10 +1,15 @@
-use std::net::SocketAddr;
+use std::{cmp::Ordering, net::SocketAddr};
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{Ball, Direction};
+use super::game_settings::{
+    DEFAULT_BOARD_SIZE, DEFAULT_LIVES, DEFAULT_PADDLE_SPEED, DEFAULT_PADDLE_WIDTH,
+};
+use super::{Ball, BoardSize, Direction};
+
+pub const MAX_PADDLE_BOOST: f32 = 3.0; // fastest a held key moves the paddle, in paddle deltas
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PlayerPosition {
@@ -40,6 +45,24 @@ pub struct Player {
     pub paddle_width: f32,
     pub is_ready: bool,
     pub is_ai: bool,
+    #[serde(default)]
+    pub returns: u32, // balls deflected by the paddle
+    #[serde(default)]
+    pub lives: u32, // left in the lives mode, the paddle is removed at zero
+    #[serde(default)]
+    pub last_score_at: Option<chrono::DateTime<chrono::Utc>>, // when the current score was reached
+    #[serde(skip)]
+    pub reconnect_token: Option<String>, // handed out to the joining client only, bots have none
+    #[serde(skip)]
+    pub pending_direction: Option<Direction>, // latest move input, applied on the next tick
+    #[serde(skip)]
+    pub pending_boost: Option<f32>, // speed of the pending move when the key is held
+    #[serde(skip)]
+    pub last_input_seq: u64, // sequence of the newest input applied, older ones arrived too late
+    #[serde(skip)]
+    pub ai_target: Option<f32>, // where the bot last saw the ball crossing its side
+    #[serde(skip)]
+    pub ai_reaction_countdown: u32, // ticks until the bot looks at the ball again
 }
 
 impl Player {
@@ -52,22 +75,61 @@ impl Player {
             score: 0,
             addr: None,
             position: None,
-            paddle_delta: 0.3,
-            paddle_position: 5.0,
-            paddle_width: 1.0,
+            paddle_delta: DEFAULT_PADDLE_SPEED,
+            paddle_position: DEFAULT_BOARD_SIZE / 2.0,
+            paddle_width: DEFAULT_PADDLE_WIDTH,
             is_ready: is_ai, // AI players are always ready
             is_ai,
+            returns: 0,
+            lives: DEFAULT_LIVES,
+            last_score_at: None,
+            reconnect_token: None,
+            pending_direction: None,
+            pending_boost: None,
+            last_input_seq: 0,
+            ai_target: None,
+            ai_reaction_countdown: 0,
+        }
+    }
+
+    /// Creates the token the client has to send with its `JoinGame` inputs
+    pub fn issue_reconnect_token(&mut self) -> String {
+        let token = Uuid::new_v4().simple().to_string();
+        self.reconnect_token = Some(token.clone());
+        token
+    }
+
+    /// Whether `token` may join as this player, anyone may join as a player without a token
+    pub fn accepts_reconnect_token(&self, token: Option<&str>) -> bool {
+        match &self.reconnect_token {
+            Some(expected) => token == Some(expected.as_str()),
+            None => true,
         }
     }
 
     pub fn increment_score(&mut self) {
         self.score += 1;
+        self.last_score_at = Some(chrono::Utc::now());
+    }
+
+    pub fn move_paddle(&mut self, direction: Direction, board: BoardSize) {
+        self.move_paddle_by(direction, 1.0, board);
     }
 
-    pub fn move_paddle(&mut self, direction: Direction) {
+    /// Moves the paddle `boost` times its delta, capped at `MAX_PADDLE_BOOST`
+    pub fn move_paddle_by(&mut self, direction: Direction, boost: f32, board: BoardSize) {
+        self.shift_paddle(direction, self.paddle_delta * clamp_boost(boost), board);
+    }
+
+    /// Moves a bot's paddle its delta scaled by `dt`, the ticks of game time that passed
+    fn move_ai_paddle(&mut self, direction: Direction, dt: f32, board: BoardSize) {
+        self.shift_paddle(direction, self.paddle_delta * dt, board);
+    }
+
+    fn shift_paddle(&mut self, direction: Direction, step: f32, board: BoardSize) {
         let mut delta = match direction {
-            Direction::Positive => self.paddle_delta,
-            Direction::Negative => -self.paddle_delta,
+            Direction::Positive => step,
+            Direction::Negative => -step,
         };
 
         // artificially slow down the paddle movement for AI players
@@ -75,18 +137,38 @@ impl Player {
             delta *= 0.2;
         }
 
-        self.paddle_position = (self.paddle_position + delta).clamp(
+        self.place_paddle(self.paddle_position + delta, board);
+    }
+
+    /// Length of the side the paddle moves along, a player without a side takes the width
+    pub fn side_length(&self, board: BoardSize) -> f32 {
+        self.position
+            .map_or(board.width, |position| board.side_length(position))
+    }
+
+    /// Puts the paddle at `position`, kept whole within the side
+    pub fn place_paddle(&mut self, position: f32, board: BoardSize) {
+        self.paddle_position = position.clamp(
             0.0 + (self.paddle_width / 2.0),
-            10.0 - (self.paddle_width / 2.0),
+            self.side_length(board) - (self.paddle_width / 2.0),
         );
     }
 
-    pub fn move_towards(&mut self, position: f32) {
+    /// Applies the move queued since the last tick, at most one paddle step per tick
+    pub fn apply_pending_move(&mut self, board: BoardSize) {
+        let boost = self.pending_boost.take().unwrap_or(1.0);
+        if let Some(direction) = self.pending_direction.take() {
+            self.move_paddle_by(direction, boost, board);
+        }
+    }
+
+    /// Heads for `position`, aiming off the center of the paddle by a jitter from `rng`
+    /// once close enough
+    pub fn move_towards(&mut self, position: f32, board: BoardSize, dt: f32, rng: &mut impl Rng) {
         let mut target_position = position;
 
         if (position - self.paddle_position).abs() < self.paddle_width / 2.0 {
-            let offset = rand::random::<f32>() * (self.paddle_width / 2.0);
-            let mut rng = rand::rng();
+            let offset = rng.random::<f32>() * (self.paddle_width / 2.0);
             let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
 
             target_position = position + (offset * sign);
@@ -97,13 +179,21 @@ impl Player {
         }
 
         if self.paddle_position > target_position {
-            self.move_paddle(Direction::Negative);
+            self.move_ai_paddle(Direction::Negative, dt, board);
         } else {
-            self.move_paddle(Direction::Positive);
+            self.move_ai_paddle(Direction::Positive, dt, board);
         }
     }
 
-    pub fn calculate_ball_position(&self, ball: Ball, rec_step: i8) -> Option<f32> {
+    /// Where the ball will cross our side, `None` when it is not heading at us.
+    /// Bounces are only predicted off `occupied` free sides, as a player would return the ball
+    pub fn calculate_ball_position(
+        &self,
+        ball: Ball,
+        rec_step: i8,
+        board: BoardSize,
+        occupied: &[PlayerPosition],
+    ) -> Option<f32> {
         if rec_step > 2 {
             return None;
         }
@@ -115,7 +205,7 @@ impl Player {
                 } else {
                     let time = (0.0 - ball.position.y) / ball.velocity.y;
                     let x = ball.position.x + ball.velocity.x * time;
-                    if time >= 0.0 && (0.0..=10.0).contains(&x) {
+                    if time >= 0.0 && (0.0..=board.width).contains(&x) {
                         Some(x)
                     } else {
                         None
@@ -123,30 +213,37 @@ impl Player {
                 }
             }
             Some(PlayerPosition::Bottom) => {
-                if ball.velocity.y < 0.0 {
+                if ball.velocity.y <= 0.0 {
                     None
                 } else {
-                    let time = (10.0 - ball.position.y) / ball.velocity.y;
+                    let time = (board.height - ball.position.y) / ball.velocity.y;
                     let x = ball.position.x + ball.velocity.x * time;
-                    if time >= 0.0 && (0.0..=10.0).contains(&x) {
+                    let wall = if ball.velocity.x < 0.0 {
+                        PlayerPosition::Left
+                    } else {
+                        PlayerPosition::Right
+                    };
+                    if time >= 0.0 && (0.0..=board.width).contains(&x) {
                         Some(x)
+                    } else if occupied.contains(&wall) {
+                        None
                     } else {
                         let time_to_wall = if ball.velocity.x < 0.0 {
                             (0.0 + ball.radius - ball.position.x) / ball.velocity.x
                         } else {
-                            (10.0 - ball.radius - ball.position.x) / ball.velocity.x
+                            (board.width - ball.radius - ball.position.x) / ball.velocity.x
                         };
 
                         let mut new_ball = ball.clone();
                         new_ball.position.x = if ball.velocity.x < 0.0 {
                             ball.radius
                         } else {
-                            10.0 - ball.radius
+                            board.width - ball.radius
                         };
                         new_ball.position.y = ball.position.y + time_to_wall * ball.velocity.y;
                         new_ball.velocity.x = -ball.velocity.x;
 
-                        self.calculate_ball_position(new_ball, rec_step + 1)
+                        self.calculate_ball_position(new_ball, rec_step + 1, board, occupied)
                     }
                 }
             }
@@ -156,7 +253,7 @@ impl Player {
                 } else {
                     let time = (0.0 - ball.position.x) / ball.velocity.x;
                     let y = ball.position.y + ball.velocity.y * time;
-                    if time >= 0.0 && (0.0..=10.0).contains(&y) {
+                    if time >= 0.0 && (0.0..=board.height).contains(&y) {
                         Some(y)
                     } else {
                         None
@@ -164,12 +261,12 @@ impl Player {
                 }
             }
             Some(PlayerPosition::Right) => {
-                if ball.velocity.x < 0.0 {
+                if ball.velocity.x <= 0.0 {
                     None
                 } else {
-                    let time = (10.0 - ball.position.x) / ball.velocity.x;
+                    let time = (board.width - ball.position.x) / ball.velocity.x;
                     let y = ball.position.y + ball.velocity.y * time;
-                    if time >= 0.0 && (0.0..=10.0).contains(&y) {
+                    if time >= 0.0 && (0.0..=board.height).contains(&y) {
                         Some(y)
                     } else {
                         None
@@ -181,23 +278,87 @@ impl Player {
         side_intersection
     }
 
-    pub fn ai(&mut self, ball: Ball) {
-        let side_intersection: Option<f32> = self.calculate_ball_position(ball, 1);
+    /// Heads back to the middle of the side, standing still once close enough to not jitter
+    pub fn recenter(&mut self, board: BoardSize, dt: f32) {
+        let center = self.side_length(board) / 2.0;
+        if (self.paddle_position - center).abs() <= self.paddle_width / 4.0 {
+            return;
+        }
 
-        match side_intersection {
-            Some(x) => {
-                self.move_towards(x);
-            }
-            None => {
-                self.move_towards(5.0);
-            }
+        if self.paddle_position > center {
+            self.move_ai_paddle(Direction::Negative, dt, board);
+        } else {
+            self.move_ai_paddle(Direction::Positive, dt, board);
+        }
+    }
+
+    /// Moves the bot towards where the ball crosses its side, looking at the ball only
+    /// every `reaction_ticks` ticks and chasing the target it saw in between,
+    /// as far as it gets in `dt` ticks of game time
+    pub fn ai(
+        &mut self,
+        ball: Ball,
+        board: BoardSize,
+        occupied: &[PlayerPosition],
+        reaction_ticks: u32,
+        dt: f32,
+        rng: &mut impl Rng,
+    ) {
+        if self.ai_reaction_countdown == 0 {
+            self.ai_target = self.calculate_ball_position(ball, 1, board, occupied);
+            self.ai_reaction_countdown = reaction_ticks.saturating_sub(1);
+        } else {
+            self.ai_reaction_countdown -= 1;
+        }
+
+        match self.ai_target {
+            Some(x) => self.move_towards(x, board, dt, rng),
+            // the ball is heading at someone else, wait in the middle instead of chasing it
+            None => self.recenter(board, dt),
         }
     }
 }
 
+/// Characters a player name may contain besides letters and digits
+const USERNAME_PUNCTUATION: [char; 4] = [' ', '_', '-', '.'];
+
+/// Drops the characters a player name may not contain and cuts it to `max_length`,
+/// what is left may be empty
+pub fn sanitize_username(name: &str, max_length: usize) -> String {
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_alphanumeric() || USERNAME_PUNCTUATION.contains(c))
+        .collect();
+    let name: String = name.trim().chars().take(max_length).collect();
+    name.trim_end().to_string()
+}
+
+/// Breaks a tie on score, whoever reached it first ranks higher, players who never scored last
+pub fn score_tie_break(
+    a: Option<chrono::DateTime<chrono::Utc>>,
+    b: Option<chrono::DateTime<chrono::Utc>>,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Keeps a client supplied boost between a normal step and `MAX_PADDLE_BOOST`
+pub fn clamp_boost(boost: f32) -> f32 {
+    if boost.is_finite() {
+        boost.clamp(1.0, MAX_PADDLE_BOOST)
+    } else {
+        1.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::models::Vec2;
 
     #[test]
     fn test_move_paddle() {
@@ -206,18 +367,267 @@ mod tests {
         player.paddle_delta = 0.5;
         player.paddle_width = 1.0;
 
-        player.move_paddle(Direction::Positive);
+        player.move_paddle(Direction::Positive, BoardSize::square(10.0));
         assert_eq!(player.paddle_position, 5.5);
 
-        player.move_paddle(Direction::Negative);
+        player.move_paddle(Direction::Negative, BoardSize::square(10.0));
         assert_eq!(player.paddle_position, 5.0);
 
         player.paddle_position = 0.5;
-        player.move_paddle(Direction::Negative);
+        player.move_paddle(Direction::Negative, BoardSize::square(10.0));
         assert_eq!(player.paddle_position, 0.5);
 
         player.paddle_position = 9.5;
-        player.move_paddle(Direction::Positive);
+        player.move_paddle(Direction::Positive, BoardSize::square(10.0));
         assert_eq!(player.paddle_position, 9.5);
+
+        player.move_paddle(Direction::Positive, BoardSize::square(20.0));
+        assert_eq!(player.paddle_position, 10.0);
+    }
+
+    #[test]
+    fn test_increment_score_records_when() {
+        let mut player = Player::new("Player".to_string(), false);
+        assert_eq!(player.last_score_at, None);
+
+        player.increment_score();
+        let first = player.last_score_at.unwrap();
+        player.increment_score();
+        assert_eq!(player.score, 2);
+        assert!(player.last_score_at.unwrap() >= first);
+    }
+
+    #[test]
+    fn test_score_tie_break() {
+        let now = chrono::Utc::now();
+        let earlier = now - chrono::Duration::seconds(1);
+
+        assert_eq!(score_tie_break(Some(earlier), Some(now)), Ordering::Less);
+        assert_eq!(score_tie_break(Some(now), Some(earlier)), Ordering::Greater);
+        assert_eq!(score_tie_break(Some(now), None), Ordering::Less);
+        assert_eq!(score_tie_break(None, None), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_apply_pending_move() {
+        let mut player = Player::new("Test".to_string(), false);
+        player.paddle_position = 5.0;
+        player.paddle_delta = 0.5;
+
+        player.apply_pending_move(BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 5.0);
+
+        player.pending_direction = Some(Direction::Positive);
+        player.apply_pending_move(BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 5.5);
+        assert!(player.pending_direction.is_none());
+
+        // the queued move is consumed
+        player.apply_pending_move(BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 5.5);
+    }
+
+    #[test]
+    fn test_move_paddle_by() {
+        let mut player = Player::new("Test".to_string(), false);
+        player.paddle_position = 5.0;
+        player.paddle_delta = 0.5;
+        player.paddle_width = 1.0;
+
+        player.move_paddle_by(Direction::Positive, 2.0, BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 6.0);
+
+        // capped, and never slower than a normal step
+        player.move_paddle_by(Direction::Negative, 100.0, BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 6.0 - 0.5 * MAX_PADDLE_BOOST);
+        player.paddle_position = 5.0;
+        player.move_paddle_by(Direction::Positive, f32::NAN, BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 5.5);
+        player.move_paddle_by(Direction::Positive, 0.0, BoardSize::square(10.0));
+        assert_eq!(player.paddle_position, 6.0);
+    }
+
+    #[test]
+    fn test_sanitize_username() {
+        assert_eq!(sanitize_username("player_1", 20), "player_1");
+        assert_eq!(sanitize_username("  Jane Doe-2.0  ", 20), "Jane Doe-2.0");
+        assert_eq!(sanitize_username("a\u{1b}[31mred", 20), "a31mred");
+        assert_eq!(sanitize_username(&"x".repeat(100), 20), "x".repeat(20));
+        // cut right after a space, it is not kept at the end
+        assert_eq!(sanitize_username("abc def", 4), "abc");
+        assert_eq!(sanitize_username("<>{}", 20), "");
+    }
+
+    #[test]
+    fn test_paddle_stays_on_its_side_of_a_rectangular_board() {
+        let board = BoardSize {
+            width: 20.0,
+            height: 10.0,
+        };
+        let mut bottom = bot(PlayerPosition::Bottom, 19.0);
+        bottom.paddle_delta = 1.0;
+        bottom.is_ai = false;
+        bottom.move_paddle(Direction::Positive, board);
+        assert_eq!(bottom.paddle_position, 19.5);
+
+        let mut left = bot(PlayerPosition::Left, 9.0);
+        left.paddle_delta = 1.0;
+        left.is_ai = false;
+        left.move_paddle(Direction::Positive, board);
+        assert_eq!(left.paddle_position, 9.5);
+
+        // a ball crossing the bottom far right is within reach on a wide board only
+        let crossing = ball(17.0, 5.0, 0.0, 0.15);
+        assert_eq!(
+            bottom.calculate_ball_position(crossing.clone(), 1, board, &ALL),
+            Some(17.0)
+        );
+        assert_eq!(
+            bottom.calculate_ball_position(crossing, 1, BoardSize::square(10.0), &ALL),
+            None
+        );
+    }
+
+    fn bot(position: PlayerPosition, paddle_position: f32) -> Player {
+        let mut player = Player::new("bot".to_string(), true);
+        player.position = Some(position);
+        player.paddle_position = paddle_position;
+        player
+    }
+
+    fn ball(x: f32, y: f32, velocity_x: f32, velocity_y: f32) -> Ball {
+        let mut ball = Ball::new();
+        ball.position = Vec2 { x, y };
+        ball.velocity = Vec2 {
+            x: velocity_x,
+            y: velocity_y,
+        };
+        ball
+    }
+
+    const ALL: [PlayerPosition; 4] = [
+        PlayerPosition::Top,
+        PlayerPosition::Bottom,
+        PlayerPosition::Left,
+        PlayerPosition::Right,
+    ];
+
+    #[test]
+    fn test_ai_recenters_when_ball_moves_away() {
+        let mut player = bot(PlayerPosition::Bottom, 8.0);
+
+        // the ball moves up towards the top player, right above our paddle
+        player.ai(
+            ball(8.0, 7.0, 0.0, -0.15),
+            BoardSize::square(10.0),
+            &ALL,
+            1,
+            1.0,
+            &mut rand::rng(),
+        );
+
+        assert!(player.paddle_position < 8.0);
+    }
+
+    #[test]
+    fn test_ai_ignores_ball_heading_at_other_player() {
+        // heading at the left player, it would only reach the bottom after a bounce
+        let heading_left = ball(2.0, 7.0, -0.1, 0.1);
+
+        let mut player = bot(PlayerPosition::Bottom, 5.0);
+        player.ai(
+            heading_left.clone(),
+            BoardSize::square(10.0),
+            &ALL,
+            1,
+            1.0,
+            &mut rand::rng(),
+        );
+        assert_eq!(player.paddle_position, 5.0); // already centered, no jitter
+
+        // with the left side being a wall the bounce is worth chasing
+        let mut player = bot(PlayerPosition::Bottom, 5.0);
+        player.ai(
+            heading_left,
+            BoardSize::square(10.0),
+            &[PlayerPosition::Top, PlayerPosition::Bottom],
+            1,
+            1.0,
+            &mut rand::rng(),
+        );
+        assert!(player.paddle_position < 5.0);
+    }
+
+    #[test]
+    fn test_ai_intercepts_ball_heading_at_it() {
+        let mut player = bot(PlayerPosition::Left, 5.0);
+
+        player.ai(
+            ball(5.0, 2.0, -0.15, 0.0),
+            BoardSize::square(10.0),
+            &ALL,
+            1,
+            1.0,
+            &mut rand::rng(),
+        );
+
+        assert!(player.paddle_position < 5.0);
+    }
+
+    #[test]
+    fn test_ai_moves_with_game_time() {
+        let moved = |dt| {
+            let mut player = bot(PlayerPosition::Left, 5.0);
+            player.ai(
+                ball(5.0, 2.0, -0.15, 0.0),
+                BoardSize::square(10.0),
+                &ALL,
+                1,
+                dt,
+                &mut rand::rng(),
+            );
+            5.0 - player.paddle_position
+        };
+
+        // slowed down to a quarter, the bot covers a quarter of the distance
+        assert!((moved(0.25) * 4.0 - moved(1.0)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_ai_reaction_delay() {
+        let mut player = bot(PlayerPosition::Left, 5.0);
+
+        player.ai(
+            ball(5.0, 2.0, -0.15, 0.0),
+            BoardSize::square(10.0),
+            &ALL,
+            3,
+            1.0,
+            &mut rand::rng(),
+        );
+        assert_eq!(player.ai_target, Some(2.0));
+
+        // the ball turned towards the other end, but the bot does not see it yet
+        for _ in 0..2 {
+            player.ai(
+                ball(5.0, 8.0, -0.15, 0.0),
+                BoardSize::square(10.0),
+                &ALL,
+                3,
+                1.0,
+                &mut rand::rng(),
+            );
+            assert_eq!(player.ai_target, Some(2.0));
+        }
+
+        player.ai(
+            ball(5.0, 8.0, -0.15, 0.0),
+            BoardSize::square(10.0),
+            &ALL,
+            3,
+            1.0,
+            &mut rand::rng(),
+        );
+        assert_eq!(player.ai_target, Some(8.0));
     }
 }
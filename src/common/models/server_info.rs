@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// Where the server takes game traffic, for clients to find the UDP socket on their own
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub udp_port: u16,
+    /// Address players send UDP to when it differs from the API host, e.g. behind NAT or Docker
+    pub advertised_udp_addr: Option<String>,
+}
+
+impl ServerInfo {
+    /// The advertised address, or the UDP port on the host the API was reached on
+    pub fn socket_addr(&self, api_url: &str) -> String {
+        if let Some(addr) = &self.advertised_udp_addr {
+            return addr.clone();
+        }
+        let host = api_url
+            .split_once("://")
+            .map_or(api_url, |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+        format!("{}:{}", host, self.udp_port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_addr() {
+        let info = ServerInfo {
+            udp_port: 34254,
+            advertised_udp_addr: None,
+        };
+        assert_eq!(info.socket_addr("http://127.0.0.1:3000"), "127.0.0.1:34254");
+        assert_eq!(
+            info.socket_addr("https://quadropong.example/"),
+            "quadropong.example:34254"
+        );
+
+        let info = ServerInfo {
+            udp_port: 34254,
+            advertised_udp_addr: Some("203.0.113.7:40000".to_string()),
+        };
+        assert_eq!(
+            info.socket_addr("http://127.0.0.1:3000"),
+            "203.0.113.7:40000"
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+/// A game setting that was rejected, along with why
+#[derive(Error, Debug)]
+#[error("Invalid setting `{field}`: {reason}")]
+pub struct SettingsError {
+    pub field: &'static str,
+    pub reason: String,
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::http::{header, HeaderMap};
+use subtle::ConstantTimeEq;
+
+/// Token operators send as `Authorization: Bearer <token>` to use the admin endpoints,
+/// without one configured the endpoints are open to everyone
+#[derive(Clone, Default)]
+pub struct AdminToken(Option<Arc<str>>);
+
+impl AdminToken {
+    /// An empty token is taken as none, so a blank variable does not lock anyone out
+    pub fn new(token: Option<String>) -> Self {
+        Self(token.filter(|token| !token.is_empty()).map(Arc::from))
+    }
+
+    pub fn authorizes(&self, headers: &HeaderMap) -> bool {
+        let Some(expected) = &self.0 else {
+            return true;
+        };
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            // compared in constant time, how much of a guess matched must not show in the timing
+            .is_some_and(|token| bool::from(token.as_bytes().ct_eq(expected.as_bytes())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, authorization.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_authorizes() {
+        let token = AdminToken::new(Some("secret".to_string()));
+        assert!(token.authorizes(&headers("Bearer secret")));
+        assert!(!token.authorizes(&headers("Bearer wrong")));
+        assert!(!token.authorizes(&headers("secret")));
+        assert!(!token.authorizes(&HeaderMap::new()));
+
+        // no token configured, or a blank one, keeps the endpoints open
+        for token in [AdminToken::new(None), AdminToken::new(Some(String::new()))] {
+            assert!(token.authorizes(&HeaderMap::new()));
+            assert!(token.authorizes(&headers("Bearer anything")));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+use crate::common::{GameError, SettingsError};
+
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("Invalid setting `{field}`: {reason}")]
+    InvalidSetting { field: &'static str, reason: String },
+    #[error("Too many games created, try again later")]
+    TooManyRequests,
+    #[error("The server has too many games, try again later")]
+    ServerFull,
+    #[error("Invalid id")]
+    InvalidId,
+    #[error("Game not found")]
+    GameNotFound,
+    #[error("Wrong password")]
+    WrongPassword,
+    #[error("The game is full")]
+    GameFull,
+    #[error("The game has already started")]
+    GameAlreadyStarted,
+    #[error("The game has not finished yet")]
+    GameNotFinished,
+    #[error("Missing or wrong admin token")]
+    Unauthorized,
+    #[error("Internal server error")]
+    Internal,
+}
+
+impl From<GameError> for ApiError {
+    fn from(error: GameError) -> Self {
+        match error {
+            GameError::GameFull => ApiError::GameFull,
+            GameError::GameNotFound => ApiError::GameNotFound,
+            _ => ApiError::Internal,
+        }
+    }
+}
+
+impl From<SettingsError> for ApiError {
+    fn from(error: SettingsError) -> Self {
+        ApiError::InvalidSetting {
+            field: error.field,
+            reason: error.reason,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ApiError::InvalidSetting { .. } => StatusCode::BAD_REQUEST,
+            ApiError::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::ServerFull => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::InvalidId | ApiError::GameAlreadyStarted | ApiError::GameNotFinished => {
+                StatusCode::BAD_REQUEST
+            }
+            ApiError::GameNotFound => StatusCode::NOT_FOUND,
+            ApiError::WrongPassword => StatusCode::FORBIDDEN,
+            ApiError::GameFull => StatusCode::CONFLICT,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, self.to_string()).into_response()
+    }
+}
@@ -1,44 +1,136 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    routing::{get, post},
+    extract::{ConnectInfo, FromRef, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
     Json, Router,
 };
 
-use std::sync::Arc;
+use serde::Deserialize;
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::common::{models::GameState, Game, GameRooms, JoinGameRequest, Player};
+use crate::common::{
+    models::CreateGameRequest,
+    models::{
+        sanitize_username, GameResultsDto, GameState, LeaderboardEntry, QuickMatchRequest,
+        QuickMatchResponse, ServerInfo, DEFAULT_MAX_PLAYERS,
+    },
+    Game, GameRooms, JoinGameRequest, JoinGameResponse, Player, PlayerPosition,
+};
+
+use super::{AdminToken, ApiError, CreationLimiter};
+
+const DEFAULT_LEADERBOARD_LIMIT: usize = 10;
+
+/// State of the game routes, handlers take the part they need
+#[derive(Clone)]
+pub struct AppState {
+    pub game_rooms: Arc<Mutex<GameRooms>>,
+    pub creation_limiter: Arc<Mutex<CreationLimiter>>,
+    pub admin_token: AdminToken,
+}
+
+impl FromRef<AppState> for Arc<Mutex<GameRooms>> {
+    fn from_ref(state: &AppState) -> Self {
+        state.game_rooms.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Mutex<CreationLimiter>> {
+    fn from_ref(state: &AppState) -> Self {
+        state.creation_limiter.clone()
+    }
+}
+
+impl FromRef<AppState> for AdminToken {
+    fn from_ref(state: &AppState) -> Self {
+        state.admin_token.clone()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LeaderboardQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultsFormat {
+    #[default]
+    Json,
+    Csv, // for pasting into spreadsheets
+}
+
+#[derive(Deserialize)]
+pub struct ResultsQuery {
+    #[serde(default)]
+    pub format: ResultsFormat,
+}
 
 pub async fn join_game(
     State(app_state): State<Arc<Mutex<GameRooms>>>,
     Path(game_id): Path<String>,
     Json(payload): Json<JoinGameRequest>,
-) -> Result<Json<Player>, StatusCode> {
-    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| StatusCode::BAD_REQUEST)?;
+) -> Result<Json<JoinGameResponse>, ApiError> {
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| ApiError::InvalidId)?;
 
     let mut game_rooms = app_state.lock().await;
+    let server_info = game_rooms.server_info.clone();
+    let max_username_length = game_rooms.config.max_username_length;
 
     let game = game_rooms
         .lobbies
         .get_mut(&game_uuid)
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .ok_or(ApiError::GameNotFound)?;
+
+    if !game.check_password(payload.password.as_deref()) {
+        return Err(ApiError::WrongPassword);
+    }
 
     if game.state != GameState::WaitingForPlayers {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::GameAlreadyStarted);
     }
 
-    // Generate player name based on request or player count
-    let player_name = match payload.username {
-        Some(name) if !name.is_empty() => name,
-        _ => {
-            let player_number = game.players.len() + 1;
-            format!("player_{}", player_number)
-        }
-    };
+    seat_player(
+        game,
+        payload.username,
+        payload.preferred_position,
+        server_info,
+        max_username_length,
+    )
+    .map(Json)
+}
 
-    let player_positions = game.assign_position();
+/// The name a joining player is known by, their username kept to the allowed characters
+/// and length, or a numbered default when nothing of it is left
+fn player_name(game: &Game, username: Option<String>, max_length: usize) -> String {
+    let name = username
+        .map(|name| sanitize_username(&name, max_length))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| format!("player_{}", game.players.len() + 1));
+    game.unique_name(&name)
+}
+
+/// Seats a new player in a game that is known to be waiting for players
+fn seat_player(
+    game: &mut Game,
+    username: Option<String>,
+    preferred_position: Option<PlayerPosition>,
+    server_info: Option<ServerInfo>,
+    max_username_length: usize,
+) -> Result<JoinGameResponse, ApiError> {
+    let player_name = player_name(game, username, max_username_length);
+
+    let player_positions = game.assign_preferred_position(preferred_position);
 
     let mut player = Player::new(player_name, false);
 
@@ -46,26 +138,118 @@ pub async fn join_game(
         player.position = Some(position);
     }
 
-    let player_copy = player.clone();
+    let player_id = player.id;
+    let reconnect_token = player.issue_reconnect_token();
+
+    game.add_player(player)?;
+
+    // the game adjusts the paddle to its settings
+    game.get_player(&player_id)
+        .cloned()
+        .map(|player| JoinGameResponse {
+            player,
+            reconnect_token,
+            server_info,
+        })
+        .ok_or(ApiError::Internal)
+}
+
+/// Joins the oldest open public game, or creates one when there is none
+pub async fn quick_match(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    State(creation_limiter): State<Arc<Mutex<CreationLimiter>>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    payload: Option<Json<QuickMatchRequest>>,
+) -> Result<Json<QuickMatchResponse>, ApiError> {
+    let Json(request) = payload.unwrap_or_default();
+
+    let mut game_rooms = app_state.lock().await;
+    let server_info = game_rooms.server_info.clone();
+    let max_username_length = game_rooms.config.max_username_length;
+
+    let open_game_id = game_rooms
+        .find_joinable()
+        .into_iter()
+        .find(|game| game.check_password(None))
+        .map(|game| game.id);
+
+    let game_id = match open_game_id {
+        Some(game_id) => game_id,
+        None => {
+            // a new game counts against the same limits as creating it directly
+            if game_rooms.lobbies.len() as u64 >= game_rooms.config.max_lobbies {
+                return Err(ApiError::ServerFull);
+            }
+            if !creation_limiter
+                .lock()
+                .await
+                .try_acquire(addr.ip(), Instant::now())
+            {
+                return Err(ApiError::TooManyRequests);
+            }
+            game_rooms.create_game()
+        }
+    };
 
-    game.add_player(player)
-        .map_err(|_e| StatusCode::INTERNAL_SERVER_ERROR)
-        .map(|_| Json(player_copy))
+    let game = game_rooms
+        .find_lobby_mut(game_id)
+        .ok_or(ApiError::Internal)?;
+    let joined = seat_player(
+        game,
+        request.username,
+        None,
+        server_info,
+        max_username_length,
+    )?;
+
+    Ok(Json(QuickMatchResponse {
+        game: game.clone(),
+        joined,
+    }))
 }
 
 // Endpoint to create a new game
 pub async fn create_game(
     State(app_state): State<Arc<Mutex<GameRooms>>>,
-) -> (StatusCode, Json<Game>) {
+    State(creation_limiter): State<Arc<Mutex<CreationLimiter>>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    payload: Option<Json<CreateGameRequest>>,
+) -> Result<Json<Game>, ApiError> {
+    let Json(request) = payload.unwrap_or_default();
+    let settings = request.settings.validate_and_clamp()?;
+
     let mut game_rooms = app_state.lock().await;
 
-    let new_game_id = game_rooms.create_game();
-    let game = game_rooms.find_lobby(new_game_id);
+    // checked first, a request turned away here takes no token
+    if game_rooms.lobbies.len() as u64 >= game_rooms.config.max_lobbies {
+        return Err(ApiError::ServerFull);
+    }
+
+    // the endpoint is unauthenticated, keep a single source from filling the memory with games
+    if !creation_limiter
+        .lock()
+        .await
+        .try_acquire(addr.ip(), Instant::now())
+    {
+        return Err(ApiError::TooManyRequests);
+    }
+
+    let new_game_id = game_rooms.create_game_with_settings(settings);
 
-    match game {
-        Some(game) => (StatusCode::OK, Json(game.clone())),
-        None => (StatusCode::INTERNAL_SERVER_ERROR, Json(Game::new())),
+    if let Some(game) = game_rooms.find_lobby_mut(new_game_id) {
+        if let Some(password) = request.password {
+            game.set_password(&password);
+        }
+        if let Some(name) = request.name {
+            game.set_name(&name);
+        }
     }
+
+    game_rooms
+        .find_lobby(new_game_id)
+        .cloned()
+        .map(Json)
+        .ok_or(ApiError::Internal)
 }
 
 pub async fn get_games(
@@ -78,6 +262,17 @@ pub async fn get_games(
     (StatusCode::OK, Json(result))
 }
 
+/// Lobbies a player can join right away
+pub async fn get_joinable_games(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+) -> (StatusCode, Json<Vec<Game>>) {
+    let game_rooms = app_state.lock().await;
+
+    let result: Vec<Game> = game_rooms.find_joinable().into_iter().cloned().collect();
+
+    (StatusCode::OK, Json(result))
+}
+
 pub async fn get_game_by_id(
     State(app_state): State<Arc<Mutex<GameRooms>>>,
     Path(game_id): Path<String>,
@@ -94,23 +289,26 @@ pub async fn get_game_by_id(
         .ok_or(StatusCode::NOT_FOUND)
 }
 
-pub async fn add_bot(
+pub async fn get_player_by_id(
     State(app_state): State<Arc<Mutex<GameRooms>>>,
-    Path(game_id): Path<String>,
+    Path((game_id, player_id)): Path<(String, String)>,
 ) -> Result<Json<Player>, StatusCode> {
-    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| StatusCode::BAD_REQUEST)?;
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let player_uuid = Uuid::parse_str(&player_id).map_err(|_| StatusCode::BAD_REQUEST)?;
 
-    let mut game_rooms = app_state.lock().await;
+    let game_rooms = app_state.lock().await;
 
-    let game = game_rooms
+    game_rooms
         .lobbies
-        .get_mut(&game_uuid)
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    if (game.players.len() + 1) > 4 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+        .get(&game_uuid)
+        .and_then(|game| game.get_player(&player_uuid))
+        .cloned()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
 
+/// Seats a new bot in `game`, the caller checks there is room for it
+fn seat_bot(game: &mut Game) -> Result<Player, ApiError> {
     let player_name = format!("bot_{}", game.players.len() + 1);
 
     let mut player = Player::new(player_name, true);
@@ -121,61 +319,118 @@ pub async fn add_bot(
         player.position = Some(position);
     }
 
-    let player_copy = player.clone();
+    let player_id = player.id;
+
+    game.add_player(player)?;
+
+    // the game adjusts the paddle to its settings
+    game.get_player(&player_id)
+        .cloned()
+        .ok_or(ApiError::Internal)
+}
+
+pub async fn add_bot(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    Path(game_id): Path<String>,
+) -> Result<Json<Player>, ApiError> {
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| ApiError::InvalidId)?;
+
+    let mut game_rooms = app_state.lock().await;
+
+    let game = game_rooms
+        .lobbies
+        .get_mut(&game_uuid)
+        .ok_or(ApiError::GameNotFound)?;
+
+    if game.is_full() {
+        return Err(ApiError::GameFull);
+    }
+
+    seat_bot(game).map(Json)
+}
+
+/// Adds bots until the game has `max_players`, an already full game gets none.
+/// Admin only, it takes every free seat at once
+pub async fn fill_bots(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    State(admin_token): State<AdminToken>,
+    Path(game_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<Player>>, ApiError> {
+    if !admin_token.authorizes(&headers) {
+        return Err(ApiError::Unauthorized);
+    }
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| ApiError::InvalidId)?;
+
+    let mut game_rooms = app_state.lock().await;
+
+    let game = game_rooms
+        .lobbies
+        .get_mut(&game_uuid)
+        .ok_or(ApiError::GameNotFound)?;
+
+    let mut bots = Vec::new();
+    while !game.is_full() && game.players.len() < DEFAULT_MAX_PLAYERS {
+        bots.push(seat_bot(game)?);
+    }
 
-    game.add_player(player)
-        .map_err(|_e| StatusCode::INTERNAL_SERVER_ERROR)
-        .map(|_| Json(player_copy))
+    Ok(Json(bots))
 }
 
 pub async fn restart_game(
     State(app_state): State<Arc<Mutex<GameRooms>>>,
     Path(game_id): Path<String>,
     Json(payload): Json<JoinGameRequest>,
-) -> Result<Json<Player>, StatusCode> {
-    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| StatusCode::BAD_REQUEST)?;
+) -> Result<Json<JoinGameResponse>, ApiError> {
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| ApiError::InvalidId)?;
 
     let mut game_rooms = app_state.lock().await;
+    let server_info = game_rooms.server_info.clone();
+    let max_username_length = game_rooms.config.max_username_length;
 
-    let game = game_rooms.lobbies.get_mut(&game_uuid);
+    let game = game_rooms
+        .lobbies
+        .get_mut(&game_uuid)
+        .ok_or(ApiError::GameNotFound)?;
 
-    let game = match game {
-        Some(game) => game,
-        None => return Err(StatusCode::NOT_FOUND),
-    };
+    if !game.check_password(payload.password.as_deref()) {
+        return Err(ApiError::WrongPassword);
+    }
 
     if game.state == GameState::Finished {
-        game.set_game_state(GameState::WaitingForPlayers);
-        game.started_at = None;
-        game.finished_at = None;
-        game.players.clear();
+        game.play_again()?;
     }
 
     if game.state != GameState::WaitingForPlayers {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::GameAlreadyStarted);
     }
 
-    let player_name = match payload.username {
-        Some(name) if !name.is_empty() => name,
-        _ => {
-            let player_number = game.players.len() + 1;
-            format!("player_{}", player_number)
-        }
-    };
+    seat_player(
+        game,
+        payload.username,
+        payload.preferred_position,
+        server_info,
+        max_username_length,
+    )
+    .map(Json)
+}
 
-    let player_positions = game.assign_position();
+pub async fn rematch(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    Path(game_id): Path<String>,
+) -> Result<Json<Game>, ApiError> {
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| ApiError::InvalidId)?;
 
-    let mut player = Player::new(player_name, false);
+    let mut game_rooms = app_state.lock().await;
 
-    if let Some(position) = player_positions {
-        player.position = Some(position);
-    }
+    let game = game_rooms
+        .lobbies
+        .get_mut(&game_uuid)
+        .ok_or(ApiError::GameNotFound)?;
 
-    let player_copy = player.clone();
+    game.rematch().map_err(|_e| ApiError::GameNotFinished)?;
 
-    game.add_player(player)
-        .map_err(|_e| StatusCode::INTERNAL_SERVER_ERROR)
-        .map(|_| Json(player_copy))
+    Ok(Json(game.clone()))
 }
 
 pub async fn remove_bot(
@@ -200,33 +455,230 @@ pub async fn remove_bot(
     Ok(())
 }
 
+/// Final standings, only available once the game is finished
+pub async fn get_game_results(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    Path(game_id): Path<String>,
+    Query(query): Query<ResultsQuery>,
+) -> Result<Response, StatusCode> {
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| StatusCode::BAD_REQUEST)?;
+
+    let game_rooms = app_state.lock().await;
+
+    let game = game_rooms
+        .lobbies
+        .get(&game_uuid)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if game.state != GameState::Finished {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let results = GameResultsDto::from(game);
+    Ok(match query.format {
+        ResultsFormat::Json => Json(results).into_response(),
+        ResultsFormat::Csv => {
+            ([(header::CONTENT_TYPE, "text/csv")], results.to_csv()).into_response()
+        }
+    })
+}
+
+/// Lets operators clear stuck games without waiting for the cleaner
+pub async fn delete_game(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    State(admin_token): State<AdminToken>,
+    Path(game_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<(), StatusCode> {
+    if !admin_token.authorizes(&headers) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_e| StatusCode::BAD_REQUEST)?;
+
+    app_state
+        .lock()
+        .await
+        .purge_game(game_uuid)
+        .map(|_| ())
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+pub async fn get_leaderboard(
+    State(app_state): State<Arc<Mutex<GameRooms>>>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Json<Vec<LeaderboardEntry>> {
+    let game_rooms = app_state.lock().await;
+
+    Json(
+        game_rooms
+            .leaderboard
+            .top(query.limit.unwrap_or(DEFAULT_LEADERBOARD_LIMIT)),
+    )
+}
+
+/// Liveness probe, answers as long as the HTTP server is running
+pub async fn health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Readiness probe, answers once the UDP socket and the game loop are up
+pub async fn ready(State(ready): State<Arc<AtomicBool>>) -> StatusCode {
+    if ready.load(Ordering::Acquire) {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// UDP port and advertised address, so clients don't need the socket address configured
+pub async fn get_server_info(State(info): State<ServerInfo>) -> Json<ServerInfo> {
+    Json(info)
+}
+
+pub fn server_info_router(info: ServerInfo) -> Router {
+    Router::new()
+        .route("/server/info", get(get_server_info)) // where to send game traffic
+        .with_state(info)
+}
+
 // Build the Axum app with routes
-pub fn app(game_rooms: Arc<Mutex<GameRooms>>) -> Router {
+pub fn app(
+    game_rooms: Arc<Mutex<GameRooms>>,
+    is_ready: Arc<AtomicBool>,
+    creation_limiter: CreationLimiter,
+    admin_token: AdminToken,
+) -> Router {
+    let probes = Router::new()
+        .route("/health", get(health)) // liveness probe
+        .route("/ready", get(ready)) // readiness probe
+        .with_state(is_ready);
+
     Router::new()
         .route("/game/:id", get(get_game_by_id)) // get game by id
+        .route("/game/:id", delete(delete_game)) // remove a game right away, admin only
         .route("/game", get(get_games)) // get list of all games
         .route("/game", post(create_game)) // create a new game
+        .route("/game/joinable", get(get_joinable_games)) // waiting games with a free seat
+        .route("/quickmatch", post(quick_match)) // join any open game or create one
+        .route("/game/:id/player/:player_id", get(get_player_by_id)) // get a single player of a game
+        .route("/game/:id/results", get(get_game_results)) // final standings of a finished game
         .route("/game/:id/join", post(join_game)) // join a game
         .route("/game/:id/add_bot", post(add_bot)) // add a bot to a game
+        .route("/game/:id/fill_bots", post(fill_bots)) // add bots until the game is full, admin only
         .route("/game/:id/play_again", post(restart_game)) // add a bot to a game
         .route("/game/:id/remove_bot", post(remove_bot)) // remove a bot from a game
-        .with_state(game_rooms)
+        .route("/game/:id/rematch", post(rematch)) // restart a finished game with the same players
+        .route("/leaderboard", get(get_leaderboard)) // get top players across games
+        .with_state(AppState {
+            game_rooms,
+            creation_limiter: Arc::new(Mutex::new(creation_limiter)),
+            admin_token,
+        })
+        .merge(probes)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::models::{GameConfig, MAX_LOBBY_NAME_LENGTH};
+    use crate::common::PlayerPosition;
     use axum::body::Body;
+    use axum::extract::connect_info::MockConnectInfo;
     use axum::http::{Request, StatusCode};
     use http_body_util::BodyExt;
     use serde_json::json;
     use tower::ServiceExt;
 
+    fn test_app(game_rooms: Arc<Mutex<GameRooms>>) -> Router {
+        app(
+            game_rooms,
+            Arc::new(AtomicBool::new(true)),
+            CreationLimiter::default(),
+            AdminToken::default(),
+        )
+        .layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+    }
+
+    fn create_game_request() -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/game")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let response = app(
+            Arc::new(Mutex::new(GameRooms::new())),
+            Arc::new(AtomicBool::new(false)),
+            CreationLimiter::default(),
+            AdminToken::default(),
+        )
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_server_info() {
+        let info = ServerInfo {
+            udp_port: 34254,
+            advertised_udp_addr: Some("203.0.113.7:40000".to_string()),
+        };
+        let response = server_info_router(info.clone())
+            .oneshot(
+                Request::builder()
+                    .uri("/server/info")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let received: ServerInfo = serde_json::from_slice(&body).unwrap();
+        assert_eq!(received, info);
+    }
+
+    #[tokio::test]
+    async fn test_ready() {
+        let is_ready = Arc::new(AtomicBool::new(false));
+        let router = app(
+            Arc::new(Mutex::new(GameRooms::new())),
+            is_ready.clone(),
+            CreationLimiter::default(),
+            AdminToken::default(),
+        );
+
+        let request = || {
+            Request::builder()
+                .uri("/ready")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        is_ready.store(true, Ordering::Release);
+        let response = router.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_create_game() {
         let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -247,15 +699,16 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_games() {
+    async fn test_create_game_with_settings() {
         let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
-                    .method("GET")
+                    .method("POST")
                     .uri("/game")
-                    .body(Body::empty())
+                    .header("content-type", "application/json")
+                    .body(json!({ "board_size": 20.0 }).to_string())
                     .unwrap(),
             )
             .await
@@ -264,67 +717,466 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = response.into_body().collect().await.unwrap().to_bytes();
-        let body: Vec<Game> = serde_json::from_slice(&body).unwrap();
-
-        assert!(body.is_empty());
+        let body: Game = serde_json::from_slice(&body).unwrap();
 
-        game_rooms.lock().await.create_game();
+        assert_eq!(body.settings.board_size, 20.0);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
-                    .method("GET")
+                    .method("POST")
                     .uri("/game")
-                    .body(Body::empty())
+                    .header("content-type", "application/json")
+                    .body(json!({ "board_size": -1.0 }).to_string())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
-
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
         let body = response.into_body().collect().await.unwrap().to_bytes();
-        let body: Vec<Game> = serde_json::from_slice(&body).unwrap();
-
-        assert_eq!(body.len(), 1);
-        assert_eq!(
-            body[0],
-            game_rooms
-                .lock()
-                .await
-                .lobbies
-                .values()
-                .next()
-                .unwrap()
-                .clone()
-        );
+        assert!(String::from_utf8_lossy(&body).contains("board_size"));
+        assert_eq!(game_rooms.lock().await.lobbies.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_get_game_by_id() {
+    async fn test_create_named_game() {
         let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let long_name = "x".repeat(MAX_LOBBY_NAME_LENGTH * 2);
+
+        for (name, expected) in [
+            ("Friday pong", "Friday pong".to_string()),
+            (long_name.as_str(), "x".repeat(MAX_LOBBY_NAME_LENGTH)),
+        ] {
+            let response = test_app(game_rooms.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/game")
+                        .header("content-type", "application/json")
+                        .body(json!({ "name": name }).to_string())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
 
-        let game_id = game_rooms.lock().await.create_game();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let game: Game = serde_json::from_slice(&body).unwrap();
+            assert_eq!(game.name, Some(expected));
+        }
 
-        let response = app(game_rooms.clone())
+        // listed with its name
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("GET")
-                    .uri(format!("/game/{}", game_id))
+                    .uri("/game")
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-
-        assert_eq!(response.status(), StatusCode::OK);
-
         let body = response.into_body().collect().await.unwrap().to_bytes();
-        let body: Game = serde_json::from_slice(&body).unwrap();
-
-        assert_eq!(body, game_rooms.lock().await.lobbies[&game_id]);
+        let games: Vec<Game> = serde_json::from_slice(&body).unwrap();
+        assert!(games
+            .iter()
+            .any(|game| game.name.as_deref() == Some("Friday pong")));
+    }
 
-        let response = app(game_rooms.clone())
+    #[tokio::test]
+    async fn test_join_game_with_custom_paddle() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/game")
+                    .header("content-type", "application/json")
+                    .body(json!({ "paddle_width": 2.5, "paddle_speed": 0.6 }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let game: Game = serde_json::from_slice(&body).unwrap();
+
+        for username in ["first", "second"] {
+            let response = test_app(game_rooms.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(format!("/game/{}/join", game.id))
+                        .header("content-type", "application/json")
+                        .body(json!({ "username": username }).to_string())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let joined: JoinGameResponse = serde_json::from_slice(&body).unwrap();
+            let mut player = joined.player;
+            assert_eq!(player.paddle_width, 2.5);
+            assert_eq!(player.paddle_delta, 0.6);
+            // the token is not serialized with the player
+            assert_eq!(player.reconnect_token, None);
+            player.reconnect_token = Some(joined.reconnect_token);
+            assert_eq!(
+                game_rooms.lock().await.lobbies[&game.id].players[&player.id],
+                player
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_game_throttled_per_address() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let router = app(
+            game_rooms.clone(),
+            Arc::new(AtomicBool::new(true)),
+            CreationLimiter::new(3, std::time::Duration::from_secs(60)),
+            AdminToken::default(),
+        );
+        let from = |ip: [u8; 4]| {
+            router
+                .clone()
+                .layer(MockConnectInfo(SocketAddr::from((ip, 4000))))
+        };
+
+        let mut statuses = Vec::new();
+        for _ in 0..5 {
+            let response = from([10, 0, 0, 1])
+                .oneshot(create_game_request())
+                .await
+                .unwrap();
+            statuses.push(response.status());
+        }
+        assert_eq!(&statuses[..3], &[StatusCode::OK; 3]);
+        assert_eq!(&statuses[3..], &[StatusCode::TOO_MANY_REQUESTS; 2]);
+        assert_eq!(game_rooms.lock().await.lobbies.len(), 3);
+
+        // another address is not affected
+        let response = from([10, 0, 0, 2])
+            .oneshot(create_game_request())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_create_game_global_cap() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::with_config(GameConfig {
+            max_lobbies: 2,
+            ..Default::default()
+        })));
+        let first = game_rooms.lock().await.create_game();
+        game_rooms.lock().await.create_game();
+        // a single game per address, used up if the rejected request took a token
+        let router = app(
+            game_rooms.clone(),
+            Arc::new(AtomicBool::new(true)),
+            CreationLimiter::new(1, std::time::Duration::from_secs(60)),
+            AdminToken::default(),
+        )
+        .layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+
+        let response = router.clone().oneshot(create_game_request()).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(game_rooms.lock().await.lobbies.len(), 2);
+
+        game_rooms.lock().await.lobbies.remove(&first);
+        let response = router.oneshot(create_game_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_join_game_returns_server_info() {
+        let server_info = ServerInfo {
+            udp_port: 34254,
+            advertised_udp_addr: Some("203.0.113.7:40000".to_string()),
+        };
+        let mut rooms = GameRooms::new();
+        rooms.server_info = Some(server_info.clone());
+        let game_id = rooms.create_game();
+        let game_rooms = Arc::new(Mutex::new(rooms));
+
+        let response = test_app(game_rooms)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/join", game_id))
+                    .header("content-type", "application/json")
+                    .body(json!({ "username": "test" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let joined: JoinGameResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(joined.server_info, Some(server_info));
+    }
+
+    #[tokio::test]
+    async fn test_get_games() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/game")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Vec<Game> = serde_json::from_slice(&body).unwrap();
+
+        assert!(body.is_empty());
+
+        game_rooms.lock().await.create_game();
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/game")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Vec<Game> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.len(), 1);
+        assert_eq!(
+            body[0],
+            game_rooms
+                .lock()
+                .await
+                .lobbies
+                .values()
+                .next()
+                .unwrap()
+                .clone()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_joinable_games() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let joinable_id = game_rooms.lock().await.create_game();
+        let started_id = game_rooms.lock().await.create_game();
+        game_rooms
+            .lock()
+            .await
+            .lobbies
+            .get_mut(&started_id)
+            .unwrap()
+            .set_game_state(GameState::Active);
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/game/joinable")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Vec<Game> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].id, joinable_id);
+    }
+
+    async fn post_quick_match(game_rooms: Arc<Mutex<GameRooms>>) -> QuickMatchResponse {
+        let response = test_app(game_rooms)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/quickmatch")
+                    .header("content-type", "application/json")
+                    .body(json!({ "username": "quick" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_quick_match_joins_open_game() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let open_id = game_rooms.lock().await.create_game();
+        // private and started games are left alone
+        let private_id = game_rooms.lock().await.create_game();
+        let started_id = game_rooms.lock().await.create_game();
+        {
+            let mut game_rooms = game_rooms.lock().await;
+            game_rooms
+                .lobbies
+                .get_mut(&private_id)
+                .unwrap()
+                .set_password("secret");
+            game_rooms
+                .lobbies
+                .get_mut(&started_id)
+                .unwrap()
+                .set_game_state(GameState::Active);
+        }
+
+        let matched = post_quick_match(game_rooms.clone()).await;
+
+        assert_eq!(matched.game.id, open_id);
+        assert_eq!(matched.joined.player.name, "quick");
+        assert!(matched.game.players.contains_key(&matched.joined.player.id));
+        assert_eq!(game_rooms.lock().await.lobbies.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_quick_match_creates_game() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let matched = post_quick_match(game_rooms.clone()).await;
+
+        let game_rooms = game_rooms.lock().await;
+        assert_eq!(game_rooms.lobbies.len(), 1);
+        let game = &game_rooms.lobbies[&matched.game.id];
+        assert_eq!(game.players.len(), 1);
+        assert_eq!(game.players[&matched.joined.player.id].name, "quick");
+    }
+
+    #[tokio::test]
+    async fn test_get_player_by_id() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        let player = Player::new("Player 1".to_string(), false);
+        game_rooms
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(player.clone())
+            .unwrap();
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/game/{}/player/{}", game_id, player.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Player = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body,
+            game_rooms.lock().await.lobbies[&game_id].players[&player.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_player_by_id_not_found() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        let player = Player::new("Player 1".to_string(), false);
+        game_rooms
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(player.clone())
+            .unwrap();
+
+        // unknown game
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/game/{}/player/{}", Uuid::new_v4(), player.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // unknown player
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/game/{}/player/{}", game_id, Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_game_by_id() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/game/{}", game_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Game = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body, game_rooms.lock().await.lobbies[&game_id]);
+
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("GET")
@@ -339,7 +1191,7 @@ mod tests {
 
         let game_id = game_rooms.lock().await.create_game();
         let random_id = Uuid::new_v4();
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("GET")
@@ -360,7 +1212,7 @@ mod tests {
 
         let game_id = game_rooms.lock().await.create_game();
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -380,7 +1232,7 @@ mod tests {
         assert_eq!(body.name, "test");
         assert!(!body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -400,7 +1252,7 @@ mod tests {
         assert_eq!(body.name, "player_2"); // default name because of empty username
         assert!(!body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -420,7 +1272,7 @@ mod tests {
         assert_eq!(body.name, "player_3"); // default name because of empty username
         assert!(!body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -431,29 +1283,259 @@ mod tests {
             )
             .await
             .unwrap();
-
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Player = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.name, "test_2"); // the name is taken by the first player
+        assert!(!body.is_ai);
+
+        let random_game_id = Uuid::new_v4();
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/join", random_game_id))
+                    .header("content-type", "application/json")
+                    .body(json!({ "username": "test" }).to_string().to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_join_game_sanitizes_username() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+
+        let join = |route: &'static str, username: String| {
+            let game_rooms = game_rooms.clone();
+            async move {
+                let response = test_app(game_rooms)
+                    .oneshot(
+                        Request::builder()
+                            .method("POST")
+                            .uri(format!("/game/{}/{}", game_id, route))
+                            .header("content-type", "application/json")
+                            .body(json!({ "username": username }).to_string())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<Player>(&body).unwrap().name
+            }
+        };
+
+        // too long, cut to the configured length
+        assert_eq!(
+            join("join", "a".repeat(1000)).await,
+            "a".repeat(GameConfig::default().max_username_length)
+        );
+        // nothing allowed is left, the default name is used
+        assert_eq!(join("join", "\u{1b}<>".to_string()).await, "player_2");
+        assert_eq!(join("play_again", "   ".to_string()).await, "player_3");
+        assert_eq!(
+            join("play_again", format!("{}\n", "b".repeat(30))).await,
+            "b".repeat(GameConfig::default().max_username_length)
+        );
+    }
+
+    async fn post_join(game_rooms: Arc<Mutex<GameRooms>>, game_id: Uuid) -> (StatusCode, String) {
+        post_join_route(game_rooms, game_id, "join").await
+    }
+
+    async fn post_join_route(
+        game_rooms: Arc<Mutex<GameRooms>>,
+        game_id: Uuid,
+        route: &str,
+    ) -> (StatusCode, String) {
+        let response = test_app(game_rooms)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/{}", game_id, route))
+                    .header("content-type", "application/json")
+                    .body(json!({ "username": "late" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_join_full_game_conflict() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        {
+            let mut game_rooms = game_rooms.lock().await;
+            let game = game_rooms.lobbies.get_mut(&game_id).unwrap();
+            while !game.is_full() {
+                game.add_player(Player::new("bot".to_string(), true))
+                    .unwrap();
+            }
+        }
+
+        for route in ["join", "play_again"] {
+            let (status, body) = post_join_route(game_rooms.clone(), game_id, route).await;
+
+            assert_eq!(status, StatusCode::CONFLICT);
+            assert_eq!(body, "The game is full");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_join_started_game_bad_request() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        game_rooms
+            .lock()
+            .await
+            .lobbies
+            .get_mut(&game_id)
+            .unwrap()
+            .set_game_state(GameState::Active);
+
+        let (status, body) = post_join(game_rooms, game_id).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, "The game has already started");
+    }
+
+    #[tokio::test]
+    async fn test_join_private_game() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/game")
+                    .header("content-type", "application/json")
+                    .body(json!({ "board_size": 12.0, "password": "secret" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(!String::from_utf8_lossy(&body).contains("secret"));
+        let game: Game = serde_json::from_slice(&body).unwrap();
+        assert_eq!(game.settings.board_size, 12.0);
+        assert!(game.password_hash.is_none()); // not serialized
+
+        let join = |body: serde_json::Value| {
+            test_app(game_rooms.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/join", game.id))
+                    .header("content-type", "application/json")
+                    .body(body.to_string())
+                    .unwrap(),
+            )
+        };
+
+        let response = join(json!({ "username": "a" })).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = join(json!({ "username": "a", "password": "wrong" }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = join(json!({ "username": "a", "password": "secret" }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(game_rooms.lock().await.lobbies[&game.id].players.len(), 1);
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(Request::builder().uri("/game").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(!String::from_utf8_lossy(&body).contains("password_hash"));
+    }
+
+    #[tokio::test]
+    async fn test_join_public_game_ignores_password() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/join", game_id))
+                    .header("content-type", "application/json")
+                    .body(json!({ "username": "a", "password": "whatever" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_join_game_preferred_position() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+
+        let join = |body: serde_json::Value| {
+            test_app(game_rooms.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/join", game_id))
+                    .header("content-type", "application/json")
+                    .body(body.to_string())
+                    .unwrap(),
+            )
+        };
+
+        // free preference is honored
+        let response = join(json!({ "username": "a", "preferred_position": "Right" }))
+            .await
+            .unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Player = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.position, Some(PlayerPosition::Right));
 
+        // taken preference falls back to the next free side
+        let response = join(json!({ "username": "b", "preferred_position": "Right" }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
         let body = response.into_body().collect().await.unwrap().to_bytes();
         let body: Player = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.position, Some(PlayerPosition::Top));
 
-        assert_eq!(body.name, "test");
-        assert!(!body.is_ai);
+        join(json!({ "username": "c" })).await.unwrap();
+        join(json!({ "username": "d" })).await.unwrap();
 
-        let random_game_id = Uuid::new_v4();
-        let response = app(game_rooms.clone())
-            .oneshot(
-                Request::builder()
-                    .method("POST")
-                    .uri(format!("/game/{}/join", random_game_id))
-                    .header("content-type", "application/json")
-                    .body(json!({ "username": "test" }).to_string().to_string())
-                    .unwrap(),
-            )
+        // full game ignores the preference and keeps the roster intact
+        let response = join(json!({ "username": "e", "preferred_position": "Right" }))
             .await
             .unwrap();
-
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_ne!(response.status(), StatusCode::OK);
+        let game_rooms = game_rooms.lock().await;
+        let game = &game_rooms.lobbies[&game_id];
+        assert_eq!(game.players.len(), 4);
+        assert_eq!(
+            game.get_player_by_side(PlayerPosition::Right).unwrap().name,
+            "a"
+        );
     }
 
     #[tokio::test]
@@ -462,7 +1544,7 @@ mod tests {
 
         let game_id = game_rooms.lock().await.create_game();
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -481,7 +1563,7 @@ mod tests {
         assert_eq!(body.name, "bot_1");
         assert!(body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -500,7 +1582,7 @@ mod tests {
         assert_eq!(body.name, "bot_2");
         assert!(body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -519,7 +1601,7 @@ mod tests {
         assert_eq!(body.name, "bot_3");
         assert!(body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -538,7 +1620,7 @@ mod tests {
         assert_eq!(body.name, "bot_4");
         assert!(body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -549,10 +1631,10 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
 
         let random_game_id = Uuid::new_v4();
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -566,6 +1648,64 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    async fn post_fill_bots(game_rooms: Arc<Mutex<GameRooms>>, game_id: Uuid) -> Vec<Player> {
+        let response = test_app(game_rooms)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/fill_bots", game_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fill_bots() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        game_rooms
+            .lock()
+            .await
+            .lobbies
+            .get_mut(&game_id)
+            .unwrap()
+            .add_player(Player::new("human".to_string(), false))
+            .unwrap();
+
+        let bots = post_fill_bots(game_rooms.clone(), game_id).await;
+
+        assert_eq!(bots.len(), 3);
+        assert!(bots.iter().all(|bot| bot.is_ai && bot.position.is_some()));
+        let game_rooms_lock = game_rooms.lock().await;
+        let game = game_rooms_lock.lobbies.get(&game_id).unwrap();
+        assert_eq!(game.players.len(), DEFAULT_MAX_PLAYERS);
+        assert!(game.is_full());
+    }
+
+    #[tokio::test]
+    async fn test_fill_bots_full_game() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        assert_eq!(post_fill_bots(game_rooms.clone(), game_id).await.len(), 4);
+
+        let bots = post_fill_bots(game_rooms.clone(), game_id).await;
+
+        assert!(bots.is_empty());
+        assert_eq!(
+            game_rooms.lock().await.lobbies[&game_id].players.len(),
+            DEFAULT_MAX_PLAYERS
+        );
+    }
+
     #[tokio::test]
     async fn test_remove_bot() {
         let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
@@ -582,7 +1722,7 @@ mod tests {
             .add_player(bot)
             .unwrap();
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -595,7 +1735,7 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -609,7 +1749,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
         let random_game_id = Uuid::new_v4();
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -623,13 +1763,121 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_get_leaderboard() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        for (winner, loser) in [("alice", "bob"), ("alice", "carol"), ("bob", "carol")] {
+            let mut rooms = game_rooms.lock().await;
+            let game_id = rooms.create_game();
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            let mut winner = Player::new(winner.to_string(), false);
+            winner.score = 10;
+            game.add_player(winner).unwrap();
+            game.add_player(Player::new(loser.to_string(), false))
+                .unwrap();
+            game.started_at = Some(chrono::Utc::now());
+            game.set_game_state(GameState::Finished);
+            rooms.record_results();
+        }
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/leaderboard?limit=2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Vec<LeaderboardEntry> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.len(), 2);
+        assert_eq!(body[0].name, "alice");
+        assert_eq!(body[0].wins, 2);
+        assert_eq!(body[1].name, "bob");
+        assert_eq!(body[1].wins, 1);
+        assert_eq!(body[1].losses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rematch() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        let mut player = Player::new("player".to_string(), false);
+        player.score = 10;
+        let bot = Player::new("bot".to_string(), true);
+        {
+            let mut rooms = game_rooms.lock().await;
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            game.add_player(player.clone()).unwrap();
+            game.add_player(bot.clone()).unwrap();
+        }
+
+        let rematch_request = || {
+            test_app(game_rooms.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/rematch", game_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        // game is not finished yet
+        let response = rematch_request().await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, ApiError::GameNotFinished.to_string());
+
+        game_rooms
+            .lock()
+            .await
+            .lobbies
+            .get_mut(&game_id)
+            .unwrap()
+            .set_game_state(GameState::Finished);
+
+        let response = rematch_request().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Game = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.state, GameState::WaitingForPlayers);
+        assert_eq!(body.players.len(), 2);
+        assert!(body.players.contains_key(&player.id));
+        assert!(body.players.contains_key(&bot.id));
+        assert!(body.players.values().all(|p| p.score == 0));
+
+        let random_game_id = Uuid::new_v4();
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/rematch", random_game_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_restart_game() {
         let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
 
         let game_id = game_rooms.lock().await.create_game();
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -649,7 +1897,7 @@ mod tests {
         assert_eq!(body.name, "test");
         assert!(!body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -669,7 +1917,7 @@ mod tests {
         assert_eq!(body.name, "player_2"); // default name because of empty username
         assert!(!body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -689,7 +1937,7 @@ mod tests {
         assert_eq!(body.name, "player_3"); // default name because of empty username
         assert!(!body.is_ai);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -706,11 +1954,11 @@ mod tests {
         let body = response.into_body().collect().await.unwrap().to_bytes();
         let body: Player = serde_json::from_slice(&body).unwrap();
 
-        assert_eq!(body.name, "test");
+        assert_eq!(body.name, "test_2"); // the name is taken by the first player
         assert!(!body.is_ai);
 
         let random_game_id = Uuid::new_v4();
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -727,7 +1975,7 @@ mod tests {
         let _game = game_rooms.lock().await.lobbies.get(&game_id).unwrap();
         game_rooms.lock().await.lobbies.remove(&game_id);
 
-        let response = app(game_rooms.clone())
+        let response = test_app(game_rooms.clone())
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -741,4 +1989,304 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_restart_finished_game() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        let old_player = Player::new("old".to_string(), false);
+        {
+            let mut rooms = game_rooms.lock().await;
+            let game = rooms.lobbies.get_mut(&game_id).unwrap();
+            game.add_player(old_player.clone()).unwrap();
+            game.serving = true;
+            game.start_countdown_until = Some(chrono::Utc::now());
+            game.last_anti_stall_at = Some(chrono::Utc::now());
+            game.set_game_state(GameState::Finished);
+        }
+
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/game/{}/play_again", game_id))
+                    .header("content-type", "application/json")
+                    .body(json!({ "username": "new" }).to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let rooms = game_rooms.lock().await;
+        let game = &rooms.lobbies[&game_id];
+        assert_eq!(game.state, GameState::WaitingForPlayers);
+        assert_eq!(game.players.len(), 1);
+        assert!(!game.players.contains_key(&old_player.id));
+        assert!(!game.serving);
+        assert_eq!(game.start_countdown_until, None);
+        assert_eq!(game.last_anti_stall_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_finished_game() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+
+        let game_id = game_rooms.lock().await.create_game();
+        game_rooms
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .set_game_state(GameState::Finished);
+
+        // well within the retention window, removed anyway
+        let response = test_app(game_rooms.clone())
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/game/{}", game_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!game_rooms.lock().await.lobbies.contains_key(&game_id));
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_game() {
+        let response = test_app(Arc::new(Mutex::new(GameRooms::new())))
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/game/{}", Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_delete_game_requires_admin_token() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+        let router = app(
+            game_rooms.clone(),
+            Arc::new(AtomicBool::new(true)),
+            CreationLimiter::default(),
+            AdminToken::new(Some("secret".to_string())),
+        );
+        let request = |authorization: Option<&str>| {
+            let mut request = Request::builder()
+                .method("DELETE")
+                .uri(format!("/game/{}", game_id));
+            if let Some(authorization) = authorization {
+                request = request.header(header::AUTHORIZATION, authorization);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        for authorization in [None, Some("Bearer wrong"), Some("secret")] {
+            let response = router
+                .clone()
+                .oneshot(request(authorization))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert!(game_rooms.lock().await.lobbies.contains_key(&game_id));
+        }
+
+        let response = router
+            .oneshot(request(Some("Bearer secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!game_rooms.lock().await.lobbies.contains_key(&game_id));
+    }
+
+    #[tokio::test]
+    async fn test_fill_bots_requires_admin_token() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+        let router = app(
+            game_rooms.clone(),
+            Arc::new(AtomicBool::new(true)),
+            CreationLimiter::default(),
+            AdminToken::new(Some("secret".to_string())),
+        );
+        let request = |authorization: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/game/{}/fill_bots", game_id))
+                .header(header::AUTHORIZATION, authorization)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = router
+            .clone()
+            .oneshot(request("Bearer wrong"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(game_rooms.lock().await.lobbies[&game_id].players.is_empty());
+
+        let response = router.oneshot(request("Bearer secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(game_rooms.lock().await.lobbies[&game_id].is_full());
+    }
+
+    /// A finished game with the players scored as given, in the order they joined
+    async fn finished_game(
+        game_rooms: &Arc<Mutex<GameRooms>>,
+        scores: &[(&str, u32)],
+    ) -> (Uuid, Vec<Player>) {
+        let mut game_rooms = game_rooms.lock().await;
+        let game_id = game_rooms.create_game();
+        let game = game_rooms.find_lobby_mut(game_id).unwrap();
+
+        let mut players = Vec::new();
+        for (name, score) in scores {
+            let mut player = Player::new(name.to_string(), false);
+            player.score = *score;
+            players.push(player.clone());
+            game.add_player(player).unwrap();
+        }
+        game.set_game_state(GameState::Finished);
+        (game_id, players)
+    }
+
+    async fn get_results(
+        game_rooms: Arc<Mutex<GameRooms>>,
+        uri: String,
+    ) -> axum::response::Response {
+        test_app(game_rooms)
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_game_results() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let (game_id, players) = finished_game(
+            &game_rooms,
+            &[("third", 1), ("first", 5), ("second", 3), ("also third", 1)],
+        )
+        .await;
+
+        let response = get_results(game_rooms.clone(), format!("/game/{}/results", game_id)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let results: GameResultsDto = serde_json::from_slice(&body).unwrap();
+
+        let standings: Vec<_> = results
+            .standings
+            .iter()
+            .map(|s| (s.name.as_str(), s.score, s.rank))
+            .collect();
+        assert_eq!(
+            standings,
+            vec![
+                ("first", 5, 1),
+                ("second", 3, 2),
+                ("also third", 1, 3),
+                ("third", 1, 3)
+            ]
+        );
+        assert_eq!(results.winner, Some(players[1].id));
+        assert!(results.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_game_results_tie_broken_by_who_scored_first() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let (game_id, players) =
+            finished_game(&game_rooms, &[("alice", 4), ("bob", 4), ("carol", 1)]).await;
+        {
+            let mut game_rooms = game_rooms.lock().await;
+            let game = game_rooms.find_lobby_mut(game_id).unwrap();
+            let now = chrono::Utc::now();
+            for (player, seconds_ago) in players.iter().zip([0, 5, 10]) {
+                game.players.get_mut(&player.id).unwrap().last_score_at =
+                    Some(now - chrono::Duration::seconds(seconds_ago));
+            }
+        }
+
+        let response = get_results(game_rooms.clone(), format!("/game/{}/results", game_id)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let results: GameResultsDto = serde_json::from_slice(&body).unwrap();
+
+        let standings: Vec<_> = results
+            .standings
+            .iter()
+            .map(|s| (s.name.as_str(), s.score, s.rank))
+            .collect();
+        // bob got to 4 before alice did
+        assert_eq!(
+            standings,
+            vec![("bob", 4, 1), ("alice", 4, 2), ("carol", 1, 3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_game_results_csv() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let (game_id, players) =
+            finished_game(&game_rooms, &[("second", 2), ("first, \"the\" best", 4)]).await;
+
+        let response = get_results(
+            game_rooms.clone(),
+            format!("/game/{}/results?format=csv", game_id),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            format!(
+                "rank,player_id,name,score\n1,{},\"first, \"\"the\"\" best\",4\n2,{},second,2\n",
+                players[1].id, players[0].id
+            )
+        );
+
+        let response = get_results(
+            game_rooms.clone(),
+            format!("/game/{}/results?format=xml", game_id),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_game_results_not_finished() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+
+        for uri in [
+            format!("/game/{}/results", game_id),
+            format!("/game/{}/results?format=csv", game_id),
+        ] {
+            let response = get_results(game_rooms.clone(), uri).await;
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+        }
+    }
 }
@@ -1,3 +1,14 @@
+mod admin;
+mod error;
 mod handlers;
+mod rate_limit;
+mod ws;
 
-pub use handlers::{add_bot, app, create_game, get_game_by_id, get_games, join_game};
+pub use admin::AdminToken;
+pub use error::ApiError;
+pub use handlers::{
+    add_bot, app, create_game, fill_bots, get_game_by_id, get_games, get_player_by_id, join_game,
+    server_info_router, AppState,
+};
+pub use rate_limit::{CreationLimiter, DEFAULT_CREATE_GAME_BURST, DEFAULT_CREATE_GAME_INTERVAL};
+pub use ws::{ws_router, WsClients, WsState};
@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+pub const DEFAULT_CREATE_GAME_BURST: u32 = 5;
+pub const DEFAULT_CREATE_GAME_INTERVAL: Duration = Duration::from_secs(10);
+/// Addresses tracked at most, the ones with a full bucket are forgotten first,
+/// then the least recently seen
+const MAX_TRACKED_ADDRESSES: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Token bucket per source address, each created game takes a token,
+/// one token comes back every `interval` up to `burst`
+pub struct CreationLimiter {
+    burst: u32,
+    interval: Duration,
+    buckets: HashMap<IpAddr, Bucket>,
+}
+
+impl Default for CreationLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_CREATE_GAME_BURST, DEFAULT_CREATE_GAME_INTERVAL)
+    }
+}
+
+impl CreationLimiter {
+    pub fn new(burst: u32, interval: Duration) -> Self {
+        Self {
+            burst: burst.max(1),
+            interval: interval.max(Duration::from_millis(1)),
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let refill =
+            now.duration_since(bucket.updated_at).as_secs_f64() / self.interval.as_secs_f64();
+        (bucket.tokens + refill).min(self.burst as f64)
+    }
+
+    /// Takes a token of `ip`, false when it has none left
+    pub fn try_acquire(&mut self, ip: IpAddr, now: Instant) -> bool {
+        if self.buckets.len() >= MAX_TRACKED_ADDRESSES && !self.buckets.contains_key(&ip) {
+            self.make_room(now);
+        }
+
+        let tokens = match self.buckets.get(&ip) {
+            Some(bucket) => self.refilled(bucket, now),
+            None => self.burst as f64,
+        };
+        let allowed = tokens >= 1.0;
+        self.buckets.insert(
+            ip,
+            Bucket {
+                tokens: if allowed { tokens - 1.0 } else { tokens },
+                updated_at: now,
+            },
+        );
+        allowed
+    }
+
+    /// Forgets the addresses with a full bucket, or the least recently seen one
+    /// when all of them are still limited, so a source rotating addresses cannot grow the map
+    fn make_room(&mut self, now: Instant) {
+        let burst = self.burst as f64;
+        let buckets = std::mem::take(&mut self.buckets);
+        self.buckets = buckets
+            .into_iter()
+            .filter(|(_, bucket)| self.refilled(bucket, now) < burst)
+            .collect();
+
+        if self.buckets.len() >= MAX_TRACKED_ADDRESSES {
+            if let Some(oldest) = self
+                .buckets
+                .iter()
+                .min_by_key(|(_, bucket)| bucket.updated_at)
+                .map(|(ip, _)| *ip)
+            {
+                self.buckets.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_runs_out_and_refills() {
+        let mut limiter = CreationLimiter::new(2, Duration::from_secs(10));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire(ip, now));
+        assert!(limiter.try_acquire(ip, now));
+        assert!(!limiter.try_acquire(ip, now));
+        // other addresses have their own bucket
+        assert!(limiter.try_acquire(other, now));
+
+        assert!(!limiter.try_acquire(ip, now + Duration::from_secs(5)));
+        assert!(limiter.try_acquire(ip, now + Duration::from_secs(11)));
+        assert!(!limiter.try_acquire(ip, now + Duration::from_secs(11)));
+    }
+
+    #[test]
+    fn test_tracked_addresses_bounded() {
+        let mut limiter = CreationLimiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+        let first: IpAddr = "::1".parse().unwrap();
+        assert!(limiter.try_acquire(first, now));
+
+        // every address keeps a used bucket, none of them can simply be forgotten
+        for i in 1..=MAX_TRACKED_ADDRESSES as u128 {
+            let ip = IpAddr::from(std::net::Ipv6Addr::from(i + 1));
+            assert!(limiter.try_acquire(ip, now + Duration::from_millis(1)));
+        }
+
+        assert_eq!(limiter.buckets.len(), MAX_TRACKED_ADDRESSES);
+        // the least recently seen address went first
+        assert!(!limiter.buckets.contains_key(&first));
+    }
+}
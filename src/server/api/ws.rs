@@ -0,0 +1,344 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Path, State,
+    },
+    http::StatusCode,
+    response::Response,
+    routing::get,
+    Router,
+};
+use futures_util::{stream::SplitStream, SinkExt, StreamExt};
+use log::{debug, error, info};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    Mutex,
+};
+use uuid::Uuid;
+
+use crate::common::{
+    game_loop::InputQueue,
+    models::{ClientInput, ClientInputType, ClientInputWithAddr},
+    GameRooms,
+};
+
+/// Game states waiting to be written to a socket, a client reading slower than that misses some
+const MAX_QUEUED_STATES: usize = 16;
+/// How long an upgraded socket has to send its `JoinGame` before it is closed
+const IDENTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outgoing game states of players connected over WebSocket instead of UDP, by player id
+#[derive(Clone, Default)]
+pub struct WsClients {
+    senders: Arc<Mutex<HashMap<Uuid, mpsc::Sender<Vec<u8>>>>>,
+}
+
+impl WsClients {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a serialized game state for the player, false when they don't use WebSocket.
+    /// A full queue drops the state, the socket is behind and only writes the newest it holds
+    pub async fn send(&self, player_id: &Uuid, bytes: &[u8]) -> bool {
+        match self.senders.lock().await.get(player_id) {
+            Some(sender) => match sender.try_send(bytes.to_vec()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    debug!(
+                        "Dropped a game state for slow WebSocket player {}",
+                        player_id
+                    );
+                    true
+                }
+                Err(TrySendError::Closed(_)) => false,
+            },
+            None => false,
+        }
+    }
+
+    async fn register(&self, player_id: Uuid) -> mpsc::Receiver<Vec<u8>> {
+        let (sender, receiver) = mpsc::channel(MAX_QUEUED_STATES);
+        self.senders.lock().await.insert(player_id, sender);
+        receiver
+    }
+
+    async fn unregister(&self, player_id: &Uuid) {
+        self.senders.lock().await.remove(player_id);
+    }
+}
+
+#[derive(Clone)]
+pub struct WsState {
+    pub game_rooms: Arc<Mutex<GameRooms>>,
+    pub message_queue: Arc<Mutex<InputQueue>>,
+    pub clients: WsClients,
+}
+
+pub async fn game_ws(
+    State(state): State<WsState>,
+    Path(game_id): Path<String>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, StatusCode> {
+    let game_uuid = Uuid::parse_str(&game_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    if !state
+        .game_rooms
+        .lock()
+        .await
+        .lobbies
+        .contains_key(&game_uuid)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state, game_uuid, addr)))
+}
+
+fn decode_input(message: Message) -> Option<ClientInput> {
+    match message {
+        Message::Binary(bytes) => rmp_serde::from_slice(&bytes)
+            .inspect_err(|e| error!("Failed to deserialize WebSocket message: {}", e))
+            .ok(),
+        _ => None,
+    }
+}
+
+/// The first message must be the `JoinGame` of a player of this game,
+/// sent within `IDENTIFY_TIMEOUT`
+async fn identify(
+    stream: &mut SplitStream<WebSocket>,
+    state: &WsState,
+    game_id: Uuid,
+) -> Option<(Uuid, ClientInput)> {
+    let message = tokio::time::timeout(IDENTIFY_TIMEOUT, stream.next())
+        .await
+        .ok()??;
+    let input = decode_input(message.ok()?)?;
+    if input.action != ClientInputType::JoinGame || input.game_id != game_id.to_string() {
+        return None;
+    }
+
+    let player_id = Uuid::parse_str(&input.player_id).ok()?;
+    let game_rooms = state.game_rooms.lock().await;
+    let player = game_rooms.lobbies.get(&game_id)?.get_player(&player_id)?;
+    if !player.accepts_reconnect_token(input.reconnect_token.as_deref()) {
+        return None;
+    }
+
+    Some((player_id, input))
+}
+
+/// Feeds inputs into the game loop queue and streams the broadcast game states back
+async fn handle_socket(socket: WebSocket, state: WsState, game_id: Uuid, addr: SocketAddr) {
+    let (mut sink, mut stream) = socket.split();
+
+    let Some((player_id, join)) = identify(&mut stream, &state, game_id).await else {
+        debug!(
+            "game {}: WebSocket closed without a valid JoinGame",
+            game_id
+        );
+        let _ = sink.close().await;
+        return;
+    };
+    info!(
+        "game {}: player {} connected over WebSocket",
+        game_id, player_id
+    );
+
+    let mut outgoing = state.clients.register(player_id).await;
+    // the connection proved it may act for the player, its inputs may come from a new address
+    let reconnect_token = join.reconnect_token.clone();
+    state
+        .message_queue
+        .lock()
+        .await
+        .push(ClientInputWithAddr { addr, input: join });
+
+    let mut send_task = tokio::spawn(async move {
+        while let Some(mut bytes) = outgoing.recv().await {
+            // a client that fell behind only needs the latest state
+            while let Ok(newer) = outgoing.try_recv() {
+                bytes = newer;
+            }
+            if sink.send(Message::Binary(bytes)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let message_queue = state.message_queue.clone();
+    let mut recv_task = tokio::spawn(async move {
+        while let Some(Ok(message)) = stream.next().await {
+            if let Message::Close(_) = message {
+                break;
+            }
+            match decode_input(message) {
+                // a connection only speaks for the player it identified as
+                Some(mut input)
+                    if input.player_id == player_id.to_string()
+                        && input.game_id == game_id.to_string() =>
+                {
+                    input.reconnect_token = input.reconnect_token.or(reconnect_token.clone());
+                    message_queue
+                        .lock()
+                        .await
+                        .push(ClientInputWithAddr { addr, input });
+                }
+                Some(_) => debug!("Ignored WebSocket input for another player"),
+                None => {}
+            }
+        }
+    });
+
+    tokio::select! {
+        _ = &mut send_task => recv_task.abort(),
+        _ = &mut recv_task => send_task.abort(),
+    }
+
+    state.clients.unregister(&player_id).await;
+    info!("game {}: player {} WebSocket closed", game_id, player_id);
+}
+
+pub fn ws_router(state: WsState) -> Router {
+    Router::new()
+        .route("/game/:id/ws", get(game_ws)) // game states and inputs for clients without UDP
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::net::ws::WsClient;
+    use crate::common::models::{ClientInputType, Direction};
+    use crate::common::Player;
+
+    async fn wait_for_input(message_queue: &Arc<Mutex<InputQueue>>) -> ClientInput {
+        tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                if let Some(input) = message_queue.lock().await.pop() {
+                    return input.input;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ws_handshake_and_round_trip() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+        let player = Player::new("Player 1".to_string(), false);
+        game_rooms
+            .lock()
+            .await
+            .find_lobby_mut(game_id)
+            .unwrap()
+            .add_player(player.clone())
+            .unwrap();
+
+        let state = WsState {
+            game_rooms: game_rooms.clone(),
+            message_queue: Arc::new(Mutex::new(InputQueue::default())),
+            clients: WsClients::new(),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api_url = format!("http://{}", listener.local_addr().unwrap());
+        let app = ws_router(state.clone()).into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = WsClient::new(&api_url, game_id);
+        let input = |action| ClientInput::new(game_id.to_string(), player.id.to_string(), action);
+
+        // the join identifies the connection and is forwarded to the game loop
+        client
+            .send_client_input(input(ClientInputType::JoinGame))
+            .await
+            .unwrap();
+        let join = wait_for_input(&state.message_queue).await;
+        assert_eq!(join.action, ClientInputType::JoinGame);
+        assert_eq!(join.player_id, player.id.to_string());
+
+        client
+            .send_client_input(input(ClientInputType::MovePaddle(Direction::Positive)))
+            .await
+            .unwrap();
+        let moved = wait_for_input(&state.message_queue).await;
+        assert_eq!(
+            moved.action,
+            ClientInputType::MovePaddle(Direction::Positive)
+        );
+
+        // broadcast game states reach the client over the socket
+        let game = game_rooms.lock().await.lobbies[&game_id].clone();
+        assert!(
+            state
+                .clients
+                .send(&player.id, &game.to_network_bytes().unwrap())
+                .await
+        );
+        let received = tokio::time::timeout(Duration::from_secs(2), client.recv_updated_game())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.id, game_id);
+        assert!(received.players.contains_key(&player.id));
+    }
+
+    #[tokio::test]
+    async fn test_slow_reader_queue_bounded() {
+        let clients = WsClients::new();
+        let player_id = Uuid::new_v4();
+        let mut receiver = clients.register(player_id).await;
+
+        // nobody reads, the states past the limit are dropped but the player stays on WebSocket
+        for i in 0..MAX_QUEUED_STATES + 5 {
+            assert!(clients.send(&player_id, &[i as u8]).await);
+        }
+        let mut queued = Vec::new();
+        while let Ok(bytes) = receiver.try_recv() {
+            queued.push(bytes[0] as usize);
+        }
+        assert_eq!(queued, (0..MAX_QUEUED_STATES).collect::<Vec<_>>());
+
+        drop(receiver);
+        assert!(!clients.send(&player_id, &[0]).await);
+        assert!(!clients.send(&Uuid::new_v4(), &[0]).await);
+    }
+
+    #[tokio::test]
+    async fn test_ws_rejects_unknown_player() {
+        let game_rooms = Arc::new(Mutex::new(GameRooms::new()));
+        let game_id = game_rooms.lock().await.create_game();
+
+        let state = WsState {
+            game_rooms,
+            message_queue: Arc::new(Mutex::new(InputQueue::default())),
+            clients: WsClients::new(),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api_url = format!("http://{}", listener.local_addr().unwrap());
+        let app = ws_router(state.clone()).into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = WsClient::new(&api_url, game_id);
+        client
+            .send_client_input(ClientInput::new(
+                game_id.to_string(),
+                Uuid::new_v4().to_string(),
+                ClientInputType::JoinGame,
+            ))
+            .await
+            .unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(2), client.recv_updated_game())
+            .await
+            .unwrap();
+        assert!(result.is_err());
+        assert!(state.message_queue.lock().await.is_empty());
+    }
+}